@@ -0,0 +1,188 @@
+use crate::interval::Interval;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::vector::{Axis, Vec3};
+
+// Minimum thickness given to the boxes of flat objects, such as rectangles,
+// triangles and disks, which would otherwise have boxes no ray can hit.
+#[cfg(feature = "std")]
+pub(crate) const BOX_PADDING: f64 = 0.0001;
+
+// Axis-aligned bounding box, the region between the corners min and max.
+// A box with min above max on any axis is empty.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    min: Point3,
+    max: Point3,
+}
+
+impl Aabb {
+    pub const EMPTY: Aabb = Aabb {
+        min: Point3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        max: Point3::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+    };
+
+    // The box spanned by any two opposite corners.
+    pub fn new(a: Point3, b: Point3) -> Aabb {
+        let (a, b) = (a.to_vec3(), b.to_vec3());
+        Aabb { min: Point3::from_vec3(a.min(b)), max: Point3::from_vec3(a.max(b)) }
+    }
+
+    pub const fn min(&self) -> Point3 {
+        self.min
+    }
+
+    pub const fn max(&self) -> Point3 {
+        self.max
+    }
+
+    pub fn axis(&self, axis: Axis) -> Interval {
+        Interval::new(self.min.to_vec3().get(axis), self.max.to_vec3().get(axis))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        Axis::ALL.iter().any(|&axis| self.axis(axis).is_empty())
+    }
+
+    // The smallest box containing both.
+    pub fn surrounding_box(a: Aabb, b: Aabb) -> Aabb {
+        Aabb {
+            min: Point3::from_vec3(a.min.to_vec3().min(b.min.to_vec3())),
+            max: Point3::from_vec3(a.max.to_vec3().max(b.max.to_vec3())),
+        }
+    }
+
+    // Grows any axis thinner than min_size to min_size, keeping it centered.
+    // Flat objects such as axis-aligned rectangles would otherwise give boxes
+    // that no ray can hit.
+    pub fn padded(&self, min_size: f64) -> Aabb {
+        let (mut min, mut max) = (self.min.to_vec3(), self.max.to_vec3());
+        for &axis in &Axis::ALL {
+            let interval = self.axis(axis);
+            if interval.size() < min_size {
+                let padded = interval.expand(min_size - interval.size());
+                min.set(axis, padded.min);
+                max.set(axis, padded.max);
+            }
+        }
+        Aabb { min: Point3::from_vec3(min), max: Point3::from_vec3(max) }
+    }
+
+    pub fn size(&self) -> Vec3 {
+        if self.is_empty() { Vec3::ZERO } else { self.max - self.min }
+    }
+
+    // The axis the box is longest along; X if there is a tie.
+    pub fn longest_axis(&self) -> Axis {
+        let size = self.size();
+        if size.x() >= size.y() && size.x() >= size.z() {
+            Axis::X
+        }
+        else if size.y() >= size.z() {
+            Axis::Y
+        }
+        else {
+            Axis::Z
+        }
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        let size = self.size();
+        2.0 * (size.x() * size.y() + size.y() * size.z() + size.z() * size.x())
+    }
+
+    pub fn centroid(&self) -> Point3 {
+        self.min.lerp(self.max, 0.5)
+    }
+
+    // Slab test: the ray is inside the box where the t ranges between each
+    // pair of planes overlap. Only says whether the ray hits, not where.
+    pub fn hit(&self, ray: &Ray, t_range: Interval) -> bool {
+        let (origin, direction) = (ray.origin().to_vec3(), ray.direction());
+        let (mut t_min, mut t_max) = (t_range.min, t_range.max);
+        for &axis in &Axis::ALL {
+            let inverse = 1.0 / direction.get(axis);
+            let mut t0 = (self.min.to_vec3().get(axis) - origin.get(axis)) * inverse;
+            let mut t1 = (self.max.to_vec3().get(axis) - origin.get(axis)) * inverse;
+            if inverse < 0.0 {
+                core::mem::swap(&mut t0, &mut t1);
+            }
+            // max and min skip the NaN from a ray lying in one of the planes.
+            t_min = t0.max(t_min);
+            t_max = t1.min(t_max);
+            if t_max <= t_min {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use super::*;
+
+    fn unit_cube() -> Aabb {
+        Aabb::new(Point3::new(1.0, 1.0, 1.0), Point3::ORIGIN)
+    }
+
+    #[test]
+    fn new_orders_the_corners() {
+        let aabb = Aabb::new(Point3::new(1.0, -2.0, 3.0), Point3::new(-1.0, 2.0, 0.0));
+        assert_vec3_equal!(Point3::new(-1.0, -2.0, 0.0), aabb.min());
+        assert_vec3_equal!(Point3::new(1.0, 2.0, 3.0), aabb.max());
+        assert!(aabb.axis(Axis::Y) == Interval::new(-2.0, 2.0));
+    }
+
+    #[test]
+    fn surrounding_box() {
+        let a = unit_cube();
+        let b = Aabb::new(Point3::new(2.0, -1.0, 0.5), Point3::new(3.0, 0.0, 0.5));
+        let result = Aabb::surrounding_box(a, b);
+        assert_vec3_equal!(Point3::new(0.0, -1.0, 0.0), result.min());
+        assert_vec3_equal!(Point3::new(3.0, 1.0, 1.0), result.max());
+        assert!(Aabb::surrounding_box(a, Aabb::EMPTY) == a);
+    }
+
+    #[test]
+    fn surface_area_and_centroid() {
+        let aabb = Aabb::new(Point3::new(1.0, 2.0, 3.0), Point3::new(2.0, 4.0, 6.0));
+        assert_approx_eq!(2.0 * (2.0 + 6.0 + 3.0), aabb.surface_area());
+        assert_vec3_equal!(Point3::new(1.5, 3.0, 4.5), aabb.centroid());
+        assert!(Aabb::EMPTY.is_empty());
+        assert_eq!(0.0, Aabb::EMPTY.surface_area());
+    }
+
+    #[test]
+    fn padded() {
+        let flat = Aabb::new(Point3::new(0.0, 0.0, 2.0), Point3::new(1.0, 1.0, 2.0));
+        let padded = flat.padded(0.1);
+        assert_vec3_equal!(Point3::new(0.0, 0.0, 1.95), padded.min());
+        assert_vec3_equal!(Point3::new(1.0, 1.0, 2.05), padded.max());
+        assert!(!flat.hit(&Ray::new(Point3::new(0.5, 0.5, 5.0), -Vec3::Z), Interval::UNIVERSE));
+        assert!(padded.hit(&Ray::new(Point3::new(0.5, 0.5, 5.0), -Vec3::Z), Interval::UNIVERSE));
+        assert!(unit_cube().padded(0.1) == unit_cube());
+    }
+
+    #[test]
+    fn longest_axis() {
+        assert_eq!(Axis::X, unit_cube().longest_axis());
+        assert_eq!(Axis::Y, Aabb::new(Point3::ORIGIN, Point3::new(1.0, 3.0, 2.0)).longest_axis());
+        assert_eq!(Axis::Z, Aabb::new(Point3::ORIGIN, Point3::new(1.0, 1.0, -2.0)).longest_axis());
+    }
+
+    #[test]
+    fn hit() {
+        let aabb = unit_cube();
+        let forward = Interval::new(0.0, f64::INFINITY);
+        assert!(aabb.hit(&Ray::new(Point3::new(0.5, 0.5, 5.0), -Vec3::Z), forward));
+        assert!(aabb.hit(&Ray::new(Point3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0)), forward));
+        // Starting inside.
+        assert!(aabb.hit(&Ray::new(Point3::new(0.5, 0.5, 0.5), Vec3::X), forward));
+        assert!(!aabb.hit(&Ray::new(Point3::new(0.5, 0.5, 5.0), Vec3::Z), forward));
+        assert!(!aabb.hit(&Ray::new(Point3::new(2.0, 0.5, 5.0), -Vec3::Z), forward));
+        assert!(!aabb.hit(&Ray::new(Point3::new(0.5, 0.5, 5.0), -Vec3::Z), Interval::new(0.0, 3.0)));
+        assert!(!Aabb::EMPTY.hit(&Ray::new(Point3::ORIGIN, Vec3::X), forward));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::color::Color;
+use crate::ray::Ray;
+
+// The light arriving along rays that hit nothing. Closures taking the ray
+// work as backgrounds too.
+pub trait Background: Send + Sync {
+    fn color(&self, ray: &Ray) -> Color;
+}
+
+impl<F: Fn(&Ray) -> Color + Send + Sync> Background for F {
+    fn color(&self, ray: &Ray) -> Color {
+        self(ray)
+    }
+}
+
+// The same color in every direction. Black is no background at all, for
+// scenes lit only by their own lights.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SolidColor(pub Color);
+
+impl SolidColor {
+    pub const BLACK: SolidColor = SolidColor(Color::BLACK);
+}
+
+impl Background for SolidColor {
+    fn color(&self, _ray: &Ray) -> Color {
+        self.0
+    }
+}
+
+// Blends linearly from bottom, straight down, to top, straight up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VerticalGradient {
+    pub bottom: Color,
+    pub top: Color,
+}
+
+impl VerticalGradient {
+    pub const fn new(bottom: Color, top: Color) -> VerticalGradient {
+        VerticalGradient { bottom, top }
+    }
+
+    // White straight down blending to blue straight up, as in the baseline
+    // gradient.
+    pub const fn sky() -> VerticalGradient {
+        VerticalGradient::new(Color::WHITE, Color::new(0.5, 0.7, 1.0))
+    }
+}
+
+impl Background for VerticalGradient {
+    fn color(&self, ray: &Ray) -> Color {
+        let unit_direction = ray.direction().unit_vector();
+        let t = 0.5 * (unit_direction.y() + 1.0);
+        (1.0 - t) * self.bottom + t * self.top
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point::Point3;
+    use crate::vector::Vec3;
+    use super::*;
+
+    fn ray(direction: Vec3) -> Ray {
+        Ray::new(Point3::ORIGIN, direction)
+    }
+
+    #[test]
+    fn solid_color() {
+        let background = SolidColor(Color::new(0.1, 0.2, 0.3));
+        assert!(background.color(&ray(Vec3::X)) == Color::new(0.1, 0.2, 0.3));
+        assert!(SolidColor::BLACK.color(&ray(-Vec3::Y)) == Color::BLACK);
+    }
+
+    #[test]
+    fn vertical_gradient() {
+        let background = VerticalGradient::new(Color::BLACK, Color::WHITE);
+        assert_vec3_equal!(Vec3::from(Color::WHITE), Vec3::from(background.color(&ray(Vec3::new(0.0, 3.0, 0.0)))));
+        assert_vec3_equal!(Vec3::from(Color::BLACK), Vec3::from(background.color(&ray(-Vec3::Y))));
+        assert_vec3_equal!(Vec3::from(Color::new(0.5, 0.5, 0.5)), Vec3::from(background.color(&ray(Vec3::Z))));
+    }
+
+    #[test]
+    fn closure_background() {
+        let backgrounds: [&dyn Background; 2] = [
+            &|ray: &Ray| if ray.direction().y() > 0.0 { Color::WHITE } else { Color::BLACK },
+            &VerticalGradient::sky()
+        ];
+        assert!(backgrounds[0].color(&ray(Vec3::Y)) == Color::WHITE);
+        assert!(backgrounds[0].color(&ray(-Vec3::Y)) == Color::BLACK);
+        assert_vec3_equal!(Vec3::from(Color::new(0.5, 0.7, 1.0)), Vec3::from(backgrounds[1].color(&ray(Vec3::Y))));
+    }
+}
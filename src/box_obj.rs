@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use crate::aabb::{Aabb, BOX_PADDING};
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::vector::{Axis, Vec3};
+
+// A solid axis-aligned box, e.g. the blocks in a Cornell box. Named BoxObj
+// so it doesn't clash with std's Box.
+#[derive(Clone, Debug)]
+pub struct BoxObj {
+    aabb: Aabb,
+    material: Arc<dyn Material>,
+}
+
+impl BoxObj {
+    // The box spanned by any two opposite corners.
+    pub fn new(a: Point3, b: Point3, material: Arc<dyn Material>) -> BoxObj {
+        BoxObj { aabb: Aabb::new(a, b), material }
+    }
+
+    pub fn min(&self) -> Point3 {
+        self.aabb.min()
+    }
+
+    pub fn max(&self) -> Point3 {
+        self.aabb.max()
+    }
+}
+
+impl Hittable for BoxObj {
+    // The slab test from Aabb, also remembering which face the ray enters
+    // and leaves through. A ray starting inside hits the face it leaves by.
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        let (origin, direction) = (ray.origin().to_vec3(), ray.direction());
+        let (mut t_enter, mut enter_axis) = (f64::NEG_INFINITY, Axis::X);
+        let (mut t_exit, mut exit_axis) = (f64::INFINITY, Axis::X);
+        for &axis in &Axis::ALL {
+            let inverse = 1.0 / direction.get(axis);
+            let mut t0 = (self.aabb.min().to_vec3().get(axis) - origin.get(axis)) * inverse;
+            let mut t1 = (self.aabb.max().to_vec3().get(axis) - origin.get(axis)) * inverse;
+            if inverse < 0.0 {
+                core::mem::swap(&mut t0, &mut t1);
+            }
+            // NaN, from a ray lying in a face's plane, fails both tests.
+            if t0 > t_enter {
+                t_enter = t0;
+                enter_axis = axis;
+            }
+            if t1 < t_exit {
+                t_exit = t1;
+                exit_axis = axis;
+            }
+        }
+        if t_enter > t_exit {
+            return None;
+        }
+
+        // Faces' outward normals point against the ray where it enters and
+        // along it where it leaves.
+        let (t, axis, sign) = if t_range.surrounds(t_enter) {
+            (t_enter, enter_axis, -direction.get(enter_axis).signum())
+        }
+        else if t_range.surrounds(t_exit) {
+            (t_exit, exit_axis, direction.get(exit_axis).signum())
+        }
+        else {
+            return None;
+        };
+        let mut outward_normal = Vec3::ZERO;
+        outward_normal.set(axis, sign);
+        Some(HitRecord::new(ray, t, outward_normal, &*self.material))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.aabb.padded(BOX_PADDING))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use super::*;
+
+    fn cube() -> BoxObj {
+        BoxObj::new(Point3::new(1.0, 1.0, 1.0), Point3::new(-1.0, -1.0, -1.0), Arc::new(Black))
+    }
+
+    #[test]
+    fn every_face_has_its_own_normal() {
+        let cube = cube();
+        for &axis in &Axis::ALL {
+            for &sign in &[1.0, -1.0] {
+                let mut normal = Vec3::ZERO;
+                normal.set(axis, sign);
+                let record = cube.hit(&Ray::new(Point3::from_vec3(normal * 5.0), -normal), Interval::FORWARD).unwrap();
+                assert_approx_eq!(4.0, record.t);
+                assert!(record.front_face);
+                assert_vec3_equal!(normal, record.normal);
+                assert_vec3_equal!(Point3::from_vec3(normal), record.point);
+            }
+        }
+    }
+
+    #[test]
+    fn oblique_hit() {
+        let ray = Ray::new(Point3::new(-3.0, 0.5, 0.0), Vec3::new(2.0, 0.0, 0.5));
+        let cube = cube();
+        let record = cube.hit(&ray, Interval::FORWARD).unwrap();
+        assert_approx_eq!(1.0, record.t);
+        assert_vec3_equal!(-Vec3::X, record.normal);
+        assert_vec3_equal!(Point3::new(-1.0, 0.5, 0.5), record.point);
+    }
+
+    #[test]
+    fn hit_from_inside() {
+        let ray = Ray::new(Point3::new(0.0, 0.5, 0.0), Vec3::new(0.0, 0.0, -2.0));
+        let cube = cube();
+        let record = cube.hit(&ray, Interval::FORWARD).unwrap();
+        assert_approx_eq!(0.5, record.t);
+        assert!(!record.front_face);
+        assert_vec3_equal!(Vec3::Z, record.normal);
+    }
+
+    #[test]
+    fn misses() {
+        let cube = cube();
+        assert!(cube.hit(&Ray::new(Point3::new(-3.0, 2.0, 0.0), Vec3::X), Interval::FORWARD).is_none());
+        assert!(cube.hit(&Ray::new(Point3::new(-3.0, 0.0, 0.0), -Vec3::X), Interval::FORWARD).is_none());
+        assert!(cube.hit(&Ray::new(Point3::new(-3.0, 0.0, 0.0), Vec3::X), Interval::new(0.001, 1.0)).is_none());
+        assert!(cube.hit(&Ray::new(Point3::new(-3.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0)), Interval::FORWARD).is_none());
+    }
+
+    #[test]
+    fn bounding_box() {
+        let aabb = cube().bounding_box().unwrap();
+        assert_vec3_equal!(Point3::new(-1.0, -1.0, -1.0), aabb.min());
+        assert_vec3_equal!(Point3::new(1.0, 1.0, 1.0), aabb.max());
+    }
+}
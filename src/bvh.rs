@@ -0,0 +1,291 @@
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::hittable_list::HittableList;
+use crate::interval::Interval;
+use crate::ray::Ray;
+use crate::vector::Axis;
+
+// Bounding volume hierarchy: a binary tree of boxes around the objects.
+// A ray that misses a node's box skips everything below it, so hitting the
+// scene takes about log n box tests instead of n object tests.
+pub struct BvhNode {
+    aabb: Aabb,
+    left: Box<dyn Hittable>,
+    right: Option<Box<dyn Hittable>>,
+}
+
+// How BvhNode decides where to split. Median splits build fastest; the
+// surface area heuristic takes longer to build but gives trees that are
+// faster to trace, especially for meshes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SplitMethod {
+    // Halve the objects along the axis their centers spread most on.
+    Median,
+    // Pick the split with the lowest expected cost, estimated from the
+    // surface areas of the two halves, among bucketed candidates.
+    #[default]
+    Sah,
+}
+
+// SAH candidates per axis.
+const BINS: usize = 12;
+
+impl BvhNode {
+    // Built with the surface area heuristic. None if there are no objects
+    // or any of them is unbounded.
+    pub fn new(objects: Vec<Box<dyn Hittable>>) -> Option<BvhNode> {
+        BvhNode::with_split_method(objects, SplitMethod::Sah)
+    }
+
+    pub fn with_split_method(objects: Vec<Box<dyn Hittable>>, method: SplitMethod) -> Option<BvhNode> {
+        let objects = objects.into_iter()
+            .map(|object| object.bounding_box().map(|aabb| (aabb, object)))
+            .collect::<Option<Vec<_>>>()?;
+        if objects.is_empty() {
+            return None;
+        }
+        Some(BvhNode::build(objects, method))
+    }
+
+    pub fn from_list(list: HittableList) -> Option<BvhNode> {
+        BvhNode::new(list.into_objects())
+    }
+
+    fn build(mut objects: Vec<(Aabb, Box<dyn Hittable>)>, method: SplitMethod) -> BvhNode {
+        let aabb = objects.iter().fold(Aabb::EMPTY, |aabb, (object_box, _)| Aabb::surrounding_box(aabb, *object_box));
+        if objects.len() <= 2 {
+            let right = if objects.len() == 2 { objects.pop().map(|(_, object)| object) } else { None };
+            let (_, left) = objects.pop().unwrap();
+            return BvhNode { aabb, left, right };
+        }
+
+        let right = match method {
+            SplitMethod::Median => None,
+            SplitMethod::Sah => split_sah(&mut objects)
+        };
+        // SAH finds no split when all the centers coincide.
+        let right = right.unwrap_or_else(|| split_median(&mut objects));
+        BvhNode {
+            aabb,
+            left: Box::new(BvhNode::build(objects, method)),
+            right: Some(Box::new(BvhNode::build(right, method))),
+        }
+    }
+}
+
+fn centroid_bounds(objects: &[(Aabb, Box<dyn Hittable>)]) -> Aabb {
+    objects.iter().fold(Aabb::EMPTY, |centroids, (object_box, _)| {
+        Aabb::surrounding_box(centroids, Aabb::new(object_box.centroid(), object_box.centroid()))
+    })
+}
+
+// Sorts along the axis the centers are most spread out on and returns the
+// back half, leaving the front half in objects.
+fn split_median(objects: &mut Vec<(Aabb, Box<dyn Hittable>)>) -> Vec<(Aabb, Box<dyn Hittable>)> {
+    let axis = centroid_bounds(objects).longest_axis();
+    objects.sort_by(|(a, _), (b, _)| {
+        a.centroid().to_vec3().get(axis).total_cmp(&b.centroid().to_vec3().get(axis))
+    });
+    objects.split_off(objects.len() / 2)
+}
+
+// Buckets the centers along each axis and splits between the buckets where
+// count times surface area, summed over both sides, is lowest. Returns the
+// objects on the far side of the split, or None if there is no split.
+fn split_sah(objects: &mut Vec<(Aabb, Box<dyn Hittable>)>) -> Option<Vec<(Aabb, Box<dyn Hittable>)>> {
+    let centroids = centroid_bounds(objects);
+    let mut best: Option<(f64, Axis, usize)> = None;
+    for &axis in &Axis::ALL {
+        let extent = centroids.axis(axis);
+        if extent.size() <= 0.0 {
+            continue;
+        }
+        let mut counts = [0usize; BINS];
+        let mut boxes = [Aabb::EMPTY; BINS];
+        for (object_box, _) in objects.iter() {
+            let i = bin(object_box, axis, extent);
+            counts[i] += 1;
+            boxes[i] = Aabb::surrounding_box(boxes[i], *object_box);
+        }
+
+        // Costs of everything right of each split, swept from the right.
+        let mut right_costs = [0.0; BINS];
+        let (mut count, mut aabb) = (0, Aabb::EMPTY);
+        for i in (1..BINS).rev() {
+            count += counts[i];
+            aabb = Aabb::surrounding_box(aabb, boxes[i]);
+            right_costs[i] = count as f64 * aabb.surface_area();
+        }
+        let (mut count, mut aabb) = (0, Aabb::EMPTY);
+        for split in 1..BINS {
+            count += counts[split - 1];
+            aabb = Aabb::surrounding_box(aabb, boxes[split - 1]);
+            if count == 0 || count == objects.len() {
+                continue;
+            }
+            let cost = count as f64 * aabb.surface_area() + right_costs[split];
+            if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                best = Some((cost, axis, split));
+            }
+        }
+    }
+
+    let (_, axis, split) = best?;
+    let extent = centroids.axis(axis);
+    let (left, right) = std::mem::take(objects).into_iter()
+        .partition(|(aabb, _)| bin(aabb, axis, extent) < split);
+    *objects = left;
+    Some(right)
+}
+
+// Which of the BINS equal slices of the centers' extent a box's center is in.
+fn bin(aabb: &Aabb, axis: Axis, extent: Interval) -> usize {
+    let offset = (aabb.centroid().to_vec3().get(axis) - extent.min) / extent.size();
+    ((offset * BINS as f64) as usize).min(BINS - 1)
+}
+
+impl Hittable for BvhNode {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        if !self.aabb.hit(ray, t_range) {
+            return None;
+        }
+        let left = self.left.hit(ray, t_range);
+        // Only a hit in front of the left one counts on the right.
+        let max = left.map_or(t_range.max, |record| record.t);
+        let right = self.right.as_ref().and_then(|right| right.hit(ray, Interval::new(t_range.min, max)));
+        right.or(left)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.aabb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use assert_approx_eq::*;
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use crate::material::Black;
+    use crate::point::Point3;
+    use crate::sphere::Sphere;
+    use crate::vector::Vec3;
+    use super::*;
+
+    // The plane z = 0, which has no bounding box.
+    struct Floor;
+
+    impl Hittable for Floor {
+        fn hit(&self, _ray: &Ray, _t_range: Interval) -> Option<HitRecord<'_>> {
+            None
+        }
+
+        fn bounding_box(&self) -> Option<Aabb> {
+            None
+        }
+    }
+
+    fn random_spheres(rng: &mut StdRng, count: usize) -> Vec<Sphere> {
+        (0..count).map(|_| {
+            let center = Point3::from_vec3(Vec3::random_range(rng, -10.0, 10.0));
+            Sphere::new(center, rng.gen_range(0.1..1.0), Arc::new(Black))
+        }).collect()
+    }
+
+    fn list(spheres: &[Sphere]) -> HittableList {
+        let mut list = HittableList::new();
+        for sphere in spheres {
+            list.add(sphere.clone());
+        }
+        list
+    }
+
+    fn bvh(spheres: &[Sphere], method: SplitMethod) -> BvhNode {
+        BvhNode::with_split_method(list(spheres).into_objects(), method).unwrap()
+    }
+
+    #[test]
+    fn needs_bounded_objects() {
+        assert!(BvhNode::new(Vec::new()).is_none());
+        let mut list = HittableList::new();
+        list.add(Sphere::new(Point3::ORIGIN, 1.0, Arc::new(Black)));
+        list.add(Floor);
+        assert!(BvhNode::from_list(list).is_none());
+    }
+
+    #[test]
+    fn single_object() {
+        let mut list = HittableList::new();
+        list.add(Sphere::new(Point3::new(0.0, 0.0, -3.0), 1.0, Arc::new(Black)));
+        let bvh = BvhNode::from_list(list).unwrap();
+        assert_approx_eq!(2.0, bvh.hit(&Ray::new(Point3::ORIGIN, -Vec3::Z), Interval::FORWARD).unwrap().t, 0.0001);
+        assert!(bvh.hit(&Ray::new(Point3::ORIGIN, Vec3::Z), Interval::FORWARD).is_none());
+    }
+
+    #[test]
+    fn bounding_box_matches_the_list() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let spheres = random_spheres(&mut rng, 50);
+        for &method in &[SplitMethod::Median, SplitMethod::Sah] {
+            assert!(bvh(&spheres, method).bounding_box() == list(&spheres).bounding_box());
+        }
+    }
+
+    #[test]
+    fn hits_match_the_list() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let spheres = random_spheres(&mut rng, 100);
+        let list = list(&spheres);
+        // Both split methods are checked against the same rays.
+        let rays: Vec<Ray> = (0..2000)
+            .map(|_| {
+                let origin = Point3::from_vec3(Vec3::random_range(&mut rng, -10.0, 10.0));
+                Ray::new(origin, Vec3::random_unit_vector(&mut rng))
+            })
+            .collect();
+        for &method in &[SplitMethod::Median, SplitMethod::Sah] {
+            let bvh = bvh(&spheres, method);
+            let mut hits = 0;
+            for ray in &rays {
+                match (list.hit(ray, Interval::FORWARD), bvh.hit(ray, Interval::FORWARD)) {
+                    (Some(expected), Some(actual)) => {
+                        hits += 1;
+                        assert_eq!(expected.t, actual.t);
+                        assert_vec3_equal!(expected.normal, actual.normal);
+                    }
+                    (None, None) => {}
+                    (expected, actual) => panic!("list hit {:?}, bvh hit {:?}", expected.map(|r| r.t), actual.map(|r| r.t))
+                }
+            }
+            assert!(hits > 100, "only {} hits", hits);
+        }
+    }
+
+    #[test]
+    fn sah_splits_off_an_outlier() {
+        // A tight cluster and one sphere far away. The median split puts part
+        // of the cluster in with the outlier; SAH gives it a node of its own.
+        let mut spheres: Vec<Sphere> = (0..9)
+            .map(|i| Sphere::new(Point3::new(i as f64 * 0.1, 0.0, 0.0), 0.1, Arc::new(Black)))
+            .collect();
+        spheres.push(Sphere::new(Point3::new(100.0, 0.0, 0.0), 0.1, Arc::new(Black)));
+        let outlier = spheres[9].bounding_box();
+
+        let sah = bvh(&spheres, SplitMethod::Sah);
+        let children = [sah.left.bounding_box(), sah.right.as_ref().unwrap().bounding_box()];
+        assert!(children.contains(&outlier));
+
+        let median = bvh(&spheres, SplitMethod::Median);
+        let children = [median.left.bounding_box(), median.right.as_ref().unwrap().bounding_box()];
+        assert!(!children.contains(&outlier));
+    }
+
+    #[test]
+    fn coincident_centers_fall_back_to_median() {
+        let spheres: Vec<Sphere> = (1..=5).map(|i| Sphere::new(Point3::ORIGIN, i as f64, Arc::new(Black))).collect();
+        let bvh = bvh(&spheres, SplitMethod::Sah);
+        let record = bvh.hit(&Ray::new(Point3::new(0.0, 0.0, 10.0), -Vec3::Z), Interval::FORWARD).unwrap();
+        assert_approx_eq!(5.0, record.t, 0.0001);
+    }
+}
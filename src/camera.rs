@@ -0,0 +1,230 @@
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+use rand::Rng;
+
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::vector::Vec3;
+
+// The viewport is a rectangle in front of the camera; rays go from the
+// camera position through points on it. By default the camera is a pinhole
+// and everything is in focus. with_defocus turns the pinhole into a thin
+// lens, and then only the plane of the viewport is sharp.
+#[derive(Clone, Copy, Debug)]
+pub struct Camera {
+    origin: Point3,
+    lower_left_corner: Point3,
+    horizontal: Vec3,
+    vertical: Vec3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    focus_dist: f64,
+    lens_radius: f64,
+    time0: f64,
+    time1: f64,
+}
+
+impl Camera {
+    // Camera at the origin looking down -z, with +y up and the viewport
+    // focal_length away.
+    pub fn new(aspect_ratio: f64, viewport_height: f64, focal_length: f64) -> Camera {
+        let viewport_width = aspect_ratio * viewport_height;
+        Camera::from_basis(Point3::ORIGIN, Vec3::X, Vec3::Y, Vec3::Z, viewport_width, viewport_height, focal_length)
+    }
+
+    // Camera at lookfrom looking at lookat. vup picks the roll: it is
+    // projected onto the viewport to become its up direction, so it only
+    // needs to not be parallel to the viewing direction. vfov is the
+    // vertical field of view in radians.
+    pub fn look_at(lookfrom: Point3, lookat: Point3, vup: Vec3, vfov: f64, aspect_ratio: f64) -> Camera {
+        let viewport_height = 2.0 * (vfov / 2.0).tan();
+        let viewport_width = aspect_ratio * viewport_height;
+        let w = (lookfrom - lookat).unit_vector();
+        let u = vup.cross(w).unit_vector();
+        let v = w.cross(u);
+        Camera::from_basis(lookfrom, u, v, w, viewport_width, viewport_height, 1.0)
+    }
+
+    // u points right, v up and w backwards, away from the scene.
+    fn from_basis(origin: Point3, u: Vec3, v: Vec3, w: Vec3,
+                  viewport_width: f64, viewport_height: f64, focal_length: f64) -> Camera {
+        let horizontal = u * viewport_width;
+        let vertical = v * viewport_height;
+        let lower_left_corner = origin - horizontal / 2.0 - vertical / 2.0 - w * focal_length;
+        Camera {
+            origin,
+            lower_left_corner,
+            horizontal,
+            vertical,
+            u,
+            v,
+            w,
+            focus_dist: focal_length,
+            lens_radius: 0.0,
+            time0: 0.0,
+            time1: 0.0
+        }
+    }
+
+    // Depth of field: rays start from a random point on a lens aperture
+    // units across, and objects focus_dist away from the camera are sharp.
+    // The field of view stays the same.
+    pub fn with_defocus(self, aperture: f64, focus_dist: f64) -> Camera {
+        let scale = focus_dist / self.focus_dist;
+        let horizontal = self.horizontal * scale;
+        let vertical = self.vertical * scale;
+        Camera {
+            lower_left_corner: self.origin - horizontal / 2.0 - vertical / 2.0 - self.w * focus_dist,
+            horizontal,
+            vertical,
+            focus_dist,
+            lens_radius: aperture / 2.0,
+            ..self
+        }
+    }
+
+    // Motion blur: the shutter is open from time0 to time1 and each ray is
+    // sent at a random moment in between.
+    pub fn with_shutter(self, time0: f64, time1: f64) -> Camera {
+        Camera { time0, time1, ..self }
+    }
+
+    // s and t run from 0 to 1 across the viewport, left to right and bottom
+    // to top. rng is only used to pick a point on the lens and a time, so a
+    // pinhole camera with a closed shutter gives the same ray every time.
+    pub fn get_ray<R: Rng + ?Sized>(&self, rng: &mut R, s: f64, t: f64) -> Ray {
+        let offset = if self.lens_radius > 0.0 {
+            let on_lens = Vec3::random_in_unit_disk(rng) * self.lens_radius;
+            self.u * on_lens.x() + self.v * on_lens.y()
+        }
+        else {
+            Vec3::ZERO
+        };
+        let time = if self.time1 > self.time0 {
+            rng.gen_range(self.time0..self.time1)
+        }
+        else {
+            self.time0
+        };
+        let origin = self.origin + offset;
+        let target = self.lower_left_corner + self.horizontal * s + self.vertical * t;
+        Ray::with_time(origin, target - origin, time)
+    }
+}
+
+// 16:9 with a viewport two units high, one unit away.
+impl Default for Camera {
+    fn default() -> Camera {
+        Camera::new(16.0 / 9.0, 2.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+    use assert_approx_eq::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::*;
+
+    #[test]
+    fn rays_start_at_the_origin() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let camera = Camera::default();
+        assert_vec3_equal!(Point3::ORIGIN, camera.get_ray(&mut rng, 0.3, 0.7).origin());
+    }
+
+    #[test]
+    fn center_ray_looks_down_negative_z() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let camera = Camera::new(2.0, 2.0, 1.5);
+        assert_vec3_equal!(Vec3::new(0.0, 0.0, -1.5), camera.get_ray(&mut rng, 0.5, 0.5).direction());
+    }
+
+    #[test]
+    fn corners_span_the_viewport() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let camera = Camera::new(2.0, 2.0, 1.0);
+        assert_vec3_equal!(Vec3::new(-2.0, -1.0, -1.0), camera.get_ray(&mut rng, 0.0, 0.0).direction());
+        assert_vec3_equal!(Vec3::new(2.0, 1.0, -1.0), camera.get_ray(&mut rng, 1.0, 1.0).direction());
+        assert_vec3_equal!(Vec3::new(2.0, -1.0, -1.0), camera.get_ray(&mut rng, 1.0, 0.0).direction());
+    }
+
+    #[test]
+    fn look_at_matches_the_default_orientation() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let camera = Camera::look_at(Point3::ORIGIN, Point3::new(0.0, 0.0, -1.0), Vec3::Y, FRAC_PI_2, 2.0);
+        let reference = Camera::new(2.0, 2.0, 1.0);
+        for &(s, t) in [(0.0, 0.0), (0.5, 0.5), (1.0, 0.25)].iter() {
+            assert_vec3_equal!(reference.get_ray(&mut rng, s, t).direction(), camera.get_ray(&mut rng, s, t).direction());
+        }
+    }
+
+    #[test]
+    fn look_at_points_at_the_target() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let lookfrom = Point3::new(-2.0, 2.0, 1.0);
+        let lookat = Point3::new(0.0, 0.0, -1.0);
+        let camera = Camera::look_at(lookfrom, lookat, Vec3::Y, 0.5, 1.5);
+        let ray = camera.get_ray(&mut rng, 0.5, 0.5);
+        assert_vec3_equal!(lookfrom, ray.origin());
+        assert_vec3_equal!((lookat - lookfrom).unit_vector(), ray.direction().unit_vector());
+
+        // The top edge of the image is higher up than the bottom edge.
+        assert!(camera.get_ray(&mut rng, 0.5, 1.0).direction().y() > camera.get_ray(&mut rng, 0.5, 0.0).direction().y());
+    }
+
+    #[test]
+    fn look_at_field_of_view() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let camera = Camera::look_at(Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0), Vec3::Y, FRAC_PI_2, 1.0);
+        let top = camera.get_ray(&mut rng, 0.5, 1.0).direction();
+        let bottom = camera.get_ray(&mut rng, 0.5, 0.0).direction();
+        assert!((top.angle_between(bottom) - FRAC_PI_2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn defocus_keeps_the_focus_plane_sharp() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let lookfrom = Point3::new(0.0, 0.0, 3.0);
+        let camera = Camera::look_at(lookfrom, Point3::ORIGIN, Vec3::Y, FRAC_PI_2, 1.0)
+            .with_defocus(0.5, 3.0);
+        for &(s, t) in [(0.5, 0.5), (0.1, 0.8)].iter() {
+            let reference = camera.get_ray(&mut rng, s, t);
+            let ray = camera.get_ray(&mut rng, s, t);
+            // Rays through the same viewport point start at different points
+            // on the lens, but meet again at the focus distance.
+            assert!(ray.origin().distance(lookfrom) <= 0.25);
+            assert!(ray.origin().distance(reference.origin()) > 0.0);
+            assert_vec3_equal!(reference.at(1.0), ray.at(1.0));
+            assert_approx_eq!(3.0, lookfrom.z() - ray.at(1.0).z(), 0.0001);
+        }
+    }
+
+    #[test]
+    fn defocus_keeps_the_field_of_view() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let pinhole = Camera::look_at(Point3::ORIGIN, Point3::new(0.0, 0.0, -1.0), Vec3::Y, 1.0, 1.5);
+        let lens = pinhole.with_defocus(0.0, 10.0);
+        for &(s, t) in [(0.0, 0.0), (1.0, 0.5)].iter() {
+            let expected = pinhole.get_ray(&mut rng, s, t).direction().unit_vector();
+            assert_vec3_equal!(expected, lens.get_ray(&mut rng, s, t).direction().unit_vector());
+        }
+    }
+
+    #[test]
+    fn shutter_times() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let camera = Camera::default();
+        assert_approx_eq!(0.0, camera.get_ray(&mut rng, 0.5, 0.5).time(), 0.0001);
+
+        let camera = camera.with_shutter(1.0, 2.0);
+        let times: Vec<f64> = (0..100).map(|_| camera.get_ray(&mut rng, 0.5, 0.5).time()).collect();
+        assert!(times.iter().all(|&time| (1.0..2.0).contains(&time)));
+        assert!(times.iter().any(|&time| time < 1.5) && times.iter().any(|&time| time > 1.5));
+
+        let camera = camera.with_shutter(3.0, 3.0);
+        assert_approx_eq!(3.0, camera.get_ray(&mut rng, 0.5, 0.5).time(), 0.0001);
+    }
+}
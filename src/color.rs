@@ -0,0 +1,278 @@
+use core::fmt;
+use core::ops::{Add, Mul, Div, AddAssign, MulAssign, DivAssign};
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+
+use crate::vector::Vec3;
+
+// Linear RGB color with channels nominally in [0, 1]. Values above 1 are
+// allowed while accumulating radiance and are only clamped on output.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Color {
+    r: f64,
+    g: f64,
+    b: f64,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0.0, 0.0, 0.0);
+    pub const WHITE: Color = Color::new(1.0, 1.0, 1.0);
+
+    pub const fn new(r: f64, g: f64, b: f64) -> Color {
+        Color { r, g, b }
+    }
+
+    pub const fn r(&self) -> f64 {
+        self.r
+    }
+
+    pub const fn g(&self) -> f64 {
+        self.g
+    }
+
+    pub const fn b(&self) -> f64 {
+        self.b
+    }
+
+    // Relative luminance with Rec. 709 primaries.
+    pub fn luminance(&self) -> f64 {
+        Vec3::from(*self).luminance()
+    }
+
+    // Linear to display space, e.g. gamma 2.0 is a square root per channel.
+    pub fn gamma_encode(self, gamma: f64) -> Color {
+        let exponent = 1.0 / gamma;
+        Color {
+            r: self.r.max(0.0).powf(exponent),
+            g: self.g.max(0.0).powf(exponent),
+            b: self.b.max(0.0).powf(exponent)
+        }
+    }
+
+    // Display space back to linear.
+    pub fn gamma_decode(self, gamma: f64) -> Color {
+        Color {
+            r: self.r.max(0.0).powf(gamma),
+            g: self.g.max(0.0).powf(gamma),
+            b: self.b.max(0.0).powf(gamma)
+        }
+    }
+
+    pub fn clamp(self) -> Color {
+        Color {
+            r: self.r.clamp(0.0, 1.0),
+            g: self.g.clamp(0.0, 1.0),
+            b: self.b.clamp(0.0, 1.0)
+        }
+    }
+
+    pub fn saturating_add(self, other: Color) -> Color {
+        (self + other).clamp()
+    }
+
+    pub fn saturating_sub(self, other: Color) -> Color {
+        Color {
+            r: self.r - other.r,
+            g: self.g - other.g,
+            b: self.b - other.b
+        }.clamp()
+    }
+
+    // Channels are clamped to [0, 1] and scaled so that 1.0 maps to 255.
+    pub fn to_rgb8(self) -> [u8; 3] {
+        fn f(channel: f64) -> u8 {
+            (channel.clamp(0.0, 1.0) * 255.99) as u8
+        }
+        [f(self.r), f(self.g), f(self.b)]
+    }
+
+    // Linear radiance to 8-bit display values.
+    pub fn to_rgb8_gamma(self, gamma: f64) -> [u8; 3] {
+        self.gamma_encode(gamma).to_rgb8()
+    }
+}
+
+impl Add<Color> for Color {
+    type Output = Color;
+    fn add(self, other: Color) -> Color {
+        Color {
+            r: self.r + other.r,
+            g: self.g + other.g,
+            b: self.b + other.b
+        }
+    }
+}
+
+impl Mul<Color> for f64 {
+    type Output = Color;
+    fn mul(self, color: Color) -> Color {
+        Color {
+            r: self * color.r,
+            g: self * color.g,
+            b: self * color.b
+        }
+    }
+}
+
+impl Mul<f64> for Color {
+    type Output = Color;
+    fn mul(self, scalar: f64) -> Color {
+        scalar * self
+    }
+}
+
+// Attenuation, e.g. light reflected off a colored surface
+impl Mul<Color> for Color {
+    type Output = Color;
+    fn mul(self, other: Color) -> Color {
+        Color {
+            r: self.r * other.r,
+            g: self.g * other.g,
+            b: self.b * other.b
+        }
+    }
+}
+
+impl Div<f64> for Color {
+    type Output = Color;
+    fn div(self, scalar: f64) -> Color {
+        Color {
+            r: self.r / scalar,
+            g: self.g / scalar,
+            b: self.b / scalar
+        }
+    }
+}
+
+impl AddAssign<Color> for Color {
+    fn add_assign(&mut self, other: Color) {
+        *self = *self + other;
+    }
+}
+
+impl MulAssign<f64> for Color {
+    fn mul_assign(&mut self, scalar: f64) {
+        *self = *self * scalar;
+    }
+}
+
+impl MulAssign<Color> for Color {
+    fn mul_assign(&mut self, other: Color) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign<f64> for Color {
+    fn div_assign(&mut self, scalar: f64) {
+        *self = *self / scalar;
+    }
+}
+
+impl From<Vec3> for Color {
+    fn from(vector: Vec3) -> Color {
+        Color::new(vector.x(), vector.y(), vector.z())
+    }
+}
+
+impl From<Color> for Vec3 {
+    fn from(color: Color) -> Vec3 {
+        Vec3::new(color.r, color.g, color.b)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.r, self.g, self.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use super::*;
+
+    macro_rules! assert_color_equal {
+        ($expected:expr, $actual:expr) => {
+            let tolerance = 0.0001;
+            assert_approx_eq!($expected.r, $actual.r, tolerance);
+            assert_approx_eq!($expected.g, $actual.g, tolerance);
+            assert_approx_eq!($expected.b, $actual.b, tolerance);
+        }
+    }
+
+    #[test]
+    fn luminance() {
+        assert_approx_eq!(1.0, Color::WHITE.luminance(), 0.0001);
+        assert_approx_eq!(0.0, Color::BLACK.luminance(), 0.0001);
+        assert_approx_eq!(0.7152, Color::new(0.0, 1.0, 0.0).luminance(), 0.0001);
+    }
+
+    #[test]
+    fn gamma_encode() {
+        let color = Color::new(0.25, 0.04, 1.0);
+        let result = color.gamma_encode(2.0);
+        let expected = Color::new(0.5, 0.2, 1.0);
+        assert_color_equal!(expected, result);
+    }
+
+    #[test]
+    fn gamma_round_trip() {
+        let color = Color::new(0.1, 0.5, 0.9);
+        let result = color.gamma_encode(2.2).gamma_decode(2.2);
+        assert_color_equal!(color, result);
+    }
+
+    #[test]
+    fn saturating_arithmetic() {
+        let color1 = Color::new(0.8, 0.5, 0.1);
+        let color2 = Color::new(0.5, 0.2, 0.3);
+        assert_color_equal!(Color::new(1.0, 0.7, 0.4), color1.saturating_add(color2));
+        assert_color_equal!(Color::new(0.3, 0.3, 0.0), color1.saturating_sub(color2));
+    }
+
+    #[test]
+    fn to_rgb8() {
+        let color = Color::new(-1.0, 0.5, 1.4);
+        assert_eq!([0u8, 127u8, 255u8], color.to_rgb8());
+        assert_eq!([255u8, 255u8, 255u8], Color::WHITE.to_rgb8());
+    }
+
+    #[test]
+    fn to_rgb8_gamma() {
+        let color = Color::new(0.25, -1.0, 4.0);
+        assert_eq!([127u8, 0u8, 255u8], color.to_rgb8_gamma(2.0));
+        assert_eq!(color.to_rgb8(), color.to_rgb8_gamma(1.0));
+        assert_eq!([0u8, 0u8, 0u8], Color::new(f64::NAN, 0.0, 0.0).to_rgb8_gamma(2.2));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let color = Color::new(0.2, 0.4, 0.6);
+        assert_color_equal!(Color::new(0.4, 0.8, 1.2), color + color);
+        assert_color_equal!(Color::new(0.1, 0.2, 0.3), 0.5 * color);
+        assert_color_equal!(Color::new(0.1, 0.2, 0.3), color * 0.5);
+        assert_color_equal!(Color::new(0.1, 0.2, 0.3), color / 2.0);
+        assert_color_equal!(Color::new(0.04, 0.16, 0.36), color * color);
+    }
+
+    #[test]
+    fn assignment_operators() {
+        let mut color = Color::new(0.2, 0.4, 0.6);
+        color += Color::new(0.2, 0.2, 0.2);
+        assert_color_equal!(Color::new(0.4, 0.6, 0.8), color);
+        color *= 0.5;
+        assert_color_equal!(Color::new(0.2, 0.3, 0.4), color);
+        color *= Color::new(0.5, 1.0, 0.0);
+        assert_color_equal!(Color::new(0.1, 0.3, 0.0), color);
+        color /= 0.1;
+        assert_color_equal!(Color::new(1.0, 3.0, 0.0), color);
+    }
+
+    #[test]
+    fn vec3_conversion() {
+        let color = Color::from(Vec3::new(0.1, 0.2, 0.3));
+        assert_color_equal!(Color::new(0.1, 0.2, 0.3), color);
+        let vector: Vec3 = color.into();
+        assert_vec3_equal!(Vec3::new(0.1, 0.2, 0.3), vector);
+    }
+}
@@ -0,0 +1,152 @@
+use core::f64::consts::PI;
+use std::sync::Arc;
+
+use crate::aabb::{Aabb, BOX_PADDING};
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::onb::Onb;
+use crate::plane;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::vector::{Vec2, Vec3};
+
+// A flat disk facing along its normal, or with an inner radius a ring.
+// Texture coordinates are polar: u goes once around the center and v from
+// the inner edge to the outer one.
+#[derive(Clone, Debug)]
+pub struct Disk {
+    center: Point3,
+    onb: Onb,
+    radius: f64,
+    inner_radius: f64,
+    material: Arc<dyn Material>,
+}
+
+impl Disk {
+    pub fn new(center: Point3, normal: Vec3, radius: f64, material: Arc<dyn Material>) -> Disk {
+        Disk { center, onb: Onb::build_from_w(normal), radius, inner_radius: 0.0, material }
+    }
+
+    // Cuts a hole of the given radius out of the middle.
+    pub fn with_inner_radius(self, inner_radius: f64) -> Disk {
+        assert!((0.0..self.radius).contains(&inner_radius), "disk inner radius must be in [0, radius)");
+        Disk { inner_radius, ..self }
+    }
+
+    pub fn center(&self) -> Point3 {
+        self.center
+    }
+
+    pub fn normal(&self) -> Vec3 {
+        self.onb.w()
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn inner_radius(&self) -> f64 {
+        self.inner_radius
+    }
+
+    pub fn area(&self) -> f64 {
+        PI * (self.radius * self.radius - self.inner_radius * self.inner_radius)
+    }
+}
+
+impl Hittable for Disk {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        let normal = self.onb.w();
+        let t = plane::intersect(self.center, normal, ray, t_range)?;
+        let offset = ray.at(t) - self.center;
+        let distance = offset.length();
+        if distance > self.radius || distance < self.inner_radius {
+            return None;
+        }
+        let angle = offset.dot(self.onb.v()).atan2(offset.dot(self.onb.u()));
+        let u = (angle + PI) / (2.0 * PI);
+        let v = (distance - self.inner_radius) / (self.radius - self.inner_radius);
+        Some(HitRecord::new(ray, t, normal, &*self.material).with_uv(Vec2::new(u, v)))
+    }
+
+    // Along each axis the rim reaches as far from the center as the radius
+    // times the sine of the angle between the axis and the normal.
+    fn bounding_box(&self) -> Option<Aabb> {
+        let normal = self.onb.w();
+        let extent = Vec3::new(
+            (1.0 - normal.x() * normal.x()).max(0.0).sqrt(),
+            (1.0 - normal.y() * normal.y()).max(0.0).sqrt(),
+            (1.0 - normal.z() * normal.z()).max(0.0).sqrt()) * self.radius;
+        Some(Aabb::new(self.center - extent, self.center + extent).padded(BOX_PADDING))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use super::*;
+
+    // Radius 2 at z = -3, facing +z.
+    fn disk() -> Disk {
+        Disk::new(Point3::new(0.0, 0.0, -3.0), Vec3::Z, 2.0, Arc::new(Black))
+    }
+
+    fn ray_to(x: f64, y: f64) -> Ray {
+        Ray::new(Point3::new(x, y, 0.0), -Vec3::Z)
+    }
+
+    #[test]
+    fn hit_inside_the_rim() {
+        let disk = disk();
+        let record = disk.hit(&ray_to(1.0, 1.0), Interval::FORWARD).unwrap();
+        assert_approx_eq!(3.0, record.t);
+        assert_vec3_equal!(Point3::new(1.0, 1.0, -3.0), record.point);
+        assert!(record.front_face);
+        assert_vec3_equal!(Vec3::Z, record.normal);
+        assert!(disk.hit(&ray_to(1.5, 1.5), Interval::FORWARD).is_none());
+        assert!(disk.hit(&Ray::new(Point3::ORIGIN, Vec3::X), Interval::FORWARD).is_none());
+
+        let record = disk.hit(&Ray::new(Point3::new(0.5, 0.0, -5.0), Vec3::Z), Interval::FORWARD).unwrap();
+        assert!(!record.front_face);
+        assert_approx_eq!(4.0 * PI, disk.area());
+    }
+
+    #[test]
+    fn annulus() {
+        let ring = disk().with_inner_radius(1.0);
+        assert!(ring.hit(&ray_to(0.5, 0.5), Interval::FORWARD).is_none());
+        assert!(ring.hit(&ray_to(0.0, 1.5), Interval::FORWARD).is_some());
+        assert_approx_eq!(3.0 * PI, ring.area());
+    }
+
+    #[test]
+    fn polar_uvs() {
+        let ring = disk().with_inner_radius(1.0);
+        let inner = ring.hit(&ray_to(1.0, 0.0), Interval::FORWARD).unwrap().uv;
+        let outer = ring.hit(&ray_to(-2.0, 0.0), Interval::FORWARD).unwrap().uv;
+        assert_approx_eq!(0.0, inner.y());
+        assert_approx_eq!(1.0, outer.y());
+        // Opposite sides are half a turn apart.
+        assert_approx_eq!(0.5, (inner.x() - outer.x()).abs());
+    }
+
+    #[test]
+    fn bounding_box() {
+        let aabb = disk().bounding_box().unwrap();
+        assert_vec3_equal!(Point3::new(-2.0, -2.0, -3.0), aabb.min(), 0.001);
+        assert_vec3_equal!(Point3::new(2.0, 2.0, -3.0), aabb.max(), 0.001);
+
+        let tilted = Disk::new(Point3::ORIGIN, Vec3::new(1.0, 1.0, 0.0), 1.0, Arc::new(Black));
+        let aabb = tilted.bounding_box().unwrap();
+        let half = 0.5f64.sqrt();
+        assert_vec3_equal!(Point3::new(half, half, 1.0), aabb.max(), 0.001);
+    }
+
+    #[test]
+    #[should_panic(expected = "disk inner radius must be in [0, radius)")]
+    fn inner_radius_past_the_rim() {
+        disk().with_inner_radius(2.0);
+    }
+}
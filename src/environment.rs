@@ -0,0 +1,255 @@
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use image::error::{DecodingError, ImageError, ImageFormatHint};
+use image::hdr::HdrDecoder;
+use image::ImageResult;
+
+use crate::background::Background;
+use crate::color::Color;
+use crate::ray::Ray;
+use crate::vector::Vec3;
+
+// An equirectangular (latitude-longitude) panorama that lights the scene
+// from infinitely far away. The middle of the image looks down -z with +x to
+// the right and +y up, so the top row is straight up and the left and right
+// edges meet behind, at +z.
+#[derive(Clone, Debug)]
+pub struct EnvironmentMap {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl EnvironmentMap {
+    // Linear colors, row by row from the top. Panics if empty; files that
+    // decode to nothing are an error from open instead.
+    pub fn new(width: usize, height: usize, pixels: Vec<Color>) -> EnvironmentMap {
+        assert!(width > 0 && height > 0, "environment map must not be empty");
+        assert_eq!(width * height, pixels.len(), "environment map size doesn't match its pixels");
+        EnvironmentMap { width, height, pixels }
+    }
+
+    // Radiance .hdr and OpenEXR .exr files are used as they are. Anything
+    // else the image crate can read, e.g. PNG or JPEG, is taken to be gamma
+    // 2.2 encoded.
+    pub fn open<P: AsRef<Path>>(path: P) -> ImageResult<EnvironmentMap> {
+        let path = path.as_ref();
+        let has_extension = |name: &str| path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case(name));
+        if has_extension("exr") {
+            open_exr(path)
+        }
+        else if has_extension("hdr") {
+            let decoder = HdrDecoder::new(BufReader::new(File::open(path)?))?;
+            let metadata = decoder.metadata();
+            let pixels = decoder.read_image_hdr()?.iter()
+                .map(|pixel| Color::new(pixel[0] as f64, pixel[1] as f64, pixel[2] as f64))
+                .collect();
+            decoded("Radiance HDR", metadata.width as usize, metadata.height as usize, pixels)
+        }
+        else {
+            let image = image::open(path)?.into_rgb();
+            let pixels = image.pixels()
+                .map(|pixel| Color::new(pixel[0] as f64, pixel[1] as f64, pixel[2] as f64) / 255.0)
+                .map(|color| color.gamma_decode(2.2))
+                .collect();
+            decoded("image", image.width() as usize, image.height() as usize, pixels)
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Bilinear lookup at texture coordinates with (0, 0) at the top left.
+    // u wraps around, v is clamped to the poles.
+    pub fn sample(&self, u: f64, v: f64) -> Color {
+        // Pixel centers are at half-integer coordinates.
+        let x = u * self.width as f64 - 0.5;
+        let y = v * self.height as f64 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as isize, y0 as isize);
+
+        let pixel = |x: isize, y: isize| {
+            let x = x.rem_euclid(self.width as isize) as usize;
+            let y = y.clamp(0, self.height as isize - 1) as usize;
+            self.pixels[y * self.width + x]
+        };
+        let top = (1.0 - fx) * pixel(x0, y0) + fx * pixel(x0 + 1, y0);
+        let bottom = (1.0 - fx) * pixel(x0, y0 + 1) + fx * pixel(x0 + 1, y0 + 1);
+        (1.0 - fy) * top + fy * bottom
+    }
+}
+
+// Reads the first layer with red, green and blue channels. Alpha is ignored.
+fn open_exr(path: &Path) -> ImageResult<EnvironmentMap> {
+    let image = exr::prelude::read_first_rgba_layer_from_file(
+        path,
+        |resolution, _| (resolution.width(), resolution.height(), vec![Color::BLACK; resolution.area()]),
+        |(width, _, pixels), position, (r, g, b, _): (f32, f32, f32, f32)| {
+            pixels[position.y() * *width + position.x()] = Color::new(r as f64, g as f64, b as f64);
+        },
+    );
+    let image = image.map_err(|error| match error {
+        exr::error::Error::Io(error) => ImageError::IoError(error),
+        error => ImageError::Decoding(DecodingError::new(ImageFormatHint::Name("OpenEXR".to_string()), error)),
+    })?;
+    let (width, height, pixels) = image.layer_data.channel_data.pixels;
+    decoded("OpenEXR", width, height, pixels)
+}
+
+// EnvironmentMap::new for a decoded file, which may be empty.
+fn decoded(format: &str, width: usize, height: usize, pixels: Vec<Color>) -> ImageResult<EnvironmentMap> {
+    if width == 0 || height == 0 {
+        let hint = ImageFormatHint::Name(format.to_string());
+        return Err(ImageError::Decoding(DecodingError::new(hint, "environment map must not be empty")));
+    }
+    Ok(EnvironmentMap::new(width, height, pixels))
+}
+
+impl Background for EnvironmentMap {
+    fn color(&self, ray: &Ray) -> Color {
+        let (u, v) = direction_to_uv(ray.direction());
+        self.sample(u, v)
+    }
+}
+
+// Texture coordinates of a direction in the layout of EnvironmentMap. The
+// direction need not be unit length.
+pub fn direction_to_uv(direction: Vec3) -> (f64, f64) {
+    let direction = direction.unit_vector();
+    let u = 0.5 + direction.x().atan2(-direction.z()) / (2.0 * PI);
+    let v = direction.y().clamp(-1.0, 1.0).acos() / PI;
+    (u, v)
+}
+
+// The unit direction in the middle of the given texture coordinates.
+pub fn uv_to_direction(u: f64, v: f64) -> Vec3 {
+    let phi = (u - 0.5) * 2.0 * PI;
+    let theta = v * PI;
+    Vec3::new(theta.sin() * phi.sin(), theta.cos(), -theta.sin() * phi.cos())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use image::Rgb;
+    use image::hdr::HDREncoder;
+    use crate::point::Point3;
+    use super::*;
+
+    fn assert_uv(expected: (f64, f64), direction: Vec3) {
+        let (u, v) = direction_to_uv(direction);
+        assert_approx_eq!(expected.0, u);
+        assert_approx_eq!(expected.1, v);
+    }
+
+    // Each pixel of a 4 x 2 map has its own color.
+    fn checker() -> EnvironmentMap {
+        let pixels = (0..8).map(|i| Color::new(i as f64, 0.0, 0.0)).collect();
+        EnvironmentMap::new(4, 2, pixels)
+    }
+
+    #[test]
+    fn direction_to_uv_layout() {
+        assert_uv((0.5, 0.5), -Vec3::Z);
+        assert_uv((0.75, 0.5), Vec3::X);
+        assert_uv((0.25, 0.5), Vec3::new(-2.0, 0.0, 0.0));
+        // u is arbitrary at the poles.
+        assert_approx_eq!(0.0, direction_to_uv(Vec3::Y).1);
+        assert_approx_eq!(1.0, direction_to_uv(-Vec3::Y).1);
+        assert_uv((0.5, 0.25), Vec3::new(0.0, 1.0, -1.0));
+    }
+
+    #[test]
+    fn uv_round_trip() {
+        for &(u, v) in &[(0.1, 0.2), (0.5, 0.5), (0.9, 0.7), (0.3, 0.95)] {
+            let direction = uv_to_direction(u, v);
+            assert_approx_eq!(1.0, direction.length());
+            let (ru, rv) = direction_to_uv(direction);
+            assert_approx_eq!(u, ru);
+            assert_approx_eq!(v, rv);
+        }
+    }
+
+    #[test]
+    fn sample_pixel_centers_and_between() {
+        let map = checker();
+        assert!(map.sample(0.125, 0.25) == Color::new(0.0, 0.0, 0.0));
+        assert!(map.sample(0.625, 0.75) == Color::new(6.0, 0.0, 0.0));
+        assert_approx_eq!(0.5, map.sample(0.25, 0.25).r());
+        assert_approx_eq!(2.0, map.sample(0.125, 0.5).r());
+        // Clamped at the poles.
+        assert_approx_eq!(2.0, map.sample(0.625, 0.0).r());
+        assert_approx_eq!(6.0, map.sample(0.625, 1.0).r());
+    }
+
+    #[test]
+    fn sample_wraps_around_the_seam() {
+        let map = checker();
+        assert_approx_eq!(1.5, map.sample(0.0, 0.25).r());
+        assert_approx_eq!(1.5, map.sample(1.0, 0.25).r());
+    }
+
+    #[test]
+    fn escaping_rays_see_the_panorama() {
+        let map = checker();
+        let ray = Ray::new(Point3::new(5.0, -3.0, 1.0), uv_to_direction(0.625, 0.25));
+        assert_approx_eq!(2.0, map.color(&ray).r());
+        assert_approx_eq!(5.0, map.color(&Ray::new(Point3::ORIGIN, uv_to_direction(0.375, 0.75))).r());
+    }
+
+    #[test]
+    fn open_hdr_file() {
+        let path = std::env::temp_dir().join(format!("vectors-environment-{}.hdr", std::process::id()));
+        let pixels: Vec<Rgb<f32>> = (0..8).map(|i| Rgb([i as f32, 0.5, 16.0])).collect();
+        HDREncoder::new(File::create(&path).unwrap()).encode(&pixels, 4, 2).unwrap();
+        let map = EnvironmentMap::open(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let map = map.unwrap();
+        assert_eq!((4, 2), (map.width(), map.height()));
+        let color = map.sample(0.875, 0.75);
+        assert_approx_eq!(7.0, color.r(), 0.05);
+        assert_approx_eq!(0.5, color.g(), 0.01);
+        assert_approx_eq!(16.0, color.b(), 0.1);
+    }
+
+    #[test]
+    fn open_exr_file() {
+        let path = std::env::temp_dir().join(format!("vectors-environment-{}.exr", std::process::id()));
+        exr::prelude::write_rgb_file(&path, 4, 2, |x, y| ((y * 4 + x) as f32, 0.5_f32, 16.0_f32)).unwrap();
+        let map = EnvironmentMap::open(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let map = map.unwrap();
+        assert_eq!((4, 2), (map.width(), map.height()));
+        assert!(map.sample(0.875, 0.75) == Color::new(7.0, 0.5, 16.0));
+        assert!(map.sample(0.125, 0.25) == Color::new(0.0, 0.5, 16.0));
+    }
+
+    #[test]
+    fn open_empty_hdr_file() {
+        let path = std::env::temp_dir().join(format!("vectors-environment-empty-{}.hdr", std::process::id()));
+        std::fs::write(&path, "#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 0 +X 0\n").unwrap();
+        let map = EnvironmentMap::open(&path);
+        std::fs::remove_file(&path).unwrap();
+        match map {
+            Err(ImageError::Decoding(error)) => assert!(error.to_string().contains("must not be empty")),
+            other => panic!("expected a decoding error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn open_missing_file() {
+        assert!(EnvironmentMap::open("does/not/exist.hdr").is_err());
+        assert!(matches!(EnvironmentMap::open("does/not/exist.exr"), Err(ImageError::IoError(_))));
+    }
+}
@@ -0,0 +1,725 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::color::Color;
+use crate::hittable::Hittable;
+use crate::instance::Instance;
+use crate::material::{Dielectric, DiffuseLight, Lambertian, Material, Metal};
+use crate::matrix::Mat4;
+use crate::mesh::TriangleMesh;
+use crate::point::Point3;
+use crate::quaternion::Quat;
+use crate::vector::{Vec2, Vec3};
+
+// glTF 2.0 import, from .gltf files with external or embedded buffers and
+// from binary .glb files. Every triangle primitive of a mesh becomes one
+// TriangleMesh, in the mesh's own space, and every node of the scene using
+// the mesh an instance of those with the node's transform. A mesh used by
+// several nodes is read once and shared. Textures are not read, only the
+// materials' constant factors.
+
+#[derive(Debug)]
+pub enum GltfError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    // The file breaks the glTF spec, e.g. with an index out of range.
+    Invalid(&'static str),
+    // Valid glTF using something this importer can't handle.
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for GltfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GltfError::Io(error) => write!(f, "{}", error),
+            GltfError::Json(error) => write!(f, "invalid glTF JSON: {}", error),
+            GltfError::Invalid(reason) => write!(f, "invalid glTF: {}", reason),
+            GltfError::Unsupported(what) => write!(f, "unsupported glTF feature: {}", what),
+        }
+    }
+}
+
+impl Error for GltfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GltfError::Io(error) => Some(error),
+            GltfError::Json(error) => Some(error),
+            GltfError::Invalid(_) | GltfError::Unsupported(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for GltfError {
+    fn from(error: io::Error) -> GltfError {
+        GltfError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for GltfError {
+    fn from(error: serde_json::Error) -> GltfError {
+        GltfError::Json(error)
+    }
+}
+
+// One node's use of a mesh primitive: the mesh, shared with the other nodes
+// using it, and the transform from its space into the scene's.
+#[derive(Clone, Debug)]
+pub struct MeshInstance {
+    mesh: Arc<TriangleMesh>,
+    transform: Mat4,
+}
+
+impl MeshInstance {
+    pub fn mesh(&self) -> &Arc<TriangleMesh> {
+        &self.mesh
+    }
+
+    pub fn transform(&self) -> Mat4 {
+        self.transform
+    }
+}
+
+// Hittables for the instances, with one BVH for each mesh however many
+// nodes use it. Meshes without faces are left out, and so are nodes scaled
+// to nothing.
+pub fn to_hittables(instances: &[MeshInstance]) -> Vec<Instance> {
+    let mut bvhs: HashMap<*const TriangleMesh, Option<Rc<dyn Hittable>>> = HashMap::new();
+    instances.iter()
+        .filter_map(|instance| {
+            let bvh = bvhs.entry(Arc::as_ptr(&instance.mesh))
+                .or_insert_with(|| TriangleMesh::bvh(&instance.mesh).map(|bvh| Rc::new(bvh) as Rc<dyn Hittable>));
+            Instance::new(bvh.clone()?, instance.transform)
+        })
+        .collect()
+}
+
+// External buffers are looked up next to the file. Primitives without a
+// material get default_material.
+pub fn load_gltf<P: AsRef<Path>>(path: P, default_material: Arc<dyn Material>) -> Result<Vec<MeshInstance>, GltfError> {
+    let path = path.as_ref();
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+    let data = fs::read(path)?;
+    parse_gltf(&data, default_material, |uri| fs::read(directory.join(uri)))
+}
+
+// Like load_gltf, with data being the contents of a .gltf or .glb file and
+// read_uri giving the contents of the external buffers.
+pub fn parse_gltf<F>(data: &[u8], default_material: Arc<dyn Material>, mut read_uri: F) -> Result<Vec<MeshInstance>, GltfError>
+    where F: FnMut(&str) -> io::Result<Vec<u8>>
+{
+    let (json, binary) = if data.starts_with(b"glTF") { split_glb(data)? } else { (data, None) };
+    let json: Value = serde_json::from_slice(json)?;
+    let buffers = array(&json["buffers"]).iter()
+        .map(|buffer| match buffer["uri"].as_str() {
+            Some(uri) => match uri.strip_prefix("data:") {
+                Some(data_uri) => decode_data_uri(data_uri),
+                None => Ok(read_uri(uri)?),
+            },
+            // Only the first buffer of a GLB file may leave out the URI.
+            None => binary.map(<[u8]>::to_vec).ok_or(GltfError::Invalid("buffer without data")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let materials = array(&json["materials"]).iter().map(material).collect();
+    let mut document = Document { json: &json, buffers, materials, default_material, meshes: HashMap::new() };
+
+    let scene = &json["scenes"][index(&json["scene"]).unwrap_or(0)];
+    if scene.is_null() {
+        return Err(GltfError::Invalid("no scene"));
+    }
+    let mut instances = Vec::new();
+    for node in array(&scene["nodes"]) {
+        let node = index(node).ok_or(GltfError::Invalid("invalid node index"))?;
+        document.add_node(node, Mat4::IDENTITY, 0, &mut instances)?;
+    }
+    Ok(instances)
+}
+
+// Node hierarchies deeper than this are taken to have a cycle.
+const MAX_DEPTH: usize = 256;
+
+struct Document<'a> {
+    json: &'a Value,
+    buffers: Vec<Vec<u8>>,
+    materials: Vec<Arc<dyn Material>>,
+    default_material: Arc<dyn Material>,
+    // The primitives of the meshes read so far, by mesh index.
+    meshes: HashMap<usize, Vec<Arc<TriangleMesh>>>,
+}
+
+impl Document<'_> {
+    fn add_node(&mut self, node: usize, parent: Mat4, depth: usize, instances: &mut Vec<MeshInstance>) -> Result<(), GltfError> {
+        if depth > MAX_DEPTH {
+            return Err(GltfError::Invalid("node hierarchy has a cycle"));
+        }
+        let json = self.json;
+        let node = &json["nodes"][node];
+        if node.is_null() {
+            return Err(GltfError::Invalid("node index out of range"));
+        }
+        let transform = parent * local_transform(node)?;
+        if let Some(mesh) = index(&node["mesh"]) {
+            let primitives = self.mesh(mesh)?;
+            instances.extend(primitives.into_iter().map(|mesh| MeshInstance { mesh, transform }));
+        }
+        for child in array(&node["children"]) {
+            let child = index(child).ok_or(GltfError::Invalid("invalid node index"))?;
+            self.add_node(child, transform, depth + 1, instances)?;
+        }
+        Ok(())
+    }
+
+    // Read the first time a node uses the mesh.
+    fn mesh(&mut self, index: usize) -> Result<Vec<Arc<TriangleMesh>>, GltfError> {
+        if let Some(primitives) = self.meshes.get(&index) {
+            return Ok(primitives.clone());
+        }
+        let mesh = &self.json["meshes"][index];
+        if mesh.is_null() {
+            return Err(GltfError::Invalid("mesh index out of range"));
+        }
+        let mut primitives = Vec::new();
+        for primitive in array(&mesh["primitives"]) {
+            primitives.extend(self.primitive(primitive)?.map(Arc::new));
+        }
+        self.meshes.insert(index, primitives.clone());
+        Ok(primitives)
+    }
+
+    // None for points and lines.
+    fn primitive(&self, primitive: &Value) -> Result<Option<TriangleMesh>, GltfError> {
+        match primitive["mode"].as_u64().unwrap_or(4) {
+            4 => {}
+            5 | 6 => return Err(GltfError::Unsupported("triangle strips and fans")),
+            _ => return Ok(None),
+        }
+        let attributes = &primitive["attributes"];
+        let position = index(&attributes["POSITION"]).ok_or(GltfError::Invalid("primitive without positions"))?;
+        let positions: Vec<Point3> = self.accessor(position, 3)?.chunks(3)
+            .map(|p| Point3::new(p[0], p[1], p[2]))
+            .collect();
+        let count = positions.len();
+
+        let indices = match index(&primitive["indices"]) {
+            Some(indices) => self.accessor(indices, 1)?.into_iter().map(|index| index as u32).collect(),
+            None => (0..count as u32).collect::<Vec<_>>(),
+        };
+        if indices.iter().any(|&index| index as usize >= count) {
+            return Err(GltfError::Invalid("vertex index out of range"));
+        }
+        let faces = indices.chunks_exact(3).map(|face| [face[0], face[1], face[2]]).collect();
+
+        let material = match index(&primitive["material"]) {
+            Some(material) => self.materials.get(material).ok_or(GltfError::Invalid("material index out of range"))?,
+            None => &self.default_material,
+        };
+        let mut mesh = TriangleMesh::new(positions, faces, material.clone());
+        if let Some(normals) = index(&attributes["NORMAL"]) {
+            let normals = self.accessor(normals, 3)?.chunks(3).map(|n| Vec3::new(n[0], n[1], n[2])).collect::<Vec<_>>();
+            check_count(normals.len(), count)?;
+            mesh = mesh.with_normals(normals);
+        }
+        if let Some(uvs) = index(&attributes["TEXCOORD_0"]) {
+            // glTF puts v = 0 at the top of an image; this crate, like OBJ,
+            // at the bottom.
+            let uvs = self.accessor(uvs, 2)?.chunks(2).map(|uv| Vec2::new(uv[0], 1.0 - uv[1])).collect::<Vec<_>>();
+            check_count(uvs.len(), count)?;
+            mesh = mesh.with_uvs(uvs);
+        }
+        Ok(Some(mesh))
+    }
+
+    // The accessor's elements, converted to f64 and flattened.
+    fn accessor(&self, accessor: usize, width: usize) -> Result<Vec<f64>, GltfError> {
+        let accessor = &self.json["accessors"][accessor];
+        if accessor.is_null() {
+            return Err(GltfError::Invalid("accessor index out of range"));
+        }
+        if !accessor["sparse"].is_null() {
+            return Err(GltfError::Unsupported("sparse accessors"));
+        }
+        let expected_type = ["SCALAR", "VEC2", "VEC3"][width - 1];
+        if accessor["type"].as_str() != Some(expected_type) {
+            return Err(GltfError::Invalid("unexpected accessor type"));
+        }
+        let count = index(&accessor["count"]).ok_or(GltfError::Invalid("accessor without count"))?;
+        let (size, signed, scale, read): (usize, bool, f64, ReadComponent) = match accessor["componentType"].as_u64() {
+            Some(5120) => (1, true, 127.0, |b| b[0] as i8 as f64),
+            Some(5121) => (1, false, 255.0, |b| b[0] as f64),
+            Some(5122) => (2, true, 32767.0, |b| i16::from_le_bytes([b[0], b[1]]) as f64),
+            Some(5123) => (2, false, 65535.0, |b| u16::from_le_bytes([b[0], b[1]]) as f64),
+            Some(5125) => (4, false, 1.0, |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64),
+            Some(5126) => (4, false, 1.0, |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64),
+            _ => return Err(GltfError::Invalid("unknown accessor component type")),
+        };
+        // Normalized integers map onto [0, 1], or [-1, 1] if signed, where
+        // both the lowest and the next value become -1.
+        let normalized = accessor["normalized"].as_bool() == Some(true);
+        let convert = |value: f64| match (normalized, signed) {
+            (true, true) => (value / scale).max(-1.0),
+            (true, false) => value / scale,
+            (false, _) => value,
+        };
+
+        // The sizes come from the file, so they are checked for overflow
+        // before anything is allocated or read.
+        let out_of_range = || GltfError::Invalid("accessor out of range");
+        let length = count.checked_mul(width).ok_or_else(out_of_range)?;
+        let element_size = width * size;
+        let view = match index(&accessor["bufferView"]) {
+            Some(view) => &self.json["bufferViews"][view],
+            // Without a buffer view every element is zero. Such accessors
+            // are meant to be filled in by sparse data, so one larger than
+            // all the buffers is taken to be broken.
+            None => {
+                let buffers: usize = self.buffers.iter().map(Vec::len).sum();
+                if length.checked_mul(size).is_none_or(|bytes| bytes > buffers) {
+                    return Err(out_of_range());
+                }
+                return Ok(vec![0.0; length]);
+            }
+        };
+        let buffer = index(&view["buffer"])
+            .and_then(|buffer| self.buffers.get(buffer))
+            .ok_or(GltfError::Invalid("buffer view without a valid buffer"))?;
+        let view_start = index(&view["byteOffset"]).unwrap_or(0);
+        let view_length = index(&view["byteLength"]).ok_or(GltfError::Invalid("buffer view without length"))?;
+        let view_bytes = view_start.checked_add(view_length)
+            .and_then(|view_end| buffer.get(view_start..view_end))
+            .ok_or(GltfError::Invalid("buffer view out of range"))?;
+        let offset = index(&accessor["byteOffset"]).unwrap_or(0);
+        let stride = index(&view["byteStride"]).unwrap_or(element_size);
+        if count > 0 {
+            let end = (count - 1).checked_mul(stride)
+                .and_then(|last| last.checked_add(offset))
+                .and_then(|last| last.checked_add(element_size));
+            if end.is_none_or(|end| end > view_bytes.len()) {
+                return Err(out_of_range());
+            }
+        }
+
+        let mut values = Vec::with_capacity(length);
+        for element in 0..count {
+            for component in 0..width {
+                let start = offset + element * stride + component * size;
+                values.push(convert(read(&view_bytes[start..start + size])));
+            }
+        }
+        Ok(values)
+    }
+}
+
+// Reads one component from its little-endian bytes.
+type ReadComponent = fn(&[u8]) -> f64;
+
+// Metallic-roughness PBR onto this renderer's materials: emissive surfaces
+// become DiffuseLight, transmissive ones Dielectric, mostly metallic ones
+// Metal with the roughness as fuzz and the rest Lambertian.
+fn material(material: &Value) -> Arc<dyn Material> {
+    let pbr = &material["pbrMetallicRoughness"];
+    let extensions = &material["extensions"];
+    let base = numbers::<4>(&pbr["baseColorFactor"]).unwrap_or([1.0; 4]);
+    let base = Color::new(base[0], base[1], base[2]);
+    let emissive = numbers::<3>(&material["emissiveFactor"]).unwrap_or([0.0; 3]);
+    let strength = extensions["KHR_materials_emissive_strength"]["emissiveStrength"].as_f64().unwrap_or(1.0);
+    let transmission = extensions["KHR_materials_transmission"]["transmissionFactor"].as_f64().unwrap_or(0.0);
+
+    if emissive != [0.0; 3] {
+        Arc::new(DiffuseLight::new(Color::new(emissive[0], emissive[1], emissive[2]) * strength))
+    }
+    else if transmission > 0.0 {
+        Arc::new(Dielectric::new(extensions["KHR_materials_ior"]["ior"].as_f64().unwrap_or(1.5)))
+    }
+    else if pbr["metallicFactor"].as_f64().unwrap_or(1.0) >= 0.5 {
+        Arc::new(Metal::new(base, pbr["roughnessFactor"].as_f64().unwrap_or(1.0)))
+    }
+    else {
+        Arc::new(Lambertian::new(base))
+    }
+}
+
+// A node's matrix, or its translation, rotation and scale combined.
+fn local_transform(node: &Value) -> Result<Mat4, GltfError> {
+    if !node["matrix"].is_null() {
+        // Stored column by column.
+        let m = numbers::<16>(&node["matrix"]).ok_or(GltfError::Invalid("invalid node matrix"))?;
+        let mut rows = [[0.0; 4]; 4];
+        for (i, value) in m.iter().enumerate() {
+            rows[i % 4][i / 4] = *value;
+        }
+        return Ok(Mat4::new(rows));
+    }
+    let invalid = || GltfError::Invalid("invalid node transform");
+    let translation = optional_numbers::<3>(&node["translation"], [0.0; 3]).ok_or_else(invalid)?;
+    let [x, y, z, w] = optional_numbers::<4>(&node["rotation"], [0.0, 0.0, 0.0, 1.0]).ok_or_else(invalid)?;
+    let scale = optional_numbers::<3>(&node["scale"], [1.0; 3]).ok_or_else(invalid)?;
+    Ok(Mat4::translation(Vec3::from(translation)) * Quat::new(w, x, y, z).normalized().to_mat4()
+        * Mat4::scale(Vec3::from(scale)))
+}
+
+fn check_count(count: usize, expected: usize) -> Result<(), GltfError> {
+    if count == expected { Ok(()) } else { Err(GltfError::Invalid("vertex attributes differ in count")) }
+}
+
+// Empty for a missing array, as glTF leaves out empty ones.
+fn array(value: &Value) -> &[Value] {
+    value.as_array().map_or(&[], Vec::as_slice)
+}
+
+fn index(value: &Value) -> Option<usize> {
+    value.as_u64().map(|index| index as usize)
+}
+
+fn numbers<const N: usize>(value: &Value) -> Option<[f64; N]> {
+    let values = value.as_array()?;
+    if values.len() != N {
+        return None;
+    }
+    let mut result = [0.0; N];
+    for (result, value) in result.iter_mut().zip(values) {
+        *result = value.as_f64()?;
+    }
+    Some(result)
+}
+
+// The default if the value is missing, None if it is malformed.
+fn optional_numbers<const N: usize>(value: &Value, default: [f64; N]) -> Option<[f64; N]> {
+    if value.is_null() { Some(default) } else { numbers(value) }
+}
+
+const GLB_JSON: u32 = 0x4E4F_534A;
+const GLB_BIN: u32 = 0x004E_4942;
+
+// The JSON and binary chunks of a GLB file.
+fn split_glb(data: &[u8]) -> Result<(&[u8], Option<&[u8]>), GltfError> {
+    let word = |offset: usize| data.get(offset..offset + 4).map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()));
+    if word(4) != Some(2) {
+        return Err(GltfError::Unsupported("GLB versions other than 2"));
+    }
+    let (mut json, mut binary) = (None, None);
+    let mut offset = 12;
+    while let (Some(length), Some(kind)) = (word(offset), word(offset + 4)) {
+        let start = offset + 8;
+        let end = start + length as usize;
+        let chunk = data.get(start..end).ok_or(GltfError::Invalid("truncated GLB chunk"))?;
+        match kind {
+            GLB_JSON => json = json.or(Some(chunk)),
+            GLB_BIN => binary = binary.or(Some(chunk)),
+            _ => {}
+        }
+        offset = end;
+    }
+    Ok((json.ok_or(GltfError::Invalid("GLB without a JSON chunk"))?, binary))
+}
+
+// "data:" already stripped, e.g. "application/octet-stream;base64,AAAA".
+fn decode_data_uri(uri: &str) -> Result<Vec<u8>, GltfError> {
+    match uri.split_once(',') {
+        Some((header, data)) if header.ends_with(";base64") =>
+            base64(data).ok_or(GltfError::Invalid("invalid base64 in data URI")),
+        _ => Err(GltfError::Unsupported("data URIs without base64")),
+    }
+}
+
+fn base64(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+    let (mut bits, mut count) = (0u32, 0);
+    for c in text.bytes().take_while(|&c| c != b'=') {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        bits = (bits << 6 | value as u32) & 0xFFFF;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            bytes.push((bits >> count) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use serde_json::json;
+    use crate::interval::Interval;
+    use crate::material::Black;
+    use crate::ray::Ray;
+    use super::*;
+
+    // A unit right triangle in the xy plane: positions, normals, texture
+    // coordinates and u16 indices, in that order.
+    fn triangle_buffer() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let floats = [
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0,
+            0.0, 1.0, 1.0, 1.0, 0.0, 0.0f32,
+        ];
+        for value in &floats {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for index in &[0u16, 1, 2] {
+            bytes.extend_from_slice(&index.to_le_bytes());
+        }
+        bytes
+    }
+
+    // The triangle mesh as the only mesh, with the given nodes, buffer and
+    // materials.
+    fn document(nodes: Value, buffer: Value, materials: Value) -> Value {
+        json!({
+            "asset": { "version": "2.0" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": nodes,
+            "meshes": [{ "primitives": [{
+                "attributes": { "POSITION": 0, "NORMAL": 1, "TEXCOORD_0": 2 },
+                "indices": 3,
+                "material": 0,
+            }] }],
+            "materials": materials,
+            "accessors": [
+                { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" },
+                { "bufferView": 0, "byteOffset": 36, "componentType": 5126, "count": 3, "type": "VEC3" },
+                { "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC2" },
+                { "bufferView": 2, "componentType": 5123, "count": 3, "type": "SCALAR" },
+            ],
+            "bufferViews": [
+                { "buffer": 0, "byteLength": 72 },
+                { "buffer": 0, "byteOffset": 72, "byteLength": 24 },
+                { "buffer": 0, "byteOffset": 96, "byteLength": 6 },
+            ],
+            "buffers": [buffer],
+        })
+    }
+
+    fn red() -> Value {
+        json!([{ "pbrMetallicRoughness": { "baseColorFactor": [1.0, 0.0, 0.0, 1.0], "metallicFactor": 0.0 } }])
+    }
+
+    fn glb(json: &Value, binary: &[u8]) -> Vec<u8> {
+        let mut json = serde_json::to_vec(json).unwrap();
+        json.resize(json.len().div_ceil(4) * 4, b' ');
+        let mut binary = binary.to_vec();
+        binary.resize(binary.len().div_ceil(4) * 4, 0);
+        let mut data = Vec::new();
+        for word in &[0x4654_6C67, 2, (28 + json.len() + binary.len()) as u32, json.len() as u32, GLB_JSON] {
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+        data.extend_from_slice(&json);
+        data.extend_from_slice(&(binary.len() as u32).to_le_bytes());
+        data.extend_from_slice(&GLB_BIN.to_le_bytes());
+        data.extend_from_slice(&binary);
+        data
+    }
+
+    fn no_files(uri: &str) -> io::Result<Vec<u8>> {
+        Err(io::Error::new(io::ErrorKind::NotFound, uri))
+    }
+
+    #[test]
+    fn glb_with_translated_node() {
+        let nodes = json!([{ "mesh": 0, "translation": [0.0, 0.0, -2.0] }]);
+        let data = glb(&document(nodes, json!({ "byteLength": 102 }), red()), &triangle_buffer());
+        let instances = parse_gltf(&data, Arc::new(Black), no_files).unwrap();
+        assert_eq!(1, instances.len());
+        let mesh = instances[0].mesh();
+        assert_eq!(&[[0, 1, 2]], mesh.faces());
+        assert_vec3_equal!(Point3::new(1.0, 0.0, 0.0), mesh.positions()[1]);
+        assert_vec3_equal!(Vec3::new(1.0, 0.0, -2.0), instances[0].transform().transform_point(Vec3::X));
+        assert_vec3_equal!(Vec3::Z, mesh.normals().unwrap()[0]);
+        // Flipped from glTF's v = 1 at the bottom.
+        assert_approx_eq!(0.0, mesh.uvs().unwrap()[0].y());
+        assert_material_equal!(Lambertian::new(Color::new(1.0, 0.0, 0.0)), mesh.material());
+    }
+
+    #[test]
+    fn node_hierarchy_and_instances() {
+        // A quarter turn about z, with a child scaled 2 and mirrored in x,
+        // which uses the mesh again.
+        let half = 0.5f64.sqrt();
+        let nodes = json!([
+            { "mesh": 0, "rotation": [0.0, 0.0, half, half], "children": [1] },
+            { "mesh": 0, "matrix": [-2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 1.0] },
+        ]);
+        let buffer = json!({ "uri": "triangle.bin", "byteLength": 102 });
+        let json = serde_json::to_vec(&document(nodes, buffer, red())).unwrap();
+        let mut requested = Vec::new();
+        let instances = parse_gltf(&json, Arc::new(Black), |uri| {
+            requested.push(uri.to_string());
+            Ok(triangle_buffer())
+        }).unwrap();
+        assert_eq!(vec!["triangle.bin"], requested);
+        assert_eq!(2, instances.len());
+        assert!(Arc::ptr_eq(instances[0].mesh(), instances[1].mesh()));
+        assert_vec3_equal!(Vec3::new(0.0, 1.0, 0.0), instances[0].transform().transform_point(Vec3::X));
+        // Scaled and mirrored, then rotated: x = 1 goes to (-2, 0, 0) and
+        // then to (0, -2, 0).
+        let child = instances[1].transform();
+        assert_vec3_equal!(Vec3::new(0.0, -2.0, 0.0), child.transform_point(Vec3::X));
+        assert_vec3_equal!(Vec3::new(-2.0, 0.0, 0.0), child.transform_point(Vec3::Y));
+
+        // Both instances trace the one mesh, and the mirrored one's normal
+        // still faces +z.
+        let hittables = to_hittables(&instances);
+        assert_eq!(2, hittables.len());
+        assert!(Rc::ptr_eq(hittables[0].object(), hittables[1].object()));
+        let down = |x, y| Ray::new(Point3::new(x, y, 1.0), -Vec3::Z);
+        assert!(hittables[0].hit(&down(-0.25, 0.25), Interval::FORWARD).is_some());
+        assert!(hittables[0].hit(&down(-0.5, -0.5), Interval::FORWARD).is_none());
+        let record = hittables[1].hit(&down(-0.5, -0.5), Interval::FORWARD).unwrap();
+        assert_approx_eq!(1.0, record.t);
+        assert_vec3_equal!(Vec3::Z, record.normal);
+        assert!(record.front_face);
+    }
+
+    #[test]
+    fn nodes_scaled_to_nothing_are_left_out() {
+        let nodes = json!([{ "mesh": 0, "scale": [1.0, 0.0, 1.0] }]);
+        let data = glb(&document(nodes, json!({ "byteLength": 102 }), red()), &triangle_buffer());
+        let instances = parse_gltf(&data, Arc::new(Black), no_files).unwrap();
+        assert_eq!(1, instances.len());
+        assert!(to_hittables(&instances).is_empty());
+    }
+
+    #[test]
+    fn embedded_buffer() {
+        assert_eq!(b"hello".to_vec(), base64("aGVsbG8=").unwrap());
+        assert!(base64("a*=").is_none());
+
+        let nodes = json!([{ "mesh": 0 }]);
+        let uri = format!("data:application/octet-stream;base64,{}", encode(&triangle_buffer()));
+        let json = serde_json::to_vec(&document(nodes, json!({ "uri": uri, "byteLength": 102 }), red())).unwrap();
+        let instances = parse_gltf(&json, Arc::new(Black), no_files).unwrap();
+        assert_vec3_equal!(Point3::new(0.0, 1.0, 0.0), instances[0].mesh().positions()[2]);
+    }
+
+    fn encode(bytes: &[u8]) -> String {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut text = String::new();
+        for chunk in bytes.chunks(3) {
+            let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, &byte)| bits | (byte as u32) << (16 - 8 * i));
+            for i in 0..=chunk.len() {
+                text.push(ALPHABET[(bits >> (18 - 6 * i) & 63) as usize] as char);
+            }
+        }
+        while !text.len().is_multiple_of(4) {
+            text.push('=');
+        }
+        text
+    }
+
+    // A document with only the given accessors over one buffer view
+    // spanning the whole buffer.
+    fn accessors(accessors: Value, buffer: Vec<u8>) -> (Value, Vec<Vec<u8>>) {
+        let json = json!({
+            "accessors": accessors,
+            "bufferViews": [{ "buffer": 0, "byteLength": buffer.len() }],
+        });
+        (json, vec![buffer])
+    }
+
+    fn read_accessor(json: &Value, buffers: Vec<Vec<u8>>, accessor: usize, width: usize) -> Result<Vec<f64>, GltfError> {
+        let document = Document { json, buffers, materials: Vec::new(), default_material: Arc::new(Black), meshes: HashMap::new() };
+        document.accessor(accessor, width)
+    }
+
+    #[test]
+    fn component_conversion() {
+        let mut buffer = Vec::new();
+        for value in &[-2.0f32, -0.5, 3.0] {
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        buffer.extend_from_slice(&[0x80, 0x81, 0xC1, 0x7F]);
+        buffer.extend_from_slice(&[0x00, 0xFF]);
+        let (json, buffers) = accessors(json!([
+            { "bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC3" },
+            { "bufferView": 0, "byteOffset": 12, "componentType": 5120, "normalized": true, "count": 2, "type": "VEC2" },
+            { "bufferView": 0, "byteOffset": 12, "componentType": 5120, "count": 2, "type": "VEC2" },
+            { "bufferView": 0, "byteOffset": 16, "componentType": 5121, "normalized": true, "count": 1, "type": "VEC2" },
+        ]), buffer);
+        // Negative floats and integers that aren't normalized are kept.
+        assert_eq!(vec![-2.0, -0.5, 3.0], read_accessor(&json, buffers.clone(), 0, 3).unwrap());
+        assert_eq!(vec![-128.0, -127.0, -63.0, 127.0], read_accessor(&json, buffers.clone(), 2, 2).unwrap());
+        // -128 and -127 both become -1.
+        let signed = read_accessor(&json, buffers.clone(), 1, 2).unwrap();
+        assert_eq!(vec![-1.0, -1.0], signed[..2].to_vec());
+        assert_approx_eq!(-63.0 / 127.0, signed[2]);
+        assert_eq!(1.0, signed[3]);
+        assert_eq!(vec![0.0, 1.0], read_accessor(&json, buffers, 3, 2).unwrap());
+    }
+
+    #[test]
+    fn accessor_sizes_are_checked() {
+        let huge = usize::MAX / 2;
+        let (json, buffers) = accessors(json!([
+            { "bufferView": 0, "componentType": 5126, "count": huge, "type": "VEC3" },
+            { "bufferView": 0, "byteOffset": huge, "componentType": 5126, "count": 1, "type": "VEC3" },
+            { "componentType": 5126, "count": huge, "type": "VEC3" },
+            { "componentType": 5126, "count": 1000, "type": "VEC3" },
+            { "componentType": 5126, "count": 1, "type": "VEC3" },
+            { "bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC3" },
+        ]), vec![0; 12]);
+        for accessor in 0..4 {
+            assert!(matches!(read_accessor(&json, buffers.clone(), accessor, 3), Err(GltfError::Invalid("accessor out of range"))));
+        }
+        assert_eq!(vec![0.0; 3], read_accessor(&json, buffers.clone(), 4, 3).unwrap());
+        assert_eq!(vec![0.0; 3], read_accessor(&json, buffers.clone(), 5, 3).unwrap());
+
+        let mut json = json;
+        json["bufferViews"][0] = json!({ "buffer": 0, "byteOffset": 4, "byteLength": usize::MAX });
+        assert!(matches!(read_accessor(&json, buffers, 5, 3), Err(GltfError::Invalid("buffer view out of range"))));
+    }
+
+    #[test]
+    fn materials() {
+        let material = |json: Value| material(&json);
+        assert_material_equal!(Metal::new(Color::WHITE, 1.0), material(json!({})));
+        let gold = json!({ "pbrMetallicRoughness": { "baseColorFactor": [1.0, 0.8, 0.3, 1.0], "roughnessFactor": 0.25 } });
+        assert_material_equal!(Metal::new(Color::new(1.0, 0.8, 0.3), 0.25), material(gold));
+        let glass = json!({ "extensions": {
+            "KHR_materials_transmission": { "transmissionFactor": 1.0 },
+            "KHR_materials_ior": { "ior": 1.33 },
+        } });
+        assert_material_equal!(Dielectric::new(1.33), material(glass));
+        let lamp = json!({ "emissiveFactor": [1.0, 0.5, 0.25], "extensions": {
+            "KHR_materials_emissive_strength": { "emissiveStrength": 4.0 },
+        } });
+        assert_material_equal!(DiffuseLight::new(Color::new(4.0, 2.0, 1.0)), material(lamp));
+    }
+
+    #[test]
+    fn errors() {
+        let parse = |json: Value| parse_gltf(&serde_json::to_vec(&json).unwrap(), Arc::new(Black), no_files);
+        let buffer = json!({ "byteLength": 102 });
+        assert!(matches!(parse_gltf(b"{", Arc::new(Black), no_files), Err(GltfError::Json(_))));
+        assert!(matches!(parse(json!({ "asset": { "version": "2.0" } })), Err(GltfError::Invalid("no scene"))));
+        // The GLB buffer without a GLB file.
+        assert!(matches!(parse(document(json!([{ "mesh": 0 }]), buffer.clone(), red())), Err(GltfError::Invalid(_))));
+        let external = json!({ "uri": "missing.bin", "byteLength": 102 });
+        assert!(matches!(parse(document(json!([{ "mesh": 0 }]), external, red())), Err(GltfError::Io(_))));
+
+        let mut broken = document(json!([{ "mesh": 0 }]), buffer.clone(), red());
+        broken["accessors"][0]["count"] = json!(10);
+        let data = glb(&broken, &triangle_buffer());
+        assert!(matches!(parse_gltf(&data, Arc::new(Black), no_files), Err(GltfError::Invalid("accessor out of range"))));
+
+        let cycle = json!([{ "children": [1] }, { "children": [0] }]);
+        let data = glb(&document(cycle, buffer, red()), &triangle_buffer());
+        assert!(matches!(parse_gltf(&data, Arc::new(Black), no_files), Err(GltfError::Invalid("node hierarchy has a cycle"))));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::aabb::Aabb;
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::vector::{Vec2, Vec3};
+
+// Where a ray hit a surface. normal always points against the ray, so
+// front_face tells which side of the surface was hit. uv are the surface's
+// texture coordinates at the hit, zero for surfaces without any. The
+// material is borrowed from the object that was hit.
+#[derive(Clone, Copy, Debug)]
+pub struct HitRecord<'a> {
+    pub point: Point3,
+    pub normal: Vec3,
+    pub t: f64,
+    pub front_face: bool,
+    pub uv: Vec2,
+    pub material: &'a dyn Material,
+}
+
+impl<'a> HitRecord<'a> {
+    // outward_normal is the unit surface normal pointing out of the object.
+    pub fn new(ray: &Ray, t: f64, outward_normal: Vec3, material: &'a dyn Material) -> HitRecord<'a> {
+        let front_face = ray.direction().dot(outward_normal) < 0.0;
+        HitRecord {
+            point: ray.at(t),
+            normal: if front_face { outward_normal } else { -outward_normal },
+            t,
+            front_face,
+            uv: Vec2::ZERO,
+            material,
+        }
+    }
+
+    pub fn with_uv(self, uv: Vec2) -> HitRecord<'a> {
+        HitRecord { uv, ..self }
+    }
+}
+
+// Anything a ray can hit. Hits are only reported for ray parameters t
+// inside t_range, which also lets callers ask for the nearest hit closer
+// than one they already have.
+pub trait Hittable {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>>;
+
+    // A box containing the object at all times, or None if it is unbounded,
+    // like an infinite plane.
+    fn bounding_box(&self) -> Option<Aabb>;
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use super::*;
+
+    // The plane z = 0, facing +z.
+    struct Floor;
+
+    impl Hittable for Floor {
+        fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+            let t = -ray.origin().z() / ray.direction().z();
+            if t_range.surrounds(t) {
+                Some(HitRecord::new(ray, t, Vec3::Z, &Black))
+            }
+            else {
+                None
+            }
+        }
+
+        fn bounding_box(&self) -> Option<Aabb> {
+            None
+        }
+    }
+
+    #[test]
+    fn front_face() {
+        let ray = Ray::new(Point3::new(1.0, 2.0, 4.0), Vec3::new(0.0, 0.0, -2.0));
+        let record = HitRecord::new(&ray, 2.0, Vec3::Z, &Black);
+        assert!(record.front_face);
+        assert_vec3_equal!(Vec3::Z, record.normal);
+        assert_vec3_equal!(Point3::new(1.0, 2.0, 0.0), record.point);
+        assert_approx_eq!(2.0, record.t, 0.0001);
+    }
+
+    #[test]
+    fn back_face() {
+        let ray = Ray::new(Point3::new(0.0, 0.0, -1.0), Vec3::Z);
+        let record = HitRecord::new(&ray, 1.0, Vec3::Z, &Black);
+        assert!(!record.front_face);
+        assert_vec3_equal!(-Vec3::Z, record.normal);
+    }
+
+    #[test]
+    fn hit_respects_t_range() {
+        let ray = Ray::new(Point3::new(0.0, 0.0, 3.0), -Vec3::Z);
+        let hittable: &dyn Hittable = &Floor;
+        assert_approx_eq!(3.0, hittable.hit(&ray, Interval::FORWARD).unwrap().t, 0.0001);
+        assert!(hittable.hit(&ray, Interval::new(0.001, 2.0)).is_none());
+
+        // Pointing away from the floor, the hit would be behind the origin.
+        let away = Ray::new(Point3::new(0.0, 0.0, 3.0), Vec3::Z);
+        assert!(hittable.hit(&away, Interval::FORWARD).is_none());
+        assert!(hittable.hit(&away, Interval::UNIVERSE).is_some());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::aabb::Aabb;
+use crate::bvh::BvhNode;
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::ray::Ray;
+
+// A scene: a collection of objects hit as one, reporting the closest hit.
+#[derive(Default)]
+pub struct HittableList {
+    objects: Vec<Box<dyn Hittable>>,
+}
+
+impl HittableList {
+    pub fn new() -> HittableList {
+        HittableList { objects: Vec::new() }
+    }
+
+    pub fn add<H: Hittable + 'static>(&mut self, object: H) {
+        self.objects.push(Box::new(object));
+    }
+
+    pub fn clear(&mut self) {
+        self.objects.clear();
+    }
+
+    pub fn objects(&self) -> &[Box<dyn Hittable>] {
+        &self.objects
+    }
+
+    pub fn into_objects(self) -> Vec<Box<dyn Hittable>> {
+        self.objects
+    }
+
+    // The same objects, with a BvhNode over the bounded ones. Unbounded
+    // objects such as planes can't go in the tree and are kept next to it,
+    // hit one by one.
+    pub fn accelerated(self) -> HittableList {
+        let (bounded, unbounded): (Vec<_>, Vec<_>) = self.objects.into_iter()
+            .partition(|object| object.bounding_box().is_some());
+        let mut objects = unbounded;
+        if let Some(bvh) = BvhNode::new(bounded) {
+            objects.insert(0, Box::new(bvh));
+        }
+        HittableList { objects }
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+}
+
+impl Hittable for HittableList {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        // Each hit narrows the range, so later objects only count if closer.
+        let mut closest: Option<HitRecord> = None;
+        for object in &self.objects {
+            let max = closest.map_or(t_range.max, |record| record.t);
+            if let Some(record) = object.hit(ray, Interval::new(t_range.min, max)) {
+                closest = Some(record);
+            }
+        }
+        closest
+    }
+
+    // None if the list is empty or anything in it is unbounded.
+    fn bounding_box(&self) -> Option<Aabb> {
+        if self.objects.is_empty() {
+            return None;
+        }
+        self.objects.iter().try_fold(Aabb::EMPTY, |aabb, object| {
+            object.bounding_box().map(|object_box| Aabb::surrounding_box(aabb, object_box))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use assert_approx_eq::*;
+    use crate::point::Point3;
+    use crate::material::Black;
+    use crate::plane::Plane;
+    use crate::sphere::Sphere;
+    use crate::vector::Vec3;
+    use super::*;
+
+    fn sphere(center: Point3, radius: f64) -> Sphere {
+        Sphere::new(center, radius, Arc::new(Black))
+    }
+
+    #[test]
+    fn empty_list_hits_nothing() {
+        let list = HittableList::new();
+        assert!(list.is_empty());
+        assert!(list.hit(&Ray::new(Point3::ORIGIN, Vec3::X), Interval::UNIVERSE).is_none());
+    }
+
+    #[test]
+    fn closest_hit_wins_regardless_of_order() {
+        let ray = Ray::new(Point3::ORIGIN, -Vec3::Z);
+        let far = sphere(Point3::new(0.0, 0.0, -10.0), 1.0);
+        let near = sphere(Point3::new(0.0, 0.0, -3.0), 1.0);
+
+        let mut list = HittableList::new();
+        list.add(far.clone());
+        list.add(near.clone());
+        assert_eq!(2, list.len());
+        assert_approx_eq!(2.0, list.hit(&ray, Interval::FORWARD).unwrap().t, 0.0001);
+
+        let mut list = HittableList::new();
+        list.add(near);
+        list.add(far);
+        assert_approx_eq!(2.0, list.hit(&ray, Interval::FORWARD).unwrap().t, 0.0001);
+    }
+
+    #[test]
+    fn range_is_respected() {
+        let mut list = HittableList::new();
+        list.add(sphere(Point3::new(0.0, 0.0, -3.0), 1.0));
+        list.add(sphere(Point3::new(0.0, 0.0, -10.0), 1.0));
+        let ray = Ray::new(Point3::ORIGIN, -Vec3::Z);
+        assert_approx_eq!(9.0, list.hit(&ray, Interval::new(5.0, f64::INFINITY)).unwrap().t, 0.0001);
+        assert!(list.hit(&ray, Interval::new(0.001, 1.0)).is_none());
+    }
+
+    #[test]
+    fn nested_lists() {
+        let mut inner = HittableList::new();
+        inner.add(sphere(Point3::new(0.0, 0.0, -3.0), 1.0));
+        let mut outer = HittableList::new();
+        outer.add(inner);
+        outer.add(sphere(Point3::new(0.0, 0.0, -10.0), 1.0));
+        assert_approx_eq!(2.0, outer.hit(&Ray::new(Point3::ORIGIN, -Vec3::Z), Interval::FORWARD).unwrap().t, 0.0001);
+
+        outer.clear();
+        assert!(outer.is_empty());
+    }
+
+    #[test]
+    fn bounding_box_covers_every_object() {
+        let mut list = HittableList::new();
+        assert!(list.bounding_box().is_none());
+        list.add(sphere(Point3::new(0.0, 0.0, -3.0), 1.0));
+        list.add(sphere(Point3::new(2.0, 1.0, -10.0), 0.5));
+        let aabb = list.bounding_box().unwrap();
+        assert_vec3_equal!(Point3::new(-1.0, -1.0, -10.5), aabb.min());
+        assert_vec3_equal!(Point3::new(2.5, 1.5, -2.0), aabb.max());
+    }
+
+    #[test]
+    fn accelerated_keeps_unbounded_objects_out_of_the_tree() {
+        let mut list = HittableList::new();
+        for i in 0..10 {
+            list.add(sphere(Point3::new(i as f64 * 3.0, 0.0, -5.0), 1.0));
+        }
+        list.add(Plane::new(Point3::new(0.0, -1.0, 0.0), Vec3::Y, Arc::new(Black)));
+        let accelerated = list.accelerated();
+        assert_eq!(2, accelerated.len());
+        assert!(accelerated.objects()[0].bounding_box().is_some());
+        assert!(accelerated.bounding_box().is_none());
+
+        let sphere_hit = accelerated.hit(&Ray::new(Point3::new(6.0, 0.0, 0.0), -Vec3::Z), Interval::FORWARD).unwrap();
+        assert_approx_eq!(4.0, sphere_hit.t);
+        let plane_hit = accelerated.hit(&Ray::new(Point3::new(1.5, 0.0, -5.0), -Vec3::Y), Interval::FORWARD).unwrap();
+        assert_approx_eq!(1.0, plane_hit.t);
+
+        let mut planes = HittableList::new();
+        planes.add(Plane::new(Point3::ORIGIN, Vec3::Y, Arc::new(Black)));
+        assert_eq!(1, planes.accelerated().len());
+        assert!(HittableList::new().accelerated().is_empty());
+    }
+}
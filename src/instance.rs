@@ -0,0 +1,132 @@
+use std::rc::Rc;
+
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::matrix::{Mat3, Mat4};
+use crate::point::Point3;
+use crate::ray::Ray;
+
+// An object placed by an affine transform, so that one object, e.g. a mesh
+// and its BVH, can appear in several places while being stored once. Rays
+// are taken into the object's space to be tested. Their directions are
+// transformed without renormalizing, which keeps t the same in both spaces.
+// Hittables aren't thread safe, so the object is shared with an Rc.
+#[derive(Clone)]
+pub struct Instance {
+    object: Rc<dyn Hittable>,
+    transform: Mat4,
+    inverse: Mat4,
+    // The inverse transpose, which keeps normals perpendicular to surfaces
+    // under non-uniform scaling.
+    normal_matrix: Mat3,
+}
+
+impl Instance {
+    // None if the transform can't be inverted, e.g. a scale of zero.
+    pub fn new(object: Rc<dyn Hittable>, transform: Mat4) -> Option<Instance> {
+        let inverse = transform.inverse()?;
+        Some(Instance { object, transform, inverse, normal_matrix: inverse.to_mat3().transpose() })
+    }
+
+    pub fn object(&self) -> &Rc<dyn Hittable> {
+        &self.object
+    }
+
+    pub fn transform(&self) -> Mat4 {
+        self.transform
+    }
+}
+
+impl Hittable for Instance {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        let origin = self.inverse.transform_point(ray.origin().to_vec3());
+        let direction = self.inverse.transform_direction(ray.direction());
+        let local = Ray::with_time(Point3::from_vec3(origin), direction, ray.time());
+        let mut record = self.object.hit(&local, t_range)?;
+        // The normal still faces against the ray after the transform, so
+        // front_face is kept.
+        record.point = ray.at(record.t);
+        record.normal = (self.normal_matrix * record.normal).unit_vector();
+        Some(record)
+    }
+
+    // The box around the transformed corners of the object's box.
+    fn bounding_box(&self) -> Option<Aabb> {
+        let aabb = self.object.bounding_box()?;
+        let (min, max) = (aabb.min(), aabb.max());
+        let corners = (0..8).map(|i| {
+            let corner = Point3::new(
+                if i & 1 == 0 { min.x() } else { max.x() },
+                if i & 2 == 0 { min.y() } else { max.y() },
+                if i & 4 == 0 { min.z() } else { max.z() },
+            );
+            let corner = Point3::from_vec3(self.transform.transform_point(corner.to_vec3()));
+            Aabb::new(corner, corner)
+        });
+        Some(corners.fold(Aabb::EMPTY, Aabb::surrounding_box))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use crate::quaternion::Quat;
+    use crate::sphere::Sphere;
+    use crate::vector::Vec3;
+    use super::*;
+
+    fn unit_sphere() -> Rc<dyn Hittable> {
+        Rc::new(Sphere::new(Point3::ORIGIN, 1.0, Arc::new(Black)))
+    }
+
+    #[test]
+    fn hit_stretched_and_moved_sphere() {
+        // An ellipsoid with semi-axes 2, 1 and 1 centered at (0, 0, -5).
+        let transform = Mat4::translation(Vec3::new(0.0, 0.0, -5.0)) * Mat4::scale(Vec3::new(2.0, 1.0, 1.0));
+        let instance = Instance::new(unit_sphere(), transform).unwrap();
+
+        let record = instance.hit(&Ray::new(Point3::new(5.0, 0.0, -5.0), -Vec3::X), Interval::FORWARD).unwrap();
+        assert_approx_eq!(3.0, record.t);
+        assert_vec3_equal!(Point3::new(2.0, 0.0, -5.0), record.point);
+        assert_vec3_equal!(Vec3::X, record.normal);
+        assert!(record.front_face);
+
+        // Relative to the center, the surface is x^2 / 4 + z^2 = 1 and its
+        // normal is along (x / 4, z), not along the scaled sphere normal.
+        let half = 0.5f64.sqrt();
+        let below = Ray::new(Point3::new(2.0 * half, 0.0, 0.0), -Vec3::Z);
+        let record = instance.hit(&below, Interval::FORWARD).unwrap();
+        assert_approx_eq!(5.0 - half, record.t);
+        assert_vec3_equal!(Vec3::new(half / 2.0, 0.0, half).unit_vector(), record.normal);
+
+        assert!(instance.hit(&Ray::new(Point3::new(0.0, 1.5, 0.0), -Vec3::Z), Interval::FORWARD).is_none());
+    }
+
+    #[test]
+    fn hit_from_inside_a_mirrored_instance() {
+        let instance = Instance::new(unit_sphere(), Mat4::scale(Vec3::new(-1.0, 1.0, 1.0))).unwrap();
+        let record = instance.hit(&Ray::new(Point3::ORIGIN, Vec3::X), Interval::FORWARD).unwrap();
+        assert_approx_eq!(1.0, record.t);
+        assert!(!record.front_face);
+        assert_vec3_equal!(-Vec3::X, record.normal);
+    }
+
+    #[test]
+    fn bounding_box_of_rotated_object() {
+        let half = 0.5f64.sqrt();
+        // A quarter turn about z.
+        let rotation = Quat::new(half, 0.0, 0.0, half).to_mat4();
+        let transform = Mat4::translation(Vec3::new(1.0, 0.0, 0.0)) * rotation * Mat4::scale(Vec3::new(3.0, 1.0, 1.0));
+        let aabb = Instance::new(unit_sphere(), transform).unwrap().bounding_box().unwrap();
+        assert_vec3_equal!(Point3::new(0.0, -3.0, -1.0), aabb.min());
+        assert_vec3_equal!(Point3::new(2.0, 3.0, 1.0), aabb.max());
+    }
+
+    #[test]
+    fn singular_transform() {
+        assert!(Instance::new(unit_sphere(), Mat4::scale(Vec3::new(1.0, 0.0, 1.0))).is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use core::fmt;
+
+// A closed range of reals, e.g. the ray parameters t a hit is accepted for.
+// An interval with min > max is empty.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Interval {
+    pub const EMPTY: Interval = Interval::new(f64::INFINITY, f64::NEG_INFINITY);
+    pub const UNIVERSE: Interval = Interval::new(f64::NEG_INFINITY, f64::INFINITY);
+    // Everything in front of a ray's origin, starting a little past it so
+    // that rays leaving a surface don't hit it again through rounding.
+    pub const FORWARD: Interval = Interval::new(0.001, f64::INFINITY);
+
+    pub const fn new(min: f64, max: f64) -> Interval {
+        Interval { min, max }
+    }
+
+    pub fn size(&self) -> f64 {
+        self.max - self.min
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min > self.max
+    }
+
+    // min <= x <= max
+    pub fn contains(&self, x: f64) -> bool {
+        self.min <= x && x <= self.max
+    }
+
+    // min < x < max, for when a hit exactly at the end points is rejected.
+    pub fn surrounds(&self, x: f64) -> bool {
+        self.min < x && x < self.max
+    }
+
+    pub fn clamp(&self, x: f64) -> f64 {
+        if x < self.min {
+            self.min
+        }
+        else if x > self.max {
+            self.max
+        }
+        else {
+            x
+        }
+    }
+
+    // Grows the interval by delta in total, half on each side.
+    pub fn expand(&self, delta: f64) -> Interval {
+        let padding = delta / 2.0;
+        Interval::new(self.min - padding, self.max + padding)
+    }
+
+    // Smallest interval containing both.
+    pub fn union(&self, other: Interval) -> Interval {
+        Interval::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    // Overlap of both, empty if they are disjoint.
+    pub fn intersection(&self, other: Interval) -> Interval {
+        Interval::new(self.min.max(other.min), self.max.min(other.max))
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {}]", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use super::*;
+
+    #[test]
+    fn size_and_emptiness() {
+        assert_approx_eq!(3.0, Interval::new(-1.0, 2.0).size(), 0.0001);
+        assert!(!Interval::new(1.0, 1.0).is_empty());
+        assert!(Interval::new(2.0, 1.0).is_empty());
+        assert!(Interval::EMPTY.is_empty());
+        assert!(!Interval::UNIVERSE.is_empty());
+    }
+
+    #[test]
+    fn contains_and_surrounds() {
+        let interval = Interval::new(0.0, 1.0);
+        assert!(interval.contains(0.0));
+        assert!(interval.contains(1.0));
+        assert!(!interval.contains(1.5));
+        assert!(!interval.surrounds(0.0));
+        assert!(interval.surrounds(0.5));
+        assert!(!Interval::EMPTY.contains(0.0));
+        assert!(Interval::UNIVERSE.surrounds(1e300));
+    }
+
+    #[test]
+    fn clamp() {
+        let interval = Interval::new(0.0, 0.999);
+        assert_approx_eq!(0.0, interval.clamp(-2.0), 0.0001);
+        assert_approx_eq!(0.5, interval.clamp(0.5), 0.0001);
+        assert_approx_eq!(0.999, interval.clamp(3.0), 0.0001);
+    }
+
+    #[test]
+    fn expand() {
+        let interval = Interval::new(1.0, 2.0).expand(0.5);
+        assert!(interval == Interval::new(0.75, 2.25));
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let a = Interval::new(0.0, 2.0);
+        let b = Interval::new(1.0, 5.0);
+        assert!(a.union(b) == Interval::new(0.0, 5.0));
+        assert!(a.intersection(b) == Interval::new(1.0, 2.0));
+        assert!(a.intersection(Interval::new(3.0, 4.0)).is_empty());
+        assert!(Interval::EMPTY.union(a) == a);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("[0.5, 2]", format!("{}", Interval::new(0.5, 2.0)));
+    }
+}
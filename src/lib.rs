@@ -0,0 +1,64 @@
+// Without the std feature the math types are no_std and take their float
+// functions from libm instead.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("vectors needs either the `std` or the `libm` feature for float math");
+
+#[cfg(test)]
+#[macro_use]
+mod test_macros;
+
+pub mod scalar;
+pub mod math;
+pub mod vector;
+pub mod point;
+pub mod color;
+pub mod matrix;
+pub mod quaternion;
+pub mod onb;
+pub mod packet;
+pub mod interval;
+pub mod ray;
+pub mod aabb;
+pub mod camera;
+pub mod hittable;
+pub mod material;
+pub mod background;
+#[cfg(feature = "std")]
+pub mod environment;
+#[cfg(feature = "std")]
+pub mod sphere;
+#[cfg(feature = "std")]
+pub mod moving_sphere;
+#[cfg(feature = "std")]
+pub mod plane;
+#[cfg(feature = "std")]
+pub mod disk;
+#[cfg(feature = "std")]
+pub mod rect;
+#[cfg(feature = "std")]
+pub mod box_obj;
+#[cfg(feature = "std")]
+pub mod triangle;
+#[cfg(feature = "std")]
+pub mod mesh;
+#[cfg(feature = "std")]
+pub mod instance;
+#[cfg(feature = "std")]
+pub mod obj;
+#[cfg(feature = "std")]
+pub mod stl;
+#[cfg(feature = "std")]
+pub mod ply;
+#[cfg(feature = "gltf")]
+pub mod gltf;
+#[cfg(feature = "std")]
+pub mod hittable_list;
+#[cfg(feature = "std")]
+pub mod bvh;
+#[cfg(feature = "std")]
+pub mod render;
+
+#[cfg(feature = "mint")]
+mod mint_support;
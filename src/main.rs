@@ -1,26 +1,181 @@
+use std::process;
+use std::str::FromStr;
+use std::sync::Arc;
+
 use image::{RgbImage, ImageBuffer, Rgb};
+use rand::Rng;
+
+use vectors::background::{Background, SolidColor, VerticalGradient};
+use vectors::camera::Camera;
+use vectors::environment::EnvironmentMap;
+use vectors::color::Color;
+use vectors::hittable_list::HittableList;
+use vectors::material::{Dielectric, Lambertian, Material, Metal};
+use vectors::plane::Plane;
+use vectors::point::Point3;
+use vectors::render::{self, PathLimits};
+use vectors::sphere::Sphere;
+use vectors::vector::Vec3;
+
+// Command line settings, e.g. `vectors --samples 100 --max-depth 50`.
+// --max-depth and --roulette-after give the render::PathLimits.
+struct Settings {
+    samples_per_pixel: u32,
+    max_depth: u32,
+    roulette_after: Option<u32>,
+    gamma: f64,
+    background: BackgroundKind,
+    environment: Option<String>,
+}
+
+// `--background sky` or `--background black`. An --environment image, if
+// given, replaces either.
+#[derive(Clone, Copy)]
+enum BackgroundKind {
+    Sky,
+    Black,
+}
+
+impl FromStr for BackgroundKind {
+    type Err = ();
 
-mod vector;
-use vector::Vec3;
+    fn from_str(s: &str) -> Result<BackgroundKind, ()> {
+        match s {
+            "sky" => Ok(BackgroundKind::Sky),
+            "black" => Ok(BackgroundKind::Black),
+            _ => Err(())
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            samples_per_pixel: 100,
+            max_depth: 50,
+            roulette_after: None,
+            gamma: 2.0,
+            background: BackgroundKind::Sky,
+            environment: None,
+        }
+    }
+}
+
+impl Settings {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Settings, String> {
+        let mut settings = Settings::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--samples" => settings.samples_per_pixel = parse_value(&arg, args.next())?,
+                "--max-depth" => settings.max_depth = parse_value(&arg, args.next())?,
+                "--roulette-after" => settings.roulette_after = Some(parse_value(&arg, args.next())?),
+                "--background" => settings.background = parse_value(&arg, args.next())?,
+                "--environment" => settings.environment = Some(parse_value(&arg, args.next())?),
+                "--gamma" => settings.gamma = parse_value(&arg, args.next())?,
+                other => return Err(format!("unknown argument `{}`", other))
+            }
+        }
+        if settings.samples_per_pixel == 0 {
+            return Err("--samples must be at least 1".to_string());
+        }
+        if !(settings.gamma > 0.0 && settings.gamma.is_finite()) {
+            return Err("--gamma must be a positive number".to_string());
+        }
+        Ok(settings)
+    }
+}
+
+fn parse_value<T: FromStr>(name: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", name))?;
+    value.parse().map_err(|_| format!("invalid value `{}` for {}", value, name))
+}
 
 fn main() {
+    let settings = Settings::from_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(2);
+    });
+
+    const ASPECT_RATIO: f64 = 16.0 / 9.0;
+    const IMAGE_WIDTH: u32 = 400;
+    const IMAGE_HEIGHT: u32 = (IMAGE_WIDTH as f64 / ASPECT_RATIO) as u32;
 
-    const IMAGE_WIDTH: u32 = 256;
-    const IMAGE_HEIGHT: u32 = 256;
+    let camera = Camera::look_at(
+        Point3::new(-2.0, 2.0, 1.0),
+        Point3::new(0.0, 0.0, -1.0),
+        Vec3::Y,
+        90f64.to_radians(),
+        ASPECT_RATIO);
+    let ground: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.0)));
+    let center: Arc<dyn Material> = Arc::new(Lambertian::new(Color::new(0.1, 0.2, 0.5)));
+    let left: Arc<dyn Material> = Arc::new(Dielectric::new(1.5));
+    let right: Arc<dyn Material> = Arc::new(Metal::new(Color::new(0.8, 0.6, 0.2), 0.0));
+    let background: Box<dyn Background> = match (&settings.environment, settings.background) {
+        (Some(path), _) => Box::new(EnvironmentMap::open(path).unwrap_or_else(|e| {
+            eprintln!("Error reading environment map {}: {}", path, e);
+            process::exit(1);
+        })),
+        (None, BackgroundKind::Sky) => Box::new(VerticalGradient::sky()),
+        (None, BackgroundKind::Black) => Box::new(SolidColor::BLACK)
+    };
+    let mut world = HittableList::new();
+    world.add(Sphere::new(Point3::new(0.0, 0.0, -1.0), 0.5, center));
+    // A hollow glass sphere: the inner, inside-out sphere is the air bubble.
+    world.add(Sphere::new(Point3::new(-1.0, 0.0, -1.0), 0.5, left.clone()));
+    world.add(Sphere::new(Point3::new(-1.0, 0.0, -1.0), -0.4, left));
+    world.add(Sphere::new(Point3::new(1.0, 0.0, -1.0), 0.5, right));
+    world.add(Plane::new(Point3::new(0.0, -0.5, 0.0), Vec3::Y, ground));
+    let world = world.accelerated();
+    let limits = PathLimits { max_depth: settings.max_depth, roulette_after: settings.roulette_after };
 
     let mut buffer: RgbImage = ImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut rng = rand::thread_rng();
     
     for (x, y, pixel) in buffer.enumerate_pixels_mut(){
-        let vector = Vec3::new(
-            x as f64 / (IMAGE_WIDTH-1) as f64,
-            y as f64 / (IMAGE_HEIGHT-1) as f64,
-            0.25);
-        let color = vector.to_rgb();
-        *pixel = Rgb(color);
+        // Average samples jittered across the pixel's square. Image rows go
+        // top to bottom, the viewport's v bottom to top.
+        let mut color = Color::BLACK;
+        for _ in 0..settings.samples_per_pixel {
+            let u = (x as f64 + rng.gen::<f64>()) / IMAGE_WIDTH as f64;
+            let v = ((IMAGE_HEIGHT-1-y) as f64 + rng.gen::<f64>()) / IMAGE_HEIGHT as f64;
+            let ray = camera.get_ray(&mut rng, u, v);
+            color += render::ray_color(&ray, &world, &*background, limits, &mut rng);
+        }
+        // Samples are averaged in linear space, then gamma encoded.
+        color /= settings.samples_per_pixel as f64;
+        *pixel = Rgb(color.to_rgb8_gamma(settings.gamma));
     }
 
     match buffer.save("image.png") {
         Err(e) => eprintln!("Error writing file: {}", e),
         Ok(()) => println!("Done."),
     };
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Settings, String> {
+        Settings::from_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn defaults_and_values() {
+        let settings = parse(&[]).unwrap();
+        assert_eq!((100, 50, None), (settings.samples_per_pixel, settings.max_depth, settings.roulette_after));
+        let settings = parse(&["--samples", "8", "--max-depth", "0", "--roulette-after", "3", "--gamma", "2.2"]).unwrap();
+        assert_eq!((8, 0, Some(3)), (settings.samples_per_pixel, settings.max_depth, settings.roulette_after));
+        assert_eq!(2.2, settings.gamma);
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert_eq!(Err("--samples needs a value".to_string()), parse(&["--samples"]).map(|_| ()));
+        assert_eq!(Err("invalid value `deep` for --max-depth".to_string()), parse(&["--max-depth", "deep"]).map(|_| ()));
+        assert_eq!(Err("unknown argument `--fast`".to_string()), parse(&["--fast"]).map(|_| ()));
+        assert_eq!(Err("--samples must be at least 1".to_string()), parse(&["--samples", "0"]).map(|_| ()));
+        for gamma in &["0", "-1", "inf", "NaN"] {
+            assert_eq!(Err("--gamma must be a positive number".to_string()), parse(&["--gamma", gamma]).map(|_| ()));
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+use rand::{Rng, RngCore};
+
+use crate::color::Color;
+use crate::hittable::HitRecord;
+use crate::ray::Ray;
+use crate::vector::Vec3;
+
+// How a surface interacts with light. Objects share materials through
+// Arc<dyn Material>, so materials must be thread safe, and Debug so that hit
+// records and scenes can be printed.
+pub trait Material: fmt::Debug + Send + Sync {
+    // The ray continuing from the hit and how much of each channel it
+    // carries, or None if the light is absorbed.
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Color, Ray)>;
+
+    // Light given off at the hit, on top of whatever is scattered. Only
+    // light sources need to override this.
+    fn emitted(&self, _ray_in: &Ray, _hit: &HitRecord) -> Color {
+        Color::BLACK
+    }
+}
+
+// Ideal diffuse surface. Offsetting the normal by a random unit vector
+// gives a cosine-weighted distribution of scattered directions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lambertian {
+    albedo: Color,
+}
+
+impl Lambertian {
+    pub const fn new(albedo: Color) -> Lambertian {
+        Lambertian { albedo }
+    }
+
+    pub const fn albedo(&self) -> Color {
+        self.albedo
+    }
+}
+
+impl Material for Lambertian {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Color, Ray)> {
+        let mut direction = hit.normal + Vec3::random_unit_vector(rng);
+        // The random vector can nearly cancel the normal, which would give a
+        // zero direction and NaNs further down the path.
+        if direction.near_zero() {
+            direction = hit.normal;
+        }
+        Some((self.albedo, Ray::with_time(hit.point, direction, ray_in.time())))
+    }
+}
+
+// Mirror reflection, blurred by moving the reflected direction to a random
+// point in a sphere of radius fuzz. Fuzz 0 is a perfect mirror; it is
+// clamped to 1, beyond which the surface stops looking metallic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Metal {
+    albedo: Color,
+    fuzz: f64,
+}
+
+impl Metal {
+    pub fn new(albedo: Color, fuzz: f64) -> Metal {
+        Metal { albedo, fuzz: fuzz.clamp(0.0, 1.0) }
+    }
+
+    pub const fn albedo(&self) -> Color {
+        self.albedo
+    }
+
+    pub const fn fuzz(&self) -> f64 {
+        self.fuzz
+    }
+}
+
+impl Material for Metal {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Color, Ray)> {
+        let mut direction = ray_in.direction().unit_vector().reflect(hit.normal);
+        if self.fuzz > 0.0 {
+            direction += self.fuzz * Vec3::random_in_unit_sphere(rng);
+        }
+        // Fuzz can push the ray below the surface; treat it as absorbed.
+        if direction.dot(hit.normal) <= 0.0 {
+            return None;
+        }
+        Some((self.albedo, Ray::with_time(hit.point, direction, ray_in.time())))
+    }
+}
+
+// Clear refracting material such as glass or water. Hitting the back face
+// means the ray is leaving the material, so a sphere with a negative radius
+// nested inside a normal one gives a hollow shell with air inside.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Dielectric {
+    refraction_index: f64,
+}
+
+impl Dielectric {
+    pub const fn new(refraction_index: f64) -> Dielectric {
+        Dielectric { refraction_index }
+    }
+
+    pub const fn refraction_index(&self) -> f64 {
+        self.refraction_index
+    }
+}
+
+impl Material for Dielectric {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Color, Ray)> {
+        let ratio = if hit.front_face { 1.0 / self.refraction_index } else { self.refraction_index };
+        let unit_direction = ray_in.direction().unit_vector();
+        let cos_theta = (-unit_direction).dot(hit.normal).min(1.0);
+        // Total internal reflection gives None; otherwise reflect with the
+        // probability given by the Fresnel equations.
+        let direction = match unit_direction.try_refract(hit.normal, ratio) {
+            Some(refracted) if schlick(cos_theta, ratio) <= rng.gen::<f64>() => refracted,
+            _ => unit_direction.reflect(hit.normal)
+        };
+        Some((Color::WHITE, Ray::with_time(hit.point, direction, ray_in.time())))
+    }
+}
+
+// Schlick's approximation of the fraction of light reflected at an
+// interface with the given ratio of refraction indices.
+fn schlick(cosine: f64, ratio: f64) -> f64 {
+    let r0 = ((1.0 - ratio) / (1.0 + ratio)).powi(2);
+    r0 + (1.0 - r0) * (1.0 - cosine).powi(5)
+}
+
+// An area light: emits a constant color from both sides of the surface and
+// reflects nothing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiffuseLight {
+    emit: Color,
+}
+
+impl DiffuseLight {
+    pub const fn new(emit: Color) -> DiffuseLight {
+        DiffuseLight { emit }
+    }
+
+    pub const fn emit(&self) -> Color {
+        self.emit
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(&self, _ray_in: &Ray, _hit: &HitRecord, _rng: &mut dyn RngCore) -> Option<(Color, Ray)> {
+        None
+    }
+
+    fn emitted(&self, _ray_in: &Ray, _hit: &HitRecord) -> Color {
+        self.emit
+    }
+}
+
+// Alternates between two materials in squares of the given size in texture
+// coordinates, for floors and other surfaces with UVs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Checker<A, B> {
+    even: A,
+    odd: B,
+    size: f64,
+}
+
+impl<A: Material, B: Material> Checker<A, B> {
+    pub const fn new(even: A, odd: B, size: f64) -> Checker<A, B> {
+        Checker { even, odd, size }
+    }
+
+    fn at(&self, hit: &HitRecord) -> &dyn Material {
+        let square = (hit.uv.x() / self.size).floor() + (hit.uv.y() / self.size).floor();
+        if square as i64 % 2 == 0 { &self.even } else { &self.odd }
+    }
+}
+
+impl<A: Material, B: Material> Material for Checker<A, B> {
+    fn scatter(&self, ray_in: &Ray, hit: &HitRecord, rng: &mut dyn RngCore) -> Option<(Color, Ray)> {
+        self.at(hit).scatter(ray_in, hit, rng)
+    }
+
+    fn emitted(&self, ray_in: &Ray, hit: &HitRecord) -> Color {
+        self.at(hit).emitted(ray_in, hit)
+    }
+}
+
+// Absorbs everything; stands in for a real material in unit tests.
+#[cfg(test)]
+#[derive(Debug)]
+pub(crate) struct Black;
+
+#[cfg(test)]
+impl Material for Black {
+    fn scatter(&self, _ray_in: &Ray, _hit: &HitRecord, _rng: &mut dyn RngCore) -> Option<(Color, Ray)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use assert_approx_eq::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::hittable::Hittable;
+    use crate::interval::Interval;
+    use crate::point::Point3;
+    use crate::sphere::Sphere;
+    use crate::vector::Vec2;
+    use super::*;
+
+    // Sends every ray straight back along the normal, tinted red.
+    #[derive(Debug)]
+    struct Retroreflector;
+
+    impl Material for Retroreflector {
+        fn scatter(&self, _ray_in: &Ray, hit: &HitRecord, _rng: &mut dyn RngCore) -> Option<(Color, Ray)> {
+            Some((Color::new(1.0, 0.0, 0.0), Ray::new(hit.point, hit.normal)))
+        }
+    }
+
+    #[test]
+    fn scatter_through_trait_object() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let ray = Ray::new(Point3::new(0.0, 0.0, 1.0), -Vec3::Z);
+        let materials: [&dyn Material; 2] = [&Retroreflector, &Black];
+        let hit = HitRecord::new(&ray, 1.0, Vec3::Z, materials[0]);
+
+        let (attenuation, scattered) = materials[0].scatter(&ray, &hit, &mut rng).unwrap();
+        assert!(attenuation == Color::new(1.0, 0.0, 0.0));
+        assert_vec3_equal!(Point3::ORIGIN, scattered.origin());
+        assert_vec3_equal!(Vec3::Z, scattered.direction());
+        assert!(materials[1].scatter(&ray, &hit, &mut rng).is_none());
+    }
+
+    #[test]
+    fn lambertian_scatters_to_the_normal_side() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let material = Lambertian::new(Color::new(0.5, 0.6, 0.7));
+        let normal = Vec3::new(1.0, 2.0, -1.0).unit_vector();
+        let ray = Ray::with_time(Point3::new(2.0, 4.0, -2.0), -normal, 0.25);
+        let hit = HitRecord::new(&ray, 1.0, normal, &material);
+        for _ in 0..1000 {
+            let (attenuation, scattered) = material.scatter(&ray, &hit, &mut rng).unwrap();
+            assert!(attenuation == material.albedo());
+            assert!(scattered.direction().dot(hit.normal) >= 0.0);
+            assert!(!scattered.direction().near_zero());
+            assert_vec3_equal!(hit.point, scattered.origin());
+            assert_eq!(0.25, scattered.time());
+        }
+    }
+
+    #[test]
+    fn metal_without_fuzz_is_a_mirror() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let material = Metal::new(Color::new(0.8, 0.8, 0.8), 0.0);
+        let ray = Ray::new(Point3::new(-1.0, 1.0, 0.0), Vec3::new(2.0, -2.0, 0.0));
+        let hit = HitRecord::new(&ray, 0.5, Vec3::Y, &material);
+        let (attenuation, scattered) = material.scatter(&ray, &hit, &mut rng).unwrap();
+        assert!(attenuation == material.albedo());
+        assert_vec3_equal!(Point3::ORIGIN, scattered.origin());
+        assert_vec3_equal!(Vec3::new(1.0, 1.0, 0.0).unit_vector(), scattered.direction());
+    }
+
+    #[test]
+    fn fuzzy_metal_stays_near_the_mirror_direction() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let material = Metal::new(Color::new(0.8, 0.6, 0.2), 0.3);
+        let ray = Ray::new(Point3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0));
+        let hit = HitRecord::new(&ray, 1.0, Vec3::Y, &material);
+        let mirror = Vec3::new(1.0, 1.0, 0.0).unit_vector();
+        let mut absorbed = 0;
+        for _ in 0..1000 {
+            match material.scatter(&ray, &hit, &mut rng) {
+                Some((_, scattered)) => {
+                    assert!(scattered.direction().dot(hit.normal) > 0.0);
+                    assert!((scattered.direction() - mirror).length() < 0.3);
+                }
+                None => absorbed += 1
+            }
+        }
+        // At 45 degrees a fuzz of 0.3 never reaches below the surface.
+        assert_eq!(0, absorbed);
+    }
+
+    #[test]
+    fn fuzzy_metal_absorbs_rays_scattered_below_the_surface() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let material = Metal::new(Color::WHITE, 5.0);
+        assert_eq!(1.0, material.fuzz());
+        // A grazing ray reflects almost along the surface, so about half of
+        // the fuzzed directions end up below it.
+        let ray = Ray::new(Point3::new(-1.0, 0.01, 0.0), Vec3::new(1.0, -0.01, 0.0));
+        let hit = HitRecord::new(&ray, 1.0, Vec3::Y, &material);
+        let absorbed = (0..1000).filter(|_| material.scatter(&ray, &hit, &mut rng).is_none()).count();
+        assert!(absorbed > 300 && absorbed < 700, "absorbed {}", absorbed);
+    }
+
+    #[test]
+    fn schlick_reflectance() {
+        // Head on, glass reflects about 4%; at grazing angles everything.
+        assert_approx_eq!(0.04, schlick(1.0, 1.0 / 1.5));
+        assert_approx_eq!(0.04, schlick(1.0, 1.5));
+        assert_approx_eq!(1.0, schlick(0.0, 1.5));
+        assert_eq!(0.0, schlick(1.0, 1.0));
+    }
+
+    #[test]
+    fn dielectric_refracts_or_reflects_without_absorbing() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let material = Dielectric::new(1.5);
+        let ray = Ray::new(Point3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let hit = HitRecord::new(&ray, 1.0, Vec3::Z, &material);
+        let mut reflected = 0;
+        for _ in 0..1000 {
+            let (attenuation, scattered) = material.scatter(&ray, &hit, &mut rng).unwrap();
+            assert!(attenuation == Color::WHITE);
+            // Head on, refraction doesn't bend the ray.
+            if scattered.direction().z() > 0.0 {
+                reflected += 1;
+                assert_vec3_equal!(Vec3::Z, scattered.direction());
+            }
+            else {
+                assert_vec3_equal!(-Vec3::Z, scattered.direction());
+            }
+        }
+        assert!(reflected > 10 && reflected < 80, "reflected {}", reflected);
+    }
+
+    #[test]
+    fn dielectric_bends_toward_the_normal_when_entering() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let material = Dielectric::new(1.5);
+        let ray = Ray::new(Point3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0));
+        let hit = HitRecord::new(&ray, 1.0, Vec3::Y, &material);
+        let sin_in = core::f64::consts::FRAC_1_SQRT_2;
+        let refracted = (0..100)
+            .filter_map(|_| material.scatter(&ray, &hit, &mut rng))
+            .map(|(_, scattered)| scattered.direction())
+            .find(|direction| direction.y() < 0.0)
+            .unwrap();
+        assert_approx_eq!(sin_in / 1.5, refracted.x());
+        assert_approx_eq!(1.0, refracted.length());
+    }
+
+    #[test]
+    fn total_internal_reflection_inside_a_hollow_sphere() {
+        // Inside the glass of a shell, heading for the air bubble at a
+        // shallow angle. The negative radius makes the bubble's surface a
+        // back face, so the ray is leaving glass and must reflect.
+        let mut rng = StdRng::seed_from_u64(6);
+        let glass = Arc::new(Dielectric::new(1.5));
+        let bubble = Sphere::new(Point3::ORIGIN, -0.4, glass);
+        let ray = Ray::new(Point3::new(-0.45, 0.3, 0.0), Vec3::X);
+        let hit = bubble.hit(&ray, Interval::FORWARD).unwrap();
+        assert!(!hit.front_face);
+        for _ in 0..100 {
+            let (_, scattered) = hit.material.scatter(&ray, &hit, &mut rng).unwrap();
+            assert_vec3_equal!(ray.direction().reflect(hit.normal), scattered.direction());
+        }
+    }
+
+    #[test]
+    fn only_lights_emit() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let light = DiffuseLight::new(Color::new(4.0, 4.0, 4.0));
+        let front = Ray::new(Point3::new(0.0, 0.0, 1.0), -Vec3::Z);
+        let back = Ray::new(Point3::new(0.0, 0.0, -1.0), Vec3::Z);
+        for ray in &[front, back] {
+            let hit = HitRecord::new(ray, 1.0, Vec3::Z, &light);
+            assert!(light.emitted(ray, &hit) == light.emit());
+            assert!(light.scatter(ray, &hit, &mut rng).is_none());
+        }
+
+        let diffuse = Lambertian::new(Color::WHITE);
+        let hit = HitRecord::new(&front, 1.0, Vec3::Z, &diffuse);
+        assert!(diffuse.emitted(&front, &hit) == Color::BLACK);
+    }
+
+    #[test]
+    fn checker_alternates_with_uv() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let checker = Checker::new(Retroreflector, DiffuseLight::new(Color::WHITE), 0.5);
+        let ray = Ray::new(Point3::new(0.0, 0.0, 1.0), -Vec3::Z);
+        for &(u, v, even) in &[(0.1, 0.1, true), (0.6, 0.1, false), (0.6, 0.7, true), (-0.1, 0.1, false), (-0.1, -0.1, true)] {
+            let hit = HitRecord::new(&ray, 1.0, Vec3::Z, &checker).with_uv(Vec2::new(u, v));
+            assert_eq!(even, checker.scatter(&ray, &hit, &mut rng).is_some());
+            assert_eq!(!even, checker.emitted(&ray, &hit) == Color::WHITE);
+        }
+    }
+}
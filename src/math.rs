@@ -0,0 +1,191 @@
+// Scalar and geometry helpers, generic over f32 and f64 like Vec3. The
+// component-wise versions of the scalar functions live on Vec3.
+use core::ops::{Add, Mul};
+
+use crate::scalar::Float;
+use crate::vector::Vec3;
+
+// 0 when x is below edge, 1 otherwise.
+pub fn step<T: Float>(edge: T, x: T) -> T {
+    if x < edge {
+        T::ZERO
+    }
+    else {
+        T::ONE
+    }
+}
+
+// Smooth Hermite ramp from 0 at edge0 to 1 at edge1, clamped outside.
+pub fn smoothstep<T: Float>(edge0: T, edge1: T, x: T) -> T {
+    let t = ((x - edge0) / (edge1 - edge0)).max(T::ZERO).min(T::ONE);
+    t * t * (T::from_f64(3.0) - T::from_f64(2.0) * t)
+}
+
+// Real roots of a t^2 + b t + c = 0 in ascending order, or None if there
+// are none. The textbook formula subtracts nearly equal numbers when b^2 is
+// much larger than 4ac, which loses most digits of the smaller root, so the
+// root with the larger magnitude is computed first and the other one from
+// t0 * t1 = c / a. With a = 0 the equation is linear and its single root is
+// returned twice.
+pub fn solve_quadratic<T: Float>(a: T, b: T, c: T) -> Option<(T, T)> {
+    if a == T::ZERO {
+        if b == T::ZERO {
+            return None;
+        }
+        let root = -c / b;
+        return Some((root, root));
+    }
+
+    let discriminant = b * b - T::from_f64(4.0) * a * c;
+    if discriminant < T::ZERO {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let q = if b < T::ZERO {
+        T::from_f64(-0.5) * (b - root)
+    }
+    else {
+        T::from_f64(-0.5) * (b + root)
+    };
+    if q == T::ZERO {
+        // b and c are both zero.
+        return Some((T::ZERO, T::ZERO));
+    }
+    let (t0, t1) = (q / a, c / q);
+    if t0 <= t1 {
+        Some((t0, t1))
+    }
+    else {
+        Some((t1, t0))
+    }
+}
+
+// Weights (u, v, w) with p = u * a + v * b + w * c and u + v + w = 1, for a
+// point p in the plane of triangle abc; points off the plane are projected
+// onto it. All weights are in [0, 1] exactly when p lies inside the triangle.
+// Returns None for degenerate triangles.
+pub fn barycentric<T: Float>(p: Vec3<T>, a: Vec3<T>, b: Vec3<T>, c: Vec3<T>) -> Option<Vec3<T>> {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d00 = ab.dot(ab);
+    let d01 = ab.dot(ac);
+    let d11 = ac.dot(ac);
+    let d20 = ap.dot(ab);
+    let d21 = ap.dot(ac);
+    let denominator = d00 * d11 - d01 * d01;
+    if denominator == T::ZERO {
+        return None;
+    }
+    let v = (d11 * d20 - d01 * d21) / denominator;
+    let w = (d00 * d21 - d01 * d20) / denominator;
+    Some(Vec3::new(T::ONE - v - w, v, w))
+}
+
+// Blends per-vertex attributes (normals, texture coordinates, colors...)
+// with weights from barycentric.
+pub fn interpolate_barycentric<T, A>(weights: Vec3<T>, a: A, b: A, c: A) -> A
+where
+    T: Float,
+    A: Mul<T, Output = A> + Add<Output = A>,
+{
+    a * weights.x() + b * weights.y() + c * weights.z()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::vector::{Vec2, Vec3};
+
+    #[test]
+    fn step() {
+        assert_approx_eq!(0.0, super::step::<f64>(0.5, 0.25), 0.0001);
+        assert_approx_eq!(1.0, super::step::<f64>(0.5, 0.5), 0.0001);
+        assert_approx_eq!(1.0, super::step::<f64>(0.5, 3.0), 0.0001);
+        assert_approx_eq!(1.0f32, super::step(-1.0f32, 0.0), 0.0001);
+    }
+
+    #[test]
+    fn smoothstep() {
+        assert_approx_eq!(0.0, super::smoothstep::<f64>(1.0, 3.0, 0.0), 0.0001);
+        assert_approx_eq!(0.0, super::smoothstep::<f64>(1.0, 3.0, 1.0), 0.0001);
+        assert_approx_eq!(0.5, super::smoothstep::<f64>(1.0, 3.0, 2.0), 0.0001);
+        assert_approx_eq!(0.15625, super::smoothstep::<f64>(1.0, 3.0, 1.5), 0.0001);
+        assert_approx_eq!(1.0, super::smoothstep::<f64>(1.0, 3.0, 3.0), 0.0001);
+        assert_approx_eq!(1.0, super::smoothstep::<f64>(1.0, 3.0, 10.0), 0.0001);
+    }
+
+    #[test]
+    fn smoothstep_is_monotonic() {
+        let samples: Vec<f64> = (0..=20).map(|i| super::smoothstep(0.0, 1.0, i as f64 / 20.0)).collect();
+        assert!(samples.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn barycentric() {
+        let a = Vec3::new(0.0, 0.0, 0.0);
+        let b = Vec3::new(2.0, 0.0, 0.0);
+        let c = Vec3::new(0.0, 2.0, 0.0);
+        assert_vec3_equal!(Vec3::X, super::barycentric(a, a, b, c).unwrap());
+        assert_vec3_equal!(Vec3::Y, super::barycentric(b, a, b, c).unwrap());
+        assert_vec3_equal!(Vec3::Z, super::barycentric(c, a, b, c).unwrap());
+        assert_vec3_equal!(Vec3::new(0.5, 0.25, 0.25), super::barycentric(Vec3::new(0.5, 0.5, 0.0), a, b, c).unwrap());
+
+        // Outside the triangle a weight goes negative; off the plane the
+        // point is projected onto it.
+        assert!(super::barycentric(Vec3::new(3.0, 0.0, 0.0), a, b, c).unwrap().x() < 0.0);
+        assert_vec3_equal!(Vec3::new(0.5, 0.25, 0.25), super::barycentric(Vec3::new(0.5, 0.5, 7.0), a, b, c).unwrap());
+    }
+
+    #[test]
+    fn barycentric_round_trip() {
+        let a = Vec3::new(1.0, -2.0, 0.5);
+        let b = Vec3::new(3.0, 1.0, -1.0);
+        let c = Vec3::new(-1.0, 2.0, 2.0);
+        let p = a * 0.2 + b * 0.3 + c * 0.5;
+        let weights = super::barycentric(p, a, b, c).unwrap();
+        assert_vec3_equal!(Vec3::new(0.2, 0.3, 0.5), weights);
+        assert_vec3_equal!(p, super::interpolate_barycentric(weights, a, b, c));
+    }
+
+    #[test]
+    fn barycentric_degenerate_triangle() {
+        let a = Vec3::new(0.0, 0.0, 0.0);
+        let b = Vec3::new(1.0, 1.0, 1.0);
+        assert!(super::barycentric(Vec3::X, a, b, b * 2.0).is_none());
+    }
+
+    #[test]
+    fn interpolate_barycentric() {
+        let weights = Vec3::new(0.5, 0.25, 0.25);
+        let uv = super::interpolate_barycentric(weights, Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0));
+        assert_approx_eq!(0.25, uv.x(), 0.0001);
+        assert_approx_eq!(0.25, uv.y(), 0.0001);
+        assert_approx_eq!(1.5, super::interpolate_barycentric(weights, 1.0, 2.0, 2.0), 0.0001);
+    }
+
+    #[test]
+    fn solve_quadratic() {
+        // (t - 2)(t + 3) = t^2 + t - 6
+        assert_eq!(Some((-3.0, 2.0)), super::solve_quadratic(1.0, 1.0, -6.0));
+        assert_eq!(Some((-3.0, 2.0)), super::solve_quadratic(-2.0, -2.0, 12.0));
+        assert_eq!(Some((1.0, 1.0)), super::solve_quadratic(1.0, -2.0, 1.0));
+        assert_eq!(Some((0.0, 0.0)), super::solve_quadratic(5.0, 0.0, 0.0));
+        assert_eq!(None, super::solve_quadratic(1.0, 0.0, 1.0));
+        assert_eq!(Some((-2.0f32, 2.0)), super::solve_quadratic(1.0f32, 0.0, -4.0));
+    }
+
+    #[test]
+    fn solve_quadratic_linear() {
+        assert_eq!(Some((1.5, 1.5)), super::solve_quadratic(0.0, 2.0, -3.0));
+        assert_eq!(None, super::solve_quadratic(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn solve_quadratic_avoids_cancellation() {
+        // Roots 1e-8 and 1e8; the naive formula gets the small one badly wrong.
+        let (small, large) = super::solve_quadratic::<f64>(1.0, -(1e8 + 1e-8), 1.0).unwrap();
+        assert_approx_eq!(1e-8, small, 1e-20);
+        assert_approx_eq!(1e8, large, 1e-6);
+    }
+}
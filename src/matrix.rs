@@ -0,0 +1,619 @@
+use core::fmt;
+use core::ops::{Mul, Index, IndexMut};
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+
+use crate::vector::{Vec3, Vec4};
+
+// Row-major 3x3 matrix. Vectors are treated as columns, so `m * v`
+// transforms v and `a * b` applies b first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat3 {
+    rows: [[f64; 3]; 3],
+}
+
+impl Mat3 {
+    pub const ZERO: Mat3 = Mat3::new([[0.0; 3]; 3]);
+    pub const IDENTITY: Mat3 = Mat3::new([
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+    ]);
+
+    pub const fn new(rows: [[f64; 3]; 3]) -> Mat3 {
+        Mat3 { rows }
+    }
+
+    pub fn from_rows(row0: Vec3, row1: Vec3, row2: Vec3) -> Mat3 {
+        Mat3::new([row0.as_array(), row1.as_array(), row2.as_array()])
+    }
+
+    pub fn from_cols(col0: Vec3, col1: Vec3, col2: Vec3) -> Mat3 {
+        Mat3::from_rows(col0, col1, col2).transpose()
+    }
+
+    pub fn from_diagonal(diagonal: Vec3) -> Mat3 {
+        let mut result = Mat3::ZERO;
+        for i in 0..3 {
+            result.rows[i][i] = diagonal[i];
+        }
+        result
+    }
+
+    pub fn row(&self, i: usize) -> Vec3 {
+        Vec3::from(self.rows[i])
+    }
+
+    pub fn col(&self, j: usize) -> Vec3 {
+        Vec3::new(self.rows[0][j], self.rows[1][j], self.rows[2][j])
+    }
+
+    pub fn transpose(&self) -> Mat3 {
+        let mut result = Mat3::ZERO;
+        for i in 0..3 {
+            for j in 0..3 {
+                result.rows[i][j] = self.rows[j][i];
+            }
+        }
+        result
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.row(0).dot(self.row(1).cross(self.row(2)))
+    }
+
+    // Returns None for singular matrices. The determinant is at most the
+    // product of the rows' lengths, so comparing against that product
+    // rather than a fixed threshold works at any scale.
+    pub fn inverse(&self) -> Option<Mat3> {
+        let determinant = self.determinant();
+        let bound = self.row(0).length() * self.row(1).length() * self.row(2).length();
+        if !determinant.is_finite() || determinant.abs() <= 1e-12 * bound {
+            return None;
+        }
+        // The columns of the adjugate are cross products of the rows.
+        let (r0, r1, r2) = (self.row(0), self.row(1), self.row(2));
+        let adjugate = Mat3::from_cols(r1.cross(r2), r2.cross(r0), r0.cross(r1));
+        Some(adjugate * (1.0 / determinant))
+    }
+
+    pub fn approx_eq(&self, other: &Mat3, epsilon: f64) -> bool {
+        (0..3).all(|i| self.row(i).approx_eq(other.row(i), epsilon))
+    }
+}
+
+impl Mul<Mat3> for Mat3 {
+    type Output = Mat3;
+    fn mul(self, other: Mat3) -> Mat3 {
+        let mut result = Mat3::ZERO;
+        for i in 0..3 {
+            for j in 0..3 {
+                result.rows[i][j] = self.row(i).dot(other.col(j));
+            }
+        }
+        result
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+    fn mul(self, vector: Vec3) -> Vec3 {
+        Vec3::new(
+            self.row(0).dot(vector),
+            self.row(1).dot(vector),
+            self.row(2).dot(vector))
+    }
+}
+
+impl Mul<f64> for Mat3 {
+    type Output = Mat3;
+    fn mul(self, scalar: f64) -> Mat3 {
+        Mat3::from_rows(self.row(0) * scalar, self.row(1) * scalar, self.row(2) * scalar)
+    }
+}
+
+impl Index<(usize, usize)> for Mat3 {
+    type Output = f64;
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        &self.rows[row][col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Mat3 {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+        &mut self.rows[row][col]
+    }
+}
+
+impl fmt::Display for Mat3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {}, {}]", self.row(0), self.row(1), self.row(2))
+    }
+}
+
+// Row-major 4x4 affine/projective transform, using the same column-vector
+// convention as Mat3.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat4 {
+    rows: [[f64; 4]; 4],
+}
+
+impl Mat4 {
+    pub const ZERO: Mat4 = Mat4::new([[0.0; 4]; 4]);
+    pub const IDENTITY: Mat4 = Mat4::new([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    pub const fn new(rows: [[f64; 4]; 4]) -> Mat4 {
+        Mat4 { rows }
+    }
+
+    // Embeds a linear transform, leaving translation at zero.
+    pub fn from_mat3(matrix: Mat3) -> Mat4 {
+        let mut result = Mat4::IDENTITY;
+        for i in 0..3 {
+            for j in 0..3 {
+                result.rows[i][j] = matrix[(i, j)];
+            }
+        }
+        result
+    }
+
+    // The upper left 3x3 block, the linear part of an affine transform.
+    pub fn to_mat3(&self) -> Mat3 {
+        let mut result = Mat3::ZERO;
+        for i in 0..3 {
+            for j in 0..3 {
+                result[(i, j)] = self.rows[i][j];
+            }
+        }
+        result
+    }
+
+    pub fn translation(offset: Vec3) -> Mat4 {
+        let mut result = Mat4::IDENTITY;
+        for i in 0..3 {
+            result.rows[i][3] = offset[i];
+        }
+        result
+    }
+
+    pub fn scale(factors: Vec3) -> Mat4 {
+        Mat4::from_mat3(Mat3::from_diagonal(factors))
+    }
+
+    // Counter-clockwise rotation in radians when looking down the axis
+    // towards the origin.
+    pub fn rotation_x(angle: f64) -> Mat4 {
+        let (sin, cos) = angle.sin_cos();
+        Mat4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, cos, -sin, 0.0],
+            [0.0, sin, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_y(angle: f64) -> Mat4 {
+        let (sin, cos) = angle.sin_cos();
+        Mat4::new([
+            [cos, 0.0, sin, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-sin, 0.0, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_z(angle: f64) -> Mat4 {
+        let (sin, cos) = angle.sin_cos();
+        Mat4::new([
+            [cos, -sin, 0.0, 0.0],
+            [sin, cos, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    // Rotation about an arbitrary axis (Rodrigues' formula).
+    pub fn rotation(axis: Vec3, angle: f64) -> Mat4 {
+        let a = axis.unit_vector();
+        let (sin, cos) = angle.sin_cos();
+        let t = 1.0 - cos;
+        let (x, y, z) = (a.x(), a.y(), a.z());
+        Mat4::from_mat3(Mat3::new([
+            [t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y],
+            [t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x],
+            [t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos],
+        ]))
+    }
+
+    // World-to-camera transform for a camera at eye looking at target. The
+    // camera looks down its local -z axis with up along +y.
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Mat4 {
+        let w = (eye - target).unit_vector();
+        let u = up.cross(w).unit_vector();
+        let v = w.cross(u);
+        let rotation = Mat4::from_mat3(Mat3::from_rows(u, v, w));
+        rotation * Mat4::translation(-eye)
+    }
+
+    // OpenGL-style projection to clip space with a vertical field of view in
+    // radians. Points between near and far map to z in [-1, 1] after the
+    // perspective divide.
+    pub fn perspective(fov: f64, aspect: f64, near: f64, far: f64) -> Mat4 {
+        let f = 1.0 / (fov / 2.0).tan();
+        Mat4::new([
+            [f / aspect, 0.0, 0.0, 0.0],
+            [0.0, f, 0.0, 0.0],
+            [0.0, 0.0, (far + near) / (near - far), 2.0 * far * near / (near - far)],
+            [0.0, 0.0, -1.0, 0.0],
+        ])
+    }
+
+    // OpenGL-style parallel projection of the box between left/right,
+    // bottom/top and -near/-far in camera space to the [-1, 1] cube.
+    pub fn orthographic(left: f64, right: f64, bottom: f64, top: f64, near: f64, far: f64) -> Mat4 {
+        Mat4::new([
+            [2.0 / (right - left), 0.0, 0.0, (right + left) / (left - right)],
+            [0.0, 2.0 / (top - bottom), 0.0, (top + bottom) / (bottom - top)],
+            [0.0, 0.0, 2.0 / (near - far), (far + near) / (near - far)],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn row(&self, i: usize) -> Vec4 {
+        Vec4::from(self.rows[i])
+    }
+
+    pub fn col(&self, j: usize) -> Vec4 {
+        Vec4::new(self.rows[0][j], self.rows[1][j], self.rows[2][j], self.rows[3][j])
+    }
+
+    pub fn transpose(&self) -> Mat4 {
+        let mut result = Mat4::ZERO;
+        for i in 0..4 {
+            for j in 0..4 {
+                result.rows[i][j] = self.rows[j][i];
+            }
+        }
+        result
+    }
+
+    // Points are translated, and divided by w when the matrix is projective.
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        (*self * point.to_point4()).to_vec3()
+    }
+
+    // Directions ignore the translation part of the matrix.
+    pub fn transform_direction(&self, direction: Vec3) -> Vec3 {
+        (*self * direction.to_dir4()).xyz()
+    }
+
+    // Gauss-Jordan elimination with partial pivoting. Returns None for
+    // singular matrices, judged by pivots that are tiny next to the largest
+    // entry so that the test doesn't depend on the matrix's scale.
+    pub fn inverse(&self) -> Option<Mat4> {
+        let largest = self.rows.iter().flatten().fold(0.0f64, |largest, x| largest.max(x.abs()));
+        if !largest.is_finite() {
+            return None;
+        }
+        let mut a = self.rows;
+        let mut inverse = Mat4::IDENTITY.rows;
+        for col in 0..4 {
+            let pivot = (col..4)
+                .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+                .unwrap();
+            if a[pivot][col].abs() <= 1e-12 * largest {
+                return None;
+            }
+            a.swap(col, pivot);
+            inverse.swap(col, pivot);
+
+            let scale = 1.0 / a[col][col];
+            for j in 0..4 {
+                a[col][j] *= scale;
+                inverse[col][j] *= scale;
+            }
+            for row in 0..4 {
+                if row != col {
+                    let factor = a[row][col];
+                    for j in 0..4 {
+                        a[row][j] -= factor * a[col][j];
+                        inverse[row][j] -= factor * inverse[col][j];
+                    }
+                }
+            }
+        }
+        Some(Mat4::new(inverse))
+    }
+
+    pub fn approx_eq(&self, other: &Mat4, epsilon: f64) -> bool {
+        (0..4).all(|i| self.row(i).approx_eq(other.row(i), epsilon))
+    }
+}
+
+impl Mul<Mat4> for Mat4 {
+    type Output = Mat4;
+    fn mul(self, other: Mat4) -> Mat4 {
+        let mut result = Mat4::ZERO;
+        for i in 0..4 {
+            for j in 0..4 {
+                result.rows[i][j] = self.row(i).dot(other.col(j));
+            }
+        }
+        result
+    }
+}
+
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+    fn mul(self, vector: Vec4) -> Vec4 {
+        Vec4::new(
+            self.row(0).dot(vector),
+            self.row(1).dot(vector),
+            self.row(2).dot(vector),
+            self.row(3).dot(vector))
+    }
+}
+
+impl Index<(usize, usize)> for Mat4 {
+    type Output = f64;
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        &self.rows[row][col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Mat4 {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+        &mut self.rows[row][col]
+    }
+}
+
+impl fmt::Display for Mat4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {}, {}, {}]", self.row(0), self.row(1), self.row(2), self.row(3))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use super::*;
+
+    macro_rules! assert_mat3_equal {
+        ($expected:expr, $actual:expr) => {
+            assert!($expected.approx_eq(&$actual, 0.0001), "expected {}, got {}", $expected, $actual);
+        }
+    }
+
+    macro_rules! assert_mat4_equal {
+        ($expected:expr, $actual:expr) => {
+            assert!($expected.approx_eq(&$actual, 0.0001), "expected {}, got {}", $expected, $actual);
+        }
+    }
+
+    fn sample() -> Mat3 {
+        Mat3::new([
+            [2.0, 0.0, 1.0],
+            [1.0, 3.0, 2.0],
+            [1.0, 1.0, 2.0],
+        ])
+    }
+
+    #[test]
+    fn identity() {
+        let matrix = sample();
+        assert_mat3_equal!(matrix, Mat3::IDENTITY * matrix);
+        assert_mat3_equal!(matrix, matrix * Mat3::IDENTITY);
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let matrix = sample();
+        assert_vec3_equal!(Vec3::new(1.0, 3.0, 2.0), matrix.row(1));
+        assert_vec3_equal!(Vec3::new(0.0, 3.0, 1.0), matrix.col(1));
+        let from_cols = Mat3::from_cols(matrix.col(0), matrix.col(1), matrix.col(2));
+        assert_mat3_equal!(matrix, from_cols);
+        assert_approx_eq!(2.0, matrix[(1, 2)], 0.001);
+    }
+
+    #[test]
+    fn matrix_multiplication() {
+        let a = sample();
+        let b = Mat3::new([
+            [1.0, 2.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [4.0, 0.0, 1.0],
+        ]);
+        let expected = Mat3::new([
+            [6.0, 4.0, 1.0],
+            [9.0, 5.0, 2.0],
+            [9.0, 3.0, 2.0],
+        ]);
+        assert_mat3_equal!(expected, a * b);
+    }
+
+    #[test]
+    fn vector_multiplication() {
+        let result = sample() * Vec3::new(1.0, 2.0, 3.0);
+        let expected = Vec3::new(5.0, 13.0, 9.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn scalar_multiplication() {
+        let result = Mat3::IDENTITY * 2.0;
+        assert_mat3_equal!(Mat3::from_diagonal(Vec3::new(2.0, 2.0, 2.0)), result);
+    }
+
+    #[test]
+    fn transpose() {
+        let matrix = sample();
+        let result = matrix.transpose();
+        assert_vec3_equal!(matrix.col(0), result.row(0));
+        assert_mat3_equal!(matrix, result.transpose());
+    }
+
+    #[test]
+    fn determinant() {
+        assert_approx_eq!(6.0, sample().determinant(), 0.0001);
+        assert_approx_eq!(24.0, Mat3::from_diagonal(Vec3::new(2.0, 3.0, 4.0)).determinant(), 0.0001);
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = sample();
+        let inverse = matrix.inverse().unwrap();
+        assert_mat3_equal!(Mat3::IDENTITY, matrix * inverse);
+        assert_mat3_equal!(Mat3::IDENTITY, inverse * matrix);
+    }
+
+    #[test]
+    fn inverse_of_singular_matrix() {
+        let matrix = Mat3::from_rows(Vec3::ONE, Vec3::ONE, Vec3::X);
+        assert!(matrix.inverse().is_none());
+        assert!(Mat3::ZERO.inverse().is_none());
+        assert!((Mat3::IDENTITY * f64::NAN).inverse().is_none());
+    }
+
+    #[test]
+    fn inverse_at_small_scale() {
+        let matrix = sample() * 1e-5;
+        let inverse = matrix.inverse().unwrap();
+        assert_mat3_equal!(Mat3::IDENTITY, matrix * inverse);
+    }
+
+    #[test]
+    fn mat4_translation() {
+        let matrix = Mat4::translation(Vec3::new(1.0, 2.0, 3.0));
+        let point = Vec3::new(1.0, 1.0, 1.0);
+        assert_vec3_equal!(Vec3::new(2.0, 3.0, 4.0), matrix.transform_point(point));
+        assert_vec3_equal!(point, matrix.transform_direction(point));
+    }
+
+    #[test]
+    fn mat4_to_mat3_drops_translation() {
+        let matrix = Mat4::translation(Vec3::new(1.0, 2.0, 3.0)) * Mat4::from_mat3(sample());
+        assert_mat3_equal!(sample(), matrix.to_mat3());
+    }
+
+    #[test]
+    fn mat4_scale() {
+        let matrix = Mat4::scale(Vec3::new(2.0, 3.0, 4.0));
+        let result = matrix.transform_point(Vec3::ONE);
+        assert_vec3_equal!(Vec3::new(2.0, 3.0, 4.0), result);
+    }
+
+    #[test]
+    fn mat4_rotation_about_axes() {
+        let quarter = std::f64::consts::FRAC_PI_2;
+        assert_vec3_equal!(Vec3::Z, Mat4::rotation_x(quarter).transform_direction(Vec3::Y));
+        assert_vec3_equal!(Vec3::X, Mat4::rotation_y(quarter).transform_direction(Vec3::Z));
+        assert_vec3_equal!(Vec3::Y, Mat4::rotation_z(quarter).transform_direction(Vec3::X));
+    }
+
+    #[test]
+    fn mat4_rotation_about_arbitrary_axis() {
+        let angle = 0.7;
+        assert_mat4_equal!(Mat4::rotation_x(angle), Mat4::rotation(Vec3::X * 3.0, angle));
+        assert_mat4_equal!(Mat4::rotation_y(angle), Mat4::rotation(Vec3::Y, angle));
+        assert_mat4_equal!(Mat4::rotation_z(angle), Mat4::rotation(Vec3::Z, angle));
+
+        let axis = Vec3::ONE;
+        let third = 2.0 * std::f64::consts::PI / 3.0;
+        let result = Mat4::rotation(axis, third).transform_direction(Vec3::X);
+        assert_vec3_equal!(Vec3::Y, result);
+    }
+
+    #[test]
+    fn mat4_composition_order() {
+        let translate = Mat4::translation(Vec3::new(1.0, 0.0, 0.0));
+        let scale = Mat4::scale(Vec3::new(2.0, 2.0, 2.0));
+        let result = (translate * scale).transform_point(Vec3::ONE);
+        assert_vec3_equal!(Vec3::new(3.0, 2.0, 2.0), result);
+    }
+
+    #[test]
+    fn mat4_look_at() {
+        let eye = Vec3::new(0.0, 0.0, 5.0);
+        let matrix = Mat4::look_at(eye, Vec3::ZERO, Vec3::Y);
+        assert_vec3_equal!(Vec3::ZERO, matrix.transform_point(eye));
+        assert_vec3_equal!(Vec3::new(0.0, 0.0, -5.0), matrix.transform_point(Vec3::ZERO));
+        assert_vec3_equal!(Vec3::Y, matrix.transform_direction(Vec3::Y));
+    }
+
+    #[test]
+    fn mat4_perspective() {
+        let matrix = Mat4::perspective(std::f64::consts::FRAC_PI_2, 2.0, 1.0, 10.0);
+        let near = matrix.transform_point(Vec3::new(0.0, 0.0, -1.0));
+        let far = matrix.transform_point(Vec3::new(0.0, 0.0, -10.0));
+        assert_approx_eq!(-1.0, near.z(), 0.0001);
+        assert_approx_eq!(1.0, far.z(), 0.0001);
+
+        let corner = matrix.transform_point(Vec3::new(2.0, 1.0, -1.0));
+        assert_vec3_equal!(Vec3::new(1.0, 1.0, -1.0), corner);
+    }
+
+    #[test]
+    fn mat4_orthographic() {
+        let matrix = Mat4::orthographic(-2.0, 4.0, -1.0, 1.0, 1.0, 11.0);
+        assert_vec3_equal!(Vec3::new(-1.0, -1.0, -1.0), matrix.transform_point(Vec3::new(-2.0, -1.0, -1.0)));
+        assert_vec3_equal!(Vec3::new(1.0, 1.0, 1.0), matrix.transform_point(Vec3::new(4.0, 1.0, -11.0)));
+        assert_vec3_equal!(Vec3::ZERO, matrix.transform_point(Vec3::new(1.0, 0.0, -6.0)));
+
+        // No perspective divide: w stays 1 and depth does not shrink x and y.
+        let point = matrix * Vec4::from_point(Vec3::new(4.0, 1.0, -6.0));
+        assert_approx_eq!(1.0, point.w(), 0.0001);
+        assert_approx_eq!(1.0, point.x(), 0.0001);
+    }
+
+    #[test]
+    fn mat4_look_at_then_perspective() {
+        let view = Mat4::look_at(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y);
+        let projection = Mat4::perspective(std::f64::consts::FRAC_PI_2, 1.0, 1.0, 9.0);
+        let clip = (projection * view).transform_point(Vec3::new(2.5, -2.5, 0.0));
+        assert_approx_eq!(0.5, clip.x(), 0.0001);
+        assert_approx_eq!(-0.5, clip.y(), 0.0001);
+        assert!(clip.z() > -1.0 && clip.z() < 1.0);
+    }
+
+    #[test]
+    fn mat4_inverse() {
+        let matrix = Mat4::translation(Vec3::new(1.0, -2.0, 3.0))
+            * Mat4::rotation(Vec3::new(1.0, 2.0, 3.0), 0.5)
+            * Mat4::scale(Vec3::new(2.0, 0.5, 3.0));
+        let inverse = matrix.inverse().unwrap();
+        assert_mat4_equal!(Mat4::IDENTITY, matrix * inverse);
+        assert_mat4_equal!(Mat4::IDENTITY, inverse * matrix);
+
+        let point = Vec3::new(0.3, 0.2, 0.1);
+        assert_vec3_equal!(point, inverse.transform_point(matrix.transform_point(point)));
+    }
+
+    #[test]
+    fn mat4_inverse_of_singular_matrix() {
+        let matrix = Mat4::scale(Vec3::new(1.0, 0.0, 1.0));
+        assert!(matrix.inverse().is_none());
+        assert!(Mat4::ZERO.inverse().is_none());
+        assert!(Mat4::translation(Vec3::new(f64::INFINITY, 0.0, 0.0)).inverse().is_none());
+    }
+
+    #[test]
+    fn mat4_inverse_at_small_scale() {
+        let matrix = Mat4::scale(Vec3::new(1e-5, 1e-5, 1e-5));
+        let inverse = matrix.inverse().unwrap();
+        assert_mat4_equal!(Mat4::IDENTITY, matrix * inverse);
+        assert_vec3_equal!(Vec3::new(1e5, 0.0, 0.0), inverse.transform_point(Vec3::X));
+    }
+
+    #[test]
+    fn mat4_transpose() {
+        let matrix = Mat4::translation(Vec3::new(1.0, 2.0, 3.0));
+        let result = matrix.transpose();
+        assert_approx_eq!(2.0, result[(3, 1)], 0.001);
+        assert_mat4_equal!(matrix, result.transpose());
+    }
+}
@@ -0,0 +1,258 @@
+use std::sync::Arc;
+
+use crate::aabb::Aabb;
+use crate::bvh::BvhNode;
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::triangle;
+use crate::vector::{Vec2, Vec3};
+
+// Triangles sharing vertex buffers: each face is three indices into the
+// positions and the optional per-vertex normals and texture coordinates.
+// Vertices shared by several faces are stored once, which for a closed mesh
+// is about a sixth of storing three per face.
+#[derive(Debug)]
+pub struct TriangleMesh {
+    positions: Vec<Point3>,
+    normals: Option<Vec<Vec3>>,
+    uvs: Option<Vec<Vec2>>,
+    faces: Vec<[u32; 3]>,
+    material: Arc<dyn Material>,
+}
+
+impl TriangleMesh {
+    // Panics if a face refers to a vertex that doesn't exist.
+    pub fn new(positions: Vec<Point3>, faces: Vec<[u32; 3]>, material: Arc<dyn Material>) -> TriangleMesh {
+        let count = positions.len();
+        assert!(faces.iter().flatten().all(|&index| (index as usize) < count),
+            "mesh face refers to a vertex out of range");
+        TriangleMesh { positions, normals: None, uvs: None, faces, material }
+    }
+
+    // Unit normals, one per vertex.
+    pub fn with_normals(self, normals: Vec<Vec3>) -> TriangleMesh {
+        assert_eq!(self.positions.len(), normals.len(), "mesh needs one normal per vertex");
+        TriangleMesh { normals: Some(normals), ..self }
+    }
+
+    // Vertex normals averaged from the faces around each vertex, weighted by
+    // their areas, so that a mesh without normals of its own shades smoothly.
+    pub fn with_smooth_normals(self) -> TriangleMesh {
+        let mut normals = vec![Vec3::ZERO; self.positions.len()];
+        for &face in &self.faces {
+            let [a, b, c] = TriangleMesh::select(&self.positions, face);
+            // Twice the area times the unit normal.
+            let normal = (b - a).cross(c - a);
+            for &index in &face {
+                normals[index as usize] += normal;
+            }
+        }
+        // Vertices only on degenerate faces get an arbitrary normal.
+        let normals = normals.into_iter().map(|normal| normal.normalize_or(Vec3::Z)).collect();
+        self.with_normals(normals)
+    }
+
+    // For files without vertex normals.
+    pub fn shaded(self, shading: Shading) -> TriangleMesh {
+        match shading {
+            Shading::Flat => self,
+            Shading::Smooth => self.with_smooth_normals(),
+        }
+    }
+
+    // Texture coordinates, one per vertex.
+    pub fn with_uvs(self, uvs: Vec<Vec2>) -> TriangleMesh {
+        assert_eq!(self.positions.len(), uvs.len(), "mesh needs one texture coordinate per vertex");
+        TriangleMesh { uvs: Some(uvs), ..self }
+    }
+
+    pub fn positions(&self) -> &[Point3] {
+        &self.positions
+    }
+
+    pub fn normals(&self) -> Option<&[Vec3]> {
+        self.normals.as_deref()
+    }
+
+    pub fn uvs(&self) -> Option<&[Vec2]> {
+        self.uvs.as_deref()
+    }
+
+    pub fn faces(&self) -> &[[u32; 3]] {
+        &self.faces
+    }
+
+    pub fn material(&self) -> &Arc<dyn Material> {
+        &self.material
+    }
+
+    // Number of triangles.
+    pub fn len(&self) -> usize {
+        self.faces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    // One hittable per face, all sharing this mesh.
+    pub fn into_triangles(self) -> Vec<MeshTriangle> {
+        TriangleMesh::triangles(&Arc::new(self))
+    }
+
+    // Like into_triangles, for a mesh that is already shared.
+    pub fn triangles(mesh: &Arc<TriangleMesh>) -> Vec<MeshTriangle> {
+        (0..mesh.faces.len()).map(|face| MeshTriangle { mesh: mesh.clone(), face }).collect()
+    }
+
+    // None for a mesh without faces.
+    pub fn into_bvh(self) -> Option<BvhNode> {
+        TriangleMesh::bvh(&Arc::new(self))
+    }
+
+    pub fn bvh(mesh: &Arc<TriangleMesh>) -> Option<BvhNode> {
+        let triangles = TriangleMesh::triangles(mesh).into_iter()
+            .map(|triangle| Box::new(triangle) as Box<dyn Hittable>)
+            .collect();
+        BvhNode::new(triangles)
+    }
+
+    fn select<T: Copy>(values: &[T], [a, b, c]: [u32; 3]) -> [T; 3] {
+        [values[a as usize], values[b as usize], values[c as usize]]
+    }
+}
+
+// How to shade a mesh that comes without vertex normals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Shading {
+    // Every face with its own normal, showing the facets.
+    #[default]
+    Flat,
+    // With normals from TriangleMesh::with_smooth_normals.
+    Smooth,
+}
+
+// One face of a TriangleMesh.
+#[derive(Clone, Debug)]
+pub struct MeshTriangle {
+    mesh: Arc<TriangleMesh>,
+    face: usize,
+}
+
+impl MeshTriangle {
+    pub fn vertices(&self) -> [Point3; 3] {
+        TriangleMesh::select(&self.mesh.positions, self.mesh.faces[self.face])
+    }
+}
+
+impl Hittable for MeshTriangle {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        let mesh = &*self.mesh;
+        let face = mesh.faces[self.face];
+        let [a, b, c] = self.vertices();
+        let (t, weights) = triangle::intersect(a, b, c, ray, t_range)?;
+        let normals = mesh.normals.as_ref().map(|normals| TriangleMesh::select(normals, face));
+        let uvs = mesh.uvs.as_ref().map(|uvs| TriangleMesh::select(uvs, face));
+        Some(triangle::shade(ray, t, weights, [a, b, c], normals, uvs, &*mesh.material))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let [a, b, c] = self.vertices();
+        Some(triangle::triangle_box(a, b, c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use super::*;
+
+    // The unit square at z = -2 as two triangles sharing the diagonal.
+    fn square() -> TriangleMesh {
+        let positions = vec![
+            Point3::new(0.0, 0.0, -2.0),
+            Point3::new(1.0, 0.0, -2.0),
+            Point3::new(1.0, 1.0, -2.0),
+            Point3::new(0.0, 1.0, -2.0),
+        ];
+        TriangleMesh::new(positions, vec![[0, 1, 2], [0, 2, 3]], Arc::new(Black))
+    }
+
+    fn ray_to(x: f64, y: f64) -> Ray {
+        Ray::new(Point3::new(x, y, 0.0), -Vec3::Z)
+    }
+
+    #[test]
+    fn triangles_share_the_mesh() {
+        let triangles = square().into_triangles();
+        assert_eq!(2, triangles.len());
+        assert_eq!(2, Arc::strong_count(&triangles[0].mesh));
+        assert_vec3_equal!(Point3::new(1.0, 1.0, -2.0), triangles[1].vertices()[1]);
+    }
+
+    #[test]
+    fn hit_either_face() {
+        let triangles = square().into_triangles();
+        let lower = triangles[0].hit(&ray_to(0.75, 0.25), Interval::FORWARD).unwrap();
+        assert_approx_eq!(2.0, lower.t);
+        assert_vec3_equal!(Vec3::Z, lower.normal);
+        assert!(triangles[1].hit(&ray_to(0.75, 0.25), Interval::FORWARD).is_none());
+        assert!(triangles[1].hit(&ray_to(0.25, 0.75), Interval::FORWARD).is_some());
+    }
+
+    #[test]
+    fn vertex_attributes() {
+        let tilted = Vec3::new(1.0, 0.0, 1.0).unit_vector();
+        let mesh = square()
+            .with_normals(vec![Vec3::Z, tilted, tilted, Vec3::Z])
+            .with_uvs(vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)]);
+        let triangles = mesh.into_triangles();
+        let record = triangles[1].hit(&ray_to(0.5, 0.75), Interval::FORWARD).unwrap();
+        assert_approx_eq!(0.5, record.uv.x());
+        assert_approx_eq!(0.75, record.uv.y());
+        let expected = (Vec3::Z * 0.5 + tilted * 0.5).unit_vector();
+        assert_vec3_equal!(expected, record.normal);
+    }
+
+    #[test]
+    fn smooth_normals() {
+        // The square with one corner pulled back, folding it along the
+        // diagonal.
+        let positions = vec![
+            Point3::new(0.0, 0.0, -2.0),
+            Point3::new(1.0, 0.0, -2.0),
+            Point3::new(1.0, 1.0, -2.0),
+            Point3::new(0.0, 1.0, -3.0),
+        ];
+        let mesh = TriangleMesh::new(positions, vec![[0, 1, 2], [0, 2, 3]], Arc::new(Black));
+        assert!(mesh.normals().is_none());
+        let mesh = mesh.shaded(Shading::Smooth);
+        let normals = mesh.normals().unwrap();
+        assert_vec3_equal!(Vec3::Z, normals[1]);
+        assert_vec3_equal!(Vec3::new(-1.0, 1.0, 1.0).unit_vector(), normals[3]);
+        // The folded face is larger, so it counts for more on the diagonal.
+        let diagonal = Vec3::new(-1.0, 1.0, 2.0).unit_vector();
+        assert_vec3_equal!(diagonal, normals[0]);
+        assert_vec3_equal!(diagonal, normals[2]);
+    }
+
+    #[test]
+    fn bvh_over_the_faces() {
+        assert!(TriangleMesh::new(Vec::new(), Vec::new(), Arc::new(Black)).into_bvh().is_none());
+        let bvh = square().into_bvh().unwrap();
+        for &(x, y) in &[(0.1, 0.1), (0.9, 0.2), (0.2, 0.9), (0.5, 0.5)] {
+            assert_approx_eq!(2.0, bvh.hit(&ray_to(x, y), Interval::FORWARD).unwrap().t);
+        }
+        assert!(bvh.hit(&ray_to(1.1, 0.5), Interval::FORWARD).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "mesh face refers to a vertex out of range")]
+    fn face_out_of_range() {
+        TriangleMesh::new(vec![Point3::ORIGIN; 3], vec![[0, 1, 3]], Arc::new(Black));
+    }
+}
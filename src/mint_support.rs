@@ -0,0 +1,75 @@
+// Conversions with the mint interchange types, which glam, nalgebra, cgmath
+// and most physics crates accept and produce.
+use crate::point::Point3;
+use crate::scalar::Float;
+use crate::vector::Vec3;
+
+impl<T> From<mint::Vector3<T>> for Vec3<T> {
+    fn from(vector: mint::Vector3<T>) -> Vec3<T> {
+        Vec3::from([vector.x, vector.y, vector.z])
+    }
+}
+
+impl<T: Float> From<Vec3<T>> for mint::Vector3<T> {
+    fn from(vector: Vec3<T>) -> mint::Vector3<T> {
+        let [x, y, z] = vector.as_array();
+        mint::Vector3 { x, y, z }
+    }
+}
+
+impl<T: Float> mint::IntoMint for Vec3<T> {
+    type MintType = mint::Vector3<T>;
+}
+
+impl From<mint::Point3<f64>> for Point3 {
+    fn from(point: mint::Point3<f64>) -> Point3 {
+        Point3::new(point.x, point.y, point.z)
+    }
+}
+
+impl From<Point3> for mint::Point3<f64> {
+    fn from(point: Point3) -> mint::Point3<f64> {
+        mint::Point3 { x: point.x(), y: point.y(), z: point.z() }
+    }
+}
+
+impl mint::IntoMint for Point3 {
+    type MintType = mint::Point3<f64>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::point::Point3;
+    use crate::vector::Vec3;
+
+    #[test]
+    fn vector_round_trip() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        let converted: mint::Vector3<f64> = vector.into();
+        assert_eq!(mint::Vector3 { x: 1.0, y: 2.0, z: 3.0 }, converted);
+        assert!(vector == Vec3::from(converted));
+
+        let converted: mint::Vector3<f32> = Vec3::<f32>::new(1.0, 2.0, 3.0).into();
+        assert_eq!(2.0f32, converted.y);
+    }
+
+    #[test]
+    fn point_round_trip() {
+        let point = Point3::new(-1.0, 0.5, 4.0);
+        let converted: mint::Point3<f64> = point.into();
+        assert_eq!(mint::Point3 { x: -1.0, y: 0.5, z: 4.0 }, converted);
+        assert!(point == Point3::from(converted));
+    }
+
+    // Typical use: accept anything that converts through mint.
+    fn sum<V: Into<mint::Vector3<f64>>>(vector: V) -> f64 {
+        let vector = vector.into();
+        vector.x + vector.y + vector.z
+    }
+
+    #[test]
+    fn generic_mint_arguments() {
+        assert_eq!(6.0, sum(Vec3::new(1.0, 2.0, 3.0)));
+        assert_eq!(6.0, sum([1.0, 2.0, 3.0]));
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::sphere::{hit_sphere, sphere_box};
+
+// A sphere moving in a straight line, at center0 at time0 and center1 at
+// time1. Rays sent at different times during the camera's shutter interval
+// see it in different places, which renders as motion blur.
+#[derive(Clone, Debug)]
+pub struct MovingSphere {
+    center0: Point3,
+    center1: Point3,
+    time0: f64,
+    time1: f64,
+    radius: f64,
+    material: Arc<dyn Material>,
+}
+
+impl MovingSphere {
+    pub fn new(center0: Point3, center1: Point3, time0: f64, time1: f64, radius: f64,
+               material: Arc<dyn Material>) -> MovingSphere {
+        MovingSphere { center0, center1, time0, time1, radius, material }
+    }
+
+    // Extrapolates linearly outside [time0, time1]. A sphere whose times are
+    // equal has no speed to extrapolate with and stays at center0.
+    pub fn center(&self, time: f64) -> Point3 {
+        if self.time0 == self.time1 {
+            return self.center0;
+        }
+        let t = (time - self.time0) / (self.time1 - self.time0);
+        self.center0 + (self.center1 - self.center0) * t
+    }
+
+    pub const fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+impl Hittable for MovingSphere {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        hit_sphere(self.center(ray.time()), self.radius, &*self.material, ray, t_range)
+    }
+
+    // Covers the sphere from time0 to time1, which should span the shutter.
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::surrounding_box(sphere_box(self.center0, self.radius), sphere_box(self.center1, self.radius)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use crate::vector::Vec3;
+    use super::*;
+
+    fn sphere() -> MovingSphere {
+        MovingSphere::new(Point3::new(0.0, 0.0, -5.0), Point3::new(2.0, 0.0, -5.0), 0.0, 1.0, 0.5, Arc::new(Black))
+    }
+
+    #[test]
+    fn center_moves_over_time() {
+        let sphere = sphere();
+        assert_vec3_equal!(Point3::new(0.0, 0.0, -5.0), sphere.center(0.0));
+        assert_vec3_equal!(Point3::new(1.0, 0.0, -5.0), sphere.center(0.5));
+        assert_vec3_equal!(Point3::new(2.0, 0.0, -5.0), sphere.center(1.0));
+    }
+
+    #[test]
+    fn center_with_equal_times() {
+        let sphere = MovingSphere::new(Point3::new(0.0, 0.0, -5.0), Point3::new(2.0, 0.0, -5.0), 1.0, 1.0, 0.5, Arc::new(Black));
+        assert_vec3_equal!(Point3::new(0.0, 0.0, -5.0), sphere.center(1.0));
+        assert_vec3_equal!(Point3::new(0.0, 0.0, -5.0), sphere.center(0.0));
+        let ray = Ray::with_time(Point3::ORIGIN, Vec3::new(0.0, 0.0, -1.0), 1.0);
+        assert_approx_eq!(4.5, sphere.hit(&ray, Interval::FORWARD).unwrap().t, 0.0001);
+    }
+
+    #[test]
+    fn hit_depends_on_ray_time() {
+        let sphere = sphere();
+        let direction = Vec3::new(0.0, 0.0, -1.0);
+        let at_start = Ray::with_time(Point3::ORIGIN, direction, 0.0);
+        let at_end = Ray::with_time(Point3::ORIGIN, direction, 1.0);
+        assert_approx_eq!(4.5, sphere.hit(&at_start, Interval::FORWARD).unwrap().t, 0.0001);
+        assert!(sphere.hit(&at_end, Interval::FORWARD).is_none());
+
+        let following = Ray::with_time(Point3::new(2.0, 0.0, 0.0), direction, 1.0);
+        let record = sphere.hit(&following, Interval::FORWARD).unwrap();
+        assert_vec3_equal!(Vec3::Z, record.normal);
+    }
+
+    #[test]
+    fn bounding_box_covers_the_whole_path() {
+        let aabb = sphere().bounding_box().unwrap();
+        assert_vec3_equal!(Point3::new(-0.5, -0.5, -5.5), aabb.min());
+        assert_vec3_equal!(Point3::new(2.5, 0.5, -4.5), aabb.max());
+    }
+}
@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::SplitWhitespace;
+use std::sync::Arc;
+
+use crate::color::Color;
+use crate::material::{Dielectric, DiffuseLight, Lambertian, Material, Metal};
+use crate::mesh::TriangleMesh;
+use crate::point::Point3;
+use crate::vector::{Vec2, Vec3};
+
+// Wavefront OBJ import. Every run of faces sharing a group and a material
+// becomes one TriangleMesh. OBJ indexes positions, texture coordinates and
+// normals separately, so each distinct combination a face uses becomes one
+// mesh vertex. Polygons are split into fans of triangles.
+
+// Materials from MTL files, by name.
+pub type Materials = HashMap<String, Arc<dyn Material>>;
+
+#[derive(Debug)]
+pub enum ObjError {
+    Io(io::Error),
+    // Line number, counting from 1, and what is wrong on it.
+    Syntax(usize, &'static str),
+    // An error in the named material library.
+    Mtl(String, Box<ObjError>),
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObjError::Io(error) => write!(f, "{}", error),
+            ObjError::Syntax(line, reason) => write!(f, "line {}: {}", line, reason),
+            ObjError::Mtl(name, error) => write!(f, "in {}: {}", name, error),
+        }
+    }
+}
+
+impl Error for ObjError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ObjError::Io(error) => Some(error),
+            ObjError::Syntax(..) => None,
+            ObjError::Mtl(_, error) => Some(&**error),
+        }
+    }
+}
+
+impl From<io::Error> for ObjError {
+    fn from(error: io::Error) -> ObjError {
+        ObjError::Io(error)
+    }
+}
+
+// Material libraries are looked up next to the OBJ file. Faces without a
+// material, or with one no library defines, get default_material, and so
+// do faces using a library that is missing.
+pub fn load_obj<P: AsRef<Path>>(path: P, default_material: Arc<dyn Material>) -> Result<Vec<TriangleMesh>, ObjError> {
+    let path = path.as_ref();
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+    let source = fs::read_to_string(path)?;
+    parse_obj(&source, default_material, |name| fs::read_to_string(directory.join(name)))
+}
+
+// Like load_obj, with read_mtl giving the contents of a material library.
+pub fn parse_obj<F>(source: &str, default_material: Arc<dyn Material>, mut read_mtl: F) -> Result<Vec<TriangleMesh>, ObjError>
+    where F: FnMut(&str) -> io::Result<String>
+{
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    let mut normals = Vec::new();
+    let mut materials = Materials::new();
+    let mut meshes = Vec::new();
+    let mut group = MeshBuilder::new(default_material.clone());
+
+    for (number, line) in lines(source) {
+        let syntax = |reason| ObjError::Syntax(number, reason);
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("v") => {
+                let [x, y, z] = numbers(&mut words, 3).ok_or_else(|| syntax("invalid vertex position"))?;
+                positions.push(Point3::new(x, y, z));
+            }
+            Some("vt") => {
+                let [u, v] = numbers(&mut words, 1).ok_or_else(|| syntax("invalid texture coordinate"))?;
+                uvs.push(Vec2::new(u, v));
+            }
+            Some("vn") => {
+                let normal = numbers(&mut words, 3).ok_or_else(|| syntax("invalid vertex normal"))?;
+                // A zero normal leaves its faces without smooth shading.
+                normals.push(Vec3::from(normal).try_normalize());
+            }
+            Some("f") => {
+                let vertices = words
+                    .map(|word| face_vertex(word, positions.len(), uvs.len(), normals.len()))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| syntax("invalid face vertex"))?;
+                if vertices.len() < 3 {
+                    return Err(syntax("face with fewer than three vertices"));
+                }
+                let indices: Vec<u32> = vertices.into_iter()
+                    .map(|vertex| group.vertex(vertex, &positions, &uvs, &normals))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| syntax("mesh with more vertices than u32 can index"))?;
+                for i in 1..indices.len() - 1 {
+                    group.faces.push([indices[0], indices[i], indices[i + 1]]);
+                }
+            }
+            Some("g") | Some("o") => {
+                let material = group.material.clone();
+                meshes.extend(std::mem::replace(&mut group, MeshBuilder::new(material)).finish());
+            }
+            Some("usemtl") => {
+                let material = words.next()
+                    .and_then(|name| materials.get(name))
+                    .unwrap_or(&default_material)
+                    .clone();
+                meshes.extend(std::mem::replace(&mut group, MeshBuilder::new(material)).finish());
+            }
+            Some("mtllib") => {
+                // A library that can't be read defines nothing, as exported
+                // files often name one that wasn't shipped with them. One
+                // that is there but broken is still an error.
+                for name in words {
+                    if let Ok(source) = read_mtl(name) {
+                        let library = parse_mtl(&source)
+                            .map_err(|error| ObjError::Mtl(name.to_string(), Box::new(error)))?;
+                        materials.extend(library);
+                    }
+                }
+            }
+            // Smoothing groups, lines, points and free-form geometry.
+            _ => {}
+        }
+    }
+    meshes.extend(group.finish());
+    Ok(meshes)
+}
+
+// The materials in an MTL file. Only what maps onto this renderer's
+// materials is read: emitters become DiffuseLight, transparent materials
+// Dielectric, ray-traced reflections Metal and anything else Lambertian.
+pub fn parse_mtl(source: &str) -> Result<Materials, ObjError> {
+    let mut materials = Materials::new();
+    let mut current: Option<(String, MtlProperties)> = None;
+
+    for (number, line) in lines(source) {
+        let syntax = |reason| ObjError::Syntax(number, reason);
+        let mut words = line.split_whitespace();
+        let keyword = match words.next() {
+            Some("newmtl") => {
+                let name = words.next().ok_or_else(|| syntax("material without a name"))?;
+                if let Some((name, properties)) = current.replace((name.to_string(), MtlProperties::default())) {
+                    materials.insert(name, properties.material());
+                }
+                continue;
+            }
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        let properties = match (keyword, current.as_mut()) {
+            ("Kd" | "Ks" | "Ke" | "Ns" | "Ni" | "d" | "Tr" | "illum", None) =>
+                return Err(syntax("material property before newmtl")),
+            (_, Some((_, properties))) => properties,
+            (_, None) => continue,
+        };
+        match keyword {
+            "Kd" | "Ks" | "Ke" => {
+                let [r, g, b] = numbers(&mut words, 3).ok_or_else(|| syntax("invalid color"))?;
+                let color = Color::new(r, g, b);
+                match keyword {
+                    "Kd" => properties.diffuse = color,
+                    "Ks" => properties.specular = color,
+                    _ => properties.emission = color,
+                }
+            }
+            "Ns" | "Ni" | "d" | "Tr" => {
+                let [value] = numbers(&mut words, 1).ok_or_else(|| syntax("invalid number"))?;
+                match keyword {
+                    "Ns" => properties.shininess = value,
+                    "Ni" => properties.refraction_index = Some(value),
+                    "d" => properties.dissolve = value,
+                    _ => properties.dissolve = 1.0 - value,
+                }
+            }
+            "illum" => {
+                let model = words.next().and_then(|word| word.parse().ok());
+                properties.illumination = Some(model.ok_or_else(|| syntax("invalid illumination model"))?);
+            }
+            // Texture maps and the rest.
+            _ => {}
+        }
+    }
+    if let Some((name, properties)) = current {
+        materials.insert(name, properties.material());
+    }
+    Ok(materials)
+}
+
+struct MtlProperties {
+    diffuse: Color,
+    specular: Color,
+    emission: Color,
+    shininess: f64,
+    refraction_index: Option<f64>,
+    dissolve: f64,
+    illumination: Option<u32>,
+}
+
+impl Default for MtlProperties {
+    fn default() -> MtlProperties {
+        MtlProperties {
+            diffuse: Color::new(0.8, 0.8, 0.8),
+            specular: Color::BLACK,
+            emission: Color::BLACK,
+            shininess: 0.0,
+            refraction_index: None,
+            dissolve: 1.0,
+            illumination: None,
+        }
+    }
+}
+
+impl MtlProperties {
+    fn material(&self) -> Arc<dyn Material> {
+        let model = self.illumination.unwrap_or(2);
+        if self.emission != Color::BLACK {
+            Arc::new(DiffuseLight::new(self.emission))
+        }
+        else if self.dissolve < 1.0 || [4, 6, 7, 9].contains(&model) {
+            Arc::new(Dielectric::new(self.refraction_index.unwrap_or(1.5)))
+        }
+        else if model == 3 || model == 5 {
+            // The usual conversion from a Phong exponent to a roughness.
+            let fuzz = (2.0 / (self.shininess.max(0.0) + 2.0)).sqrt();
+            Arc::new(Metal::new(self.specular, fuzz))
+        }
+        else {
+            Arc::new(Lambertian::new(self.diffuse))
+        }
+    }
+}
+
+// Numbered lines without comments.
+fn lines(source: &str) -> impl Iterator<Item = (usize, &str)> {
+    source.lines().enumerate().map(|(i, line)| (i + 1, line.split('#').next().unwrap_or("")))
+}
+
+// Up to N numbers, of which the first `required` must be there. Missing
+// optional ones are zero and extra ones are ignored.
+fn numbers<const N: usize>(words: &mut SplitWhitespace, required: usize) -> Option<[f64; N]> {
+    let mut values = [0.0; N];
+    for (i, value) in values.iter_mut().enumerate() {
+        match words.next() {
+            Some(word) => *value = word.parse().ok()?,
+            None if i < required => return None,
+            None => break,
+        }
+    }
+    Some(values)
+}
+
+// Indices of a face vertex's position, texture coordinate and normal, from
+// "v", "v/vt", "v//vn" or "v/vt/vn". OBJ counts from 1, and negative
+// indices count back from the last element so far.
+type FaceVertex = (usize, Option<usize>, Option<usize>);
+
+fn face_vertex(word: &str, positions: usize, uvs: usize, normals: usize) -> Option<FaceVertex> {
+    let resolve = |index: &str, count: usize| -> Option<usize> {
+        let index: i64 = index.parse().ok()?;
+        let resolved = if index < 0 { count as i64 + index } else { index - 1 };
+        if (0..count as i64).contains(&resolved) { Some(resolved as usize) } else { None }
+    };
+    let optional = |index: Option<&str>, count: usize| match index {
+        None | Some("") => Some(None),
+        Some(index) => resolve(index, count).map(Some),
+    };
+    let mut parts = word.split('/');
+    let position = resolve(parts.next()?, positions)?;
+    let uv = optional(parts.next(), uvs)?;
+    let normal = optional(parts.next(), normals)?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((position, uv, normal))
+}
+
+struct MeshBuilder {
+    material: Arc<dyn Material>,
+    indices: HashMap<FaceVertex, u32>,
+    positions: Vec<Point3>,
+    uvs: Vec<Option<Vec2>>,
+    normals: Vec<Option<Vec3>>,
+    faces: Vec<[u32; 3]>,
+}
+
+impl MeshBuilder {
+    fn new(material: Arc<dyn Material>) -> MeshBuilder {
+        MeshBuilder {
+            material,
+            indices: HashMap::new(),
+            positions: Vec::new(),
+            uvs: Vec::new(),
+            normals: Vec::new(),
+            faces: Vec::new(),
+        }
+    }
+
+    // None once the mesh has as many vertices as u32 can index.
+    fn vertex(&mut self, vertex: FaceVertex, positions: &[Point3], uvs: &[Vec2], normals: &[Option<Vec3>]) -> Option<u32> {
+        if let Some(&index) = self.indices.get(&vertex) {
+            return Some(index);
+        }
+        let (position, uv, normal) = vertex;
+        let index = u32::try_from(self.positions.len()).ok()?;
+        self.positions.push(positions[position]);
+        self.uvs.push(uv.map(|uv| uvs[uv]));
+        self.normals.push(normal.and_then(|normal| normals[normal]));
+        self.indices.insert(vertex, index);
+        Some(index)
+    }
+
+    // The mesh has normals or texture coordinates only if every vertex has
+    // them. None if there were no faces.
+    fn finish(self) -> Option<TriangleMesh> {
+        if self.faces.is_empty() {
+            return None;
+        }
+        let uvs: Option<Vec<Vec2>> = self.uvs.into_iter().collect();
+        let normals: Option<Vec<Vec3>> = self.normals.into_iter().collect();
+        let mut mesh = TriangleMesh::new(self.positions, self.faces, self.material);
+        if let Some(uvs) = uvs {
+            mesh = mesh.with_uvs(uvs);
+        }
+        if let Some(normals) = normals {
+            mesh = mesh.with_normals(normals);
+        }
+        Some(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use super::*;
+
+    fn parse(source: &str) -> Vec<TriangleMesh> {
+        parse_obj(source, Arc::new(Black), |name| Err(io::Error::new(io::ErrorKind::NotFound, name))).unwrap()
+    }
+
+    #[test]
+    fn polygons_are_fanned() {
+        let meshes = parse("\
+            # A unit square\n\
+            v 0 0 0\n\
+            v 1 0 0\n\
+            v 1 1 0\n\
+            v 0 1 0 1.0\n\
+            f 1 2 3 4\n");
+        assert_eq!(1, meshes.len());
+        assert_eq!(&[[0, 1, 2], [0, 2, 3]], meshes[0].faces());
+        assert_vec3_equal!(Point3::new(0.0, 1.0, 0.0), meshes[0].positions()[3]);
+        assert!(meshes[0].normals().is_none());
+        assert!(meshes[0].uvs().is_none());
+    }
+
+    #[test]
+    fn shared_vertices_and_attributes() {
+        let meshes = parse("\
+            v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n\
+            vt 0 0\nvt 1 0\nvt 1 1\nvt 0 1\n\
+            vn 0 0 2\n\
+            f 1/1/1 2/2/1 3/3/1\n\
+            f -4/-4/-1 -2/-2/-1 -1/-1/-1\n");
+        let mesh = &meshes[0];
+        assert_eq!(4, mesh.positions().len());
+        assert_eq!(&[[0, 1, 2], [0, 2, 3]], mesh.faces());
+        assert_vec3_equal!(Vec3::Z, mesh.normals().unwrap()[3]);
+        assert_approx_eq!(1.0, mesh.uvs().unwrap()[2].y());
+
+        // The same position with another texture coordinate is another vertex.
+        let meshes = parse("v 0 0 0\nv 1 0 0\nv 0 1 0\nvt 0 0\nvt 1 1\nf 1/1 2/1 3/1\nf 1/2 3/1 2/1\n");
+        assert_eq!(4, meshes[0].positions().len());
+    }
+
+    #[test]
+    fn normals_only_when_every_vertex_has_one() {
+        let meshes = parse("v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//1\nf 1 2 3\n");
+        assert!(meshes[0].normals().is_none());
+    }
+
+    #[test]
+    fn groups_and_materials() {
+        let obj = "\
+            mtllib scene.mtl\n\
+            v 0 0 0\nv 1 0 0\nv 0 1 0\n\
+            f 1 2 3\n\
+            usemtl glass\n\
+            f 1 2 3\n\
+            g lid\n\
+            f 3 2 1\n\
+            usemtl lamp\n\
+            usemtl missing\n\
+            f 1 2 3\n";
+        let mtl = "newmtl glass\nNi 1.33\nd 0.1\n\nnewmtl lamp\nKe 4 4 4\n";
+        let mut requested = Vec::new();
+        let meshes = parse_obj(obj, Arc::new(Black), |name| {
+            requested.push(name.to_string());
+            Ok(mtl.to_string())
+        }).unwrap();
+        assert_eq!(vec!["scene.mtl"], requested);
+        assert_eq!(4, meshes.len());
+        assert_material_equal!(Black, meshes[0].material());
+        assert_material_equal!(Dielectric::new(1.33), meshes[1].material());
+        assert_material_equal!(Dielectric::new(1.33), meshes[2].material());
+        assert_material_equal!(Black, meshes[3].material());
+    }
+
+    #[test]
+    fn mtl_materials() {
+        let materials = parse_mtl("\
+            newmtl clay\n\
+            Kd 0.5 0.4 0.3\n\
+            map_Kd clay.png\n\
+            newmtl chrome\n\
+            Ks 0.9 0.9 0.9\n\
+            Ns 6\n\
+            illum 3\n\
+            newmtl glass # no Ni\n\
+            illum 7\n\
+            newmtl lamp\n\
+            Kd 1 1 1\n\
+            Ke 10 9 8\n").unwrap();
+        assert_eq!(4, materials.len());
+        assert_material_equal!(Lambertian::new(Color::new(0.5, 0.4, 0.3)), materials["clay"]);
+        assert_material_equal!(Metal::new(Color::new(0.9, 0.9, 0.9), 0.5), materials["chrome"]);
+        assert_material_equal!(Dielectric::new(1.5), materials["glass"]);
+        assert_material_equal!(DiffuseLight::new(Color::new(10.0, 9.0, 8.0)), materials["lamp"]);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |source: &str| parse_obj(source, Arc::new(Black), |_| Ok("Kd 1 1 1\n".to_string())).unwrap_err();
+        assert!(matches!(error("v 0 0\n"), ObjError::Syntax(1, _)));
+        assert!(matches!(error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n"), ObjError::Syntax(4, _)));
+        assert!(matches!(error("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 0 1 2\n"), ObjError::Syntax(4, _)));
+        assert!(matches!(error("v 0 0 0\nv 1 0 0\nf 1 2\n"), ObjError::Syntax(3, _)));
+        assert!(matches!(error("v 0 0 0\nf 1/1 1/1 1/1\n"), ObjError::Syntax(2, _)));
+        match error("mtllib broken.mtl\n") {
+            ObjError::Mtl(name, error) => {
+                assert_eq!("broken.mtl", name);
+                assert!(matches!(*error, ObjError::Syntax(1, _)));
+            }
+            other => panic!("unexpected error {}", other),
+        }
+        assert_eq!("in broken.mtl: line 1: material property before newmtl", error("mtllib broken.mtl").to_string());
+    }
+
+    #[test]
+    fn unreadable_library_defines_nothing() {
+        let obj = "mtllib gone.mtl scene.mtl\nusemtl red\nv 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\nusemtl blue\nf 3 2 1\n";
+        let meshes = parse_obj(obj, Arc::new(Black), |name| match name {
+            "scene.mtl" => Ok("newmtl blue\nKd 0 0 1\n".to_string()),
+            _ => Err(io::Error::new(io::ErrorKind::NotFound, "not found")),
+        }).unwrap();
+        assert_eq!(2, meshes.len());
+        assert_material_equal!(Black, meshes[0].material());
+        assert_material_equal!(Lambertian::new(Color::new(0.0, 0.0, 1.0)), meshes[1].material());
+    }
+
+    #[test]
+    fn load_from_files() {
+        let directory = std::env::temp_dir().join(format!("vectors-obj-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("triangle.obj"), "mtllib triangle.mtl\nusemtl red\nv 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").unwrap();
+        fs::write(directory.join("triangle.mtl"), "newmtl red\nKd 1 0 0\n").unwrap();
+        fs::write(directory.join("unshipped.obj"), "mtllib unshipped.mtl\nusemtl red\nv 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").unwrap();
+        let meshes = load_obj(directory.join("triangle.obj"), Arc::new(Black));
+        let unshipped = load_obj(directory.join("unshipped.obj"), Arc::new(Black));
+        let missing = load_obj(directory.join("missing.obj"), Arc::new(Black));
+        fs::remove_dir_all(&directory).unwrap();
+
+        let meshes = meshes.unwrap();
+        assert_eq!(1, meshes[0].len());
+        assert_material_equal!(Lambertian::new(Color::new(1.0, 0.0, 0.0)), meshes[0].material());
+        let unshipped = unshipped.unwrap();
+        assert_eq!(1, unshipped[0].len());
+        assert_material_equal!(Black, unshipped[0].material());
+        assert!(matches!(missing, Err(ObjError::Io(_))));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::vector::Vec3;
+
+// Orthonormal basis, used to express directions sampled around +z in a
+// frame aligned with a surface normal.
+#[derive(Clone, Copy, Debug)]
+pub struct Onb {
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+}
+
+impl Onb {
+    // w is aligned with the given normal; u and v are arbitrary but
+    // perpendicular to it and to each other (right-handed).
+    pub fn build_from_w(normal: Vec3) -> Onb {
+        let w = normal.unit_vector();
+        // Any vector not parallel to w works as a starting point.
+        let a = if w.x().abs() > 0.9 { Vec3::Y } else { Vec3::X };
+        let v = w.cross(a).unit_vector();
+        let u = v.cross(w);
+        Onb { u, v, w }
+    }
+
+    pub fn u(&self) -> Vec3 {
+        self.u
+    }
+
+    pub fn v(&self) -> Vec3 {
+        self.v
+    }
+
+    pub fn w(&self) -> Vec3 {
+        self.w
+    }
+
+    // Local coordinates (a, b, c) to world space.
+    pub fn local(&self, a: f64, b: f64, c: f64) -> Vec3 {
+        a * self.u + b * self.v + c * self.w
+    }
+
+    pub fn transform(&self, vector: Vec3) -> Vec3 {
+        self.local(vector.x(), vector.y(), vector.z())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use super::*;
+
+    #[test]
+    fn build_from_w_is_orthonormal() {
+        for normal in [Vec3::new(0.3, -2.0, 1.0), Vec3::X, -Vec3::Y, Vec3::new(0.95, 0.1, 0.0)].iter() {
+            let onb = Onb::build_from_w(*normal);
+            assert_vec3_equal!(normal.unit_vector(), onb.w());
+            assert_approx_eq!(1.0, onb.u().length(), 0.0001);
+            assert_approx_eq!(1.0, onb.v().length(), 0.0001);
+            assert_approx_eq!(0.0, onb.u().dot(onb.v()), 0.0001);
+            assert_approx_eq!(0.0, onb.u().dot(onb.w()), 0.0001);
+            assert_approx_eq!(0.0, onb.v().dot(onb.w()), 0.0001);
+            assert_vec3_equal!(onb.w(), onb.u().cross(onb.v()));
+        }
+    }
+
+    #[test]
+    fn local() {
+        let onb = Onb::build_from_w(Vec3::new(0.0, 2.0, 0.0));
+        assert_vec3_equal!(Vec3::Y, onb.local(0.0, 0.0, 1.0));
+        let result = onb.local(1.0, 2.0, 3.0);
+        assert_vec3_equal!(onb.u() + 2.0 * onb.v() + 3.0 * onb.w(), result);
+    }
+
+    #[test]
+    fn transform_preserves_length() {
+        let onb = Onb::build_from_w(Vec3::new(1.0, 1.0, 1.0));
+        let vector = Vec3::new(0.3, -0.4, 1.2);
+        let result = onb.transform(vector);
+        assert_approx_eq!(vector.length(), result.length(), 0.0001);
+        assert_approx_eq!(1.2, result.dot(onb.w()), 0.0001);
+    }
+}
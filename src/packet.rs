@@ -0,0 +1,275 @@
+use core::ops::{Add, Sub, Neg, Mul, BitAnd, BitOr, Not};
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+
+use crate::vector::Vec3;
+
+// Structure-of-arrays bundle of N vectors, one lane per vector (e.g. per ray
+// in a packet). Every operation is a plain loop over lanes, which the
+// compiler turns into SIMD across rays instead of within one Vec3.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vec3Packet<const N: usize> {
+    x: [f64; N],
+    y: [f64; N],
+    z: [f64; N],
+}
+
+pub type Vec3x4 = Vec3Packet<4>;
+pub type Vec3x8 = Vec3Packet<8>;
+
+// Per-lane booleans produced by comparisons and consumed by select.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mask<const N: usize>([bool; N]);
+
+pub type Mask4 = Mask<4>;
+pub type Mask8 = Mask<8>;
+
+fn lanes<const N: usize>(f: impl FnMut(usize) -> f64) -> [f64; N] {
+    core::array::from_fn(f)
+}
+
+impl<const N: usize> Vec3Packet<N> {
+    pub fn new(vectors: [Vec3; N]) -> Vec3Packet<N> {
+        Vec3Packet {
+            x: lanes(|i| vectors[i].x()),
+            y: lanes(|i| vectors[i].y()),
+            z: lanes(|i| vectors[i].z())
+        }
+    }
+
+    // The same vector in every lane.
+    pub fn splat(vector: Vec3) -> Vec3Packet<N> {
+        Vec3Packet {
+            x: [vector.x(); N],
+            y: [vector.y(); N],
+            z: [vector.z(); N]
+        }
+    }
+
+    pub fn lane(&self, i: usize) -> Vec3 {
+        Vec3::new(self.x[i], self.y[i], self.z[i])
+    }
+
+    pub fn to_vectors(self) -> [Vec3; N] {
+        core::array::from_fn(|i| self.lane(i))
+    }
+
+    pub fn dot(&self, other: Vec3Packet<N>) -> [f64; N] {
+        lanes(|i| self.x[i] * other.x[i] + self.y[i] * other.y[i] + self.z[i] * other.z[i])
+    }
+
+    pub fn cross(&self, other: Vec3Packet<N>) -> Vec3Packet<N> {
+        Vec3Packet {
+            x: lanes(|i| self.y[i] * other.z[i] - self.z[i] * other.y[i]),
+            y: lanes(|i| self.z[i] * other.x[i] - self.x[i] * other.z[i]),
+            z: lanes(|i| self.x[i] * other.y[i] - self.y[i] * other.x[i])
+        }
+    }
+
+    pub fn length_squared(&self) -> [f64; N] {
+        self.dot(*self)
+    }
+
+    pub fn length(&self) -> [f64; N] {
+        let length_squared = self.length_squared();
+        lanes(|i| length_squared[i].sqrt())
+    }
+
+    // Lane-wise choice: lanes where the mask is set come from if_true.
+    pub fn select(mask: Mask<N>, if_true: Vec3Packet<N>, if_false: Vec3Packet<N>) -> Vec3Packet<N> {
+        let pick = |a: [f64; N], b: [f64; N]| lanes(|i| if mask.0[i] { a[i] } else { b[i] });
+        Vec3Packet {
+            x: pick(if_true.x, if_false.x),
+            y: pick(if_true.y, if_false.y),
+            z: pick(if_true.z, if_false.z)
+        }
+    }
+}
+
+impl<const N: usize> Add<Vec3Packet<N>> for Vec3Packet<N> {
+    type Output = Vec3Packet<N>;
+    fn add(self, other: Vec3Packet<N>) -> Vec3Packet<N> {
+        Vec3Packet {
+            x: lanes(|i| self.x[i] + other.x[i]),
+            y: lanes(|i| self.y[i] + other.y[i]),
+            z: lanes(|i| self.z[i] + other.z[i])
+        }
+    }
+}
+
+impl<const N: usize> Sub<Vec3Packet<N>> for Vec3Packet<N> {
+    type Output = Vec3Packet<N>;
+    fn sub(self, other: Vec3Packet<N>) -> Vec3Packet<N> {
+        Vec3Packet {
+            x: lanes(|i| self.x[i] - other.x[i]),
+            y: lanes(|i| self.y[i] - other.y[i]),
+            z: lanes(|i| self.z[i] - other.z[i])
+        }
+    }
+}
+
+impl<const N: usize> Neg for Vec3Packet<N> {
+    type Output = Vec3Packet<N>;
+    fn neg(self) -> Vec3Packet<N> {
+        Vec3Packet {
+            x: lanes(|i| -self.x[i]),
+            y: lanes(|i| -self.y[i]),
+            z: lanes(|i| -self.z[i])
+        }
+    }
+}
+
+impl<const N: usize> Mul<f64> for Vec3Packet<N> {
+    type Output = Vec3Packet<N>;
+    fn mul(self, scalar: f64) -> Vec3Packet<N> {
+        self * [scalar; N]
+    }
+}
+
+// A different scalar per lane, e.g. each ray's hit distance.
+impl<const N: usize> Mul<[f64; N]> for Vec3Packet<N> {
+    type Output = Vec3Packet<N>;
+    fn mul(self, scalars: [f64; N]) -> Vec3Packet<N> {
+        Vec3Packet {
+            x: lanes(|i| self.x[i] * scalars[i]),
+            y: lanes(|i| self.y[i] * scalars[i]),
+            z: lanes(|i| self.z[i] * scalars[i])
+        }
+    }
+}
+
+impl<const N: usize> Mask<N> {
+    pub const fn new(lanes: [bool; N]) -> Mask<N> {
+        Mask(lanes)
+    }
+
+    pub fn lt(a: [f64; N], b: [f64; N]) -> Mask<N> {
+        Mask(core::array::from_fn(|i| a[i] < b[i]))
+    }
+
+    pub fn gt(a: [f64; N], b: [f64; N]) -> Mask<N> {
+        Mask(core::array::from_fn(|i| a[i] > b[i]))
+    }
+
+    pub fn lane(&self, i: usize) -> bool {
+        self.0[i]
+    }
+
+    pub fn any(&self) -> bool {
+        self.0.iter().any(|&lane| lane)
+    }
+
+    pub fn all(&self) -> bool {
+        self.0.iter().all(|&lane| lane)
+    }
+}
+
+impl<const N: usize> BitAnd for Mask<N> {
+    type Output = Mask<N>;
+    fn bitand(self, other: Mask<N>) -> Mask<N> {
+        Mask(core::array::from_fn(|i| self.0[i] && other.0[i]))
+    }
+}
+
+impl<const N: usize> BitOr for Mask<N> {
+    type Output = Mask<N>;
+    fn bitor(self, other: Mask<N>) -> Mask<N> {
+        Mask(core::array::from_fn(|i| self.0[i] || other.0[i]))
+    }
+}
+
+impl<const N: usize> Not for Mask<N> {
+    type Output = Mask<N>;
+    fn not(self) -> Mask<N> {
+        Mask(core::array::from_fn(|i| !self.0[i]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use super::*;
+
+    fn sample() -> [Vec3; 4] {
+        [
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(-1.0, 0.5, 2.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(3.0, 4.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn lanes_round_trip() {
+        let vectors = sample();
+        let packet = Vec3x4::new(vectors);
+        for (i, vector) in vectors.iter().enumerate() {
+            assert_vec3_equal!(*vector, packet.lane(i));
+        }
+        assert!(vectors == packet.to_vectors());
+    }
+
+    #[test]
+    fn splat() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        let packet = Vec3x8::splat(vector);
+        for i in 0..8 {
+            assert_vec3_equal!(vector, packet.lane(i));
+        }
+    }
+
+    #[test]
+    fn arithmetic_matches_vec3() {
+        let a = sample();
+        let b = [Vec3::ONE, Vec3::X, Vec3::new(2.0, -1.0, 0.5), Vec3::Z];
+        let (pa, pb) = (Vec3x4::new(a), Vec3x4::new(b));
+        let scalars = [1.0, 2.0, 3.0, 4.0];
+        for i in 0..4 {
+            assert_vec3_equal!(a[i] + b[i], (pa + pb).lane(i));
+            assert_vec3_equal!(a[i] - b[i], (pa - pb).lane(i));
+            assert_vec3_equal!(-a[i], (-pa).lane(i));
+            assert_vec3_equal!(a[i] * 2.0, (pa * 2.0).lane(i));
+            assert_vec3_equal!(a[i] * scalars[i], (pa * scalars).lane(i));
+        }
+    }
+
+    #[test]
+    fn dot_cross_and_length() {
+        let a = sample();
+        let b = [Vec3::ONE, Vec3::X, Vec3::new(2.0, -1.0, 0.5), Vec3::Z];
+        let (pa, pb) = (Vec3x4::new(a), Vec3x4::new(b));
+        let dot = pa.dot(pb);
+        let cross = pa.cross(pb);
+        let length = pa.length();
+        for i in 0..4 {
+            assert_approx_eq!(a[i].dot(b[i]), dot[i], 0.0001);
+            assert_vec3_equal!(a[i].cross(b[i]), cross.lane(i));
+            assert_approx_eq!(a[i].length(), length[i], 0.0001);
+        }
+    }
+
+    #[test]
+    fn select() {
+        let a = Vec3x4::splat(Vec3::ONE);
+        let b = Vec3x4::splat(Vec3::ZERO);
+        let mask = Mask4::new([true, false, false, true]);
+        let result = Vec3x4::select(mask, a, b);
+        assert_vec3_equal!(Vec3::ONE, result.lane(0));
+        assert_vec3_equal!(Vec3::ZERO, result.lane(1));
+        assert_vec3_equal!(Vec3::ZERO, result.lane(2));
+        assert_vec3_equal!(Vec3::ONE, result.lane(3));
+    }
+
+    #[test]
+    fn masks() {
+        let values = [1.0, 5.0, -2.0, 3.0];
+        let threshold = [2.0; 4];
+        let below = Mask4::lt(values, threshold);
+        let above = Mask4::gt(values, threshold);
+        assert!(below == Mask4::new([true, false, true, false]));
+        assert!(below == !above);
+        assert!((below | above).all());
+        assert!(!(below & above).any());
+        assert!(below.lane(0));
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::onb::Onb;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::vector::{Vec2, Vec3};
+
+// The infinite plane through a point, facing along its normal. Texture
+// coordinates are distances from the point along two axes in the plane,
+// arbitrary ones unless set with with_uv_axes, so a Checker material gives
+// squares of its size in world units. Having no bounding box, planes can't
+// go in a BvhNode; see HittableList::accelerated.
+#[derive(Clone, Debug)]
+pub struct Plane {
+    point: Point3,
+    normal: Vec3,
+    u_axis: Vec3,
+    v_axis: Vec3,
+    material: Arc<dyn Material>,
+}
+
+impl Plane {
+    pub fn new(point: Point3, normal: Vec3, material: Arc<dyn Material>) -> Plane {
+        let onb = Onb::build_from_w(normal);
+        Plane { point, normal: onb.w(), u_axis: onb.u(), v_axis: onb.v(), material }
+    }
+
+    // Directions in the plane that u and v increase along, one unit per
+    // unit of their lengths. Parts along the normal are ignored, so axes
+    // parallel to it, which would leave nothing, are rejected.
+    pub fn with_uv_axes(self, u_axis: Vec3, v_axis: Vec3) -> Plane {
+        let flatten = |axis: Vec3| axis - self.normal * axis.dot(self.normal);
+        let (u_axis, v_axis) = (flatten(u_axis), flatten(v_axis));
+        assert!(u_axis.length_squared() > 0.0 && v_axis.length_squared() > 0.0,
+            "plane uv axes must not be parallel to the normal");
+        Plane { u_axis: u_axis / u_axis.length_squared(), v_axis: v_axis / v_axis.length_squared(), ..self }
+    }
+
+    pub fn point(&self) -> Point3 {
+        self.point
+    }
+
+    pub fn normal(&self) -> Vec3 {
+        self.normal
+    }
+}
+
+impl Hittable for Plane {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        let t = intersect(self.point, self.normal, ray, t_range)?;
+        let offset = ray.at(t) - self.point;
+        let uv = Vec2::new(offset.dot(self.u_axis), offset.dot(self.v_axis));
+        Some(HitRecord::new(ray, t, self.normal, &*self.material).with_uv(uv))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        None
+    }
+}
+
+// The ray's t where it crosses the plane through point with the given
+// normal, if in t_range. Shared with the flat shapes that lie in a plane.
+pub(crate) fn intersect(point: Point3, normal: Vec3, ray: &Ray, t_range: Interval) -> Option<f64> {
+    // Parallel rays give an infinite or NaN t, which is never in range.
+    let t = (point - ray.origin()).dot(normal) / ray.direction().dot(normal);
+    if t_range.surrounds(t) { Some(t) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use super::*;
+
+    fn floor() -> Plane {
+        Plane::new(Point3::new(0.0, -1.0, 0.0), Vec3::new(0.0, 2.0, 0.0), Arc::new(Black))
+    }
+
+    #[test]
+    fn hit_from_either_side() {
+        let floor = floor();
+        let record = floor.hit(&Ray::new(Point3::new(3.0, 1.0, -40.0), Vec3::new(0.0, -1.0, 0.0)), Interval::FORWARD).unwrap();
+        assert_approx_eq!(2.0, record.t);
+        assert_vec3_equal!(Point3::new(3.0, -1.0, -40.0), record.point);
+        assert!(record.front_face);
+        assert_vec3_equal!(Vec3::Y, record.normal);
+
+        let record = floor.hit(&Ray::new(Point3::new(0.0, -3.0, 0.0), Vec3::new(1.0, 1.0, 0.0)), Interval::FORWARD).unwrap();
+        assert_approx_eq!(2.0, record.t);
+        assert!(!record.front_face);
+        assert_vec3_equal!(-Vec3::Y, record.normal);
+    }
+
+    #[test]
+    fn misses() {
+        let floor = floor();
+        assert!(floor.hit(&Ray::new(Point3::ORIGIN, Vec3::Y), Interval::FORWARD).is_none());
+        assert!(floor.hit(&Ray::new(Point3::ORIGIN, Vec3::X), Interval::FORWARD).is_none());
+        assert!(floor.hit(&Ray::new(Point3::ORIGIN, -Vec3::Y), Interval::new(0.001, 0.5)).is_none());
+        assert!(floor.bounding_box().is_none());
+    }
+
+    #[test]
+    fn uvs_measure_distance_in_the_plane() {
+        let down = |x, z| Ray::new(Point3::new(x, 0.0, z), -Vec3::Y);
+        let floor = floor();
+        let a = floor.hit(&down(0.0, 0.0), Interval::FORWARD).unwrap().uv;
+        let b = floor.hit(&down(3.0, 4.0), Interval::FORWARD).unwrap().uv;
+        assert_approx_eq!(0.0, a.length());
+        assert_approx_eq!(5.0, (b - a).length());
+
+        let floor = floor.with_uv_axes(Vec3::new(2.0, 1.0, 0.0), -Vec3::Z);
+        let uv = floor.hit(&down(3.0, 4.0), Interval::FORWARD).unwrap().uv;
+        assert_approx_eq!(1.5, uv.x());
+        assert_approx_eq!(-4.0, uv.y());
+    }
+
+    #[test]
+    #[should_panic(expected = "plane uv axes must not be parallel to the normal")]
+    fn uv_axis_along_the_normal() {
+        floor().with_uv_axes(Vec3::X, Vec3::new(0.0, -3.0, 0.0));
+    }
+}
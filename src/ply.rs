@@ -0,0 +1,472 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::mesh::{Shading, TriangleMesh};
+use crate::point::Point3;
+use crate::vector::{Vec2, Vec3};
+
+// PLY import, ASCII or binary of either byte order, as written by scanners
+// and most modeling tools. Positions, normals and texture coordinates of
+// the vertex element and the vertex lists of the face element are read;
+// other elements and properties, such as colors, are skipped. Polygons are
+// split into fans of triangles.
+
+#[derive(Debug)]
+pub enum PlyError {
+    Io(io::Error),
+    // The file doesn't follow the format, or ends early.
+    Invalid(&'static str),
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for PlyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlyError::Io(error) => write!(f, "{}", error),
+            PlyError::Invalid(reason) => write!(f, "invalid PLY file: {}", reason),
+            PlyError::Unsupported(what) => write!(f, "unsupported PLY file: {}", what),
+        }
+    }
+}
+
+impl Error for PlyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PlyError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PlyError {
+    fn from(error: io::Error) -> PlyError {
+        PlyError::Io(error)
+    }
+}
+
+// The shading is used only if the file has no vertex normals.
+pub fn load_ply<P: AsRef<Path>>(path: P, shading: Shading, material: Arc<dyn Material>) -> Result<TriangleMesh, PlyError> {
+    parse_ply(&fs::read(path)?, shading, material)
+}
+
+pub fn parse_ply(data: &[u8], shading: Shading, material: Arc<dyn Material>) -> Result<TriangleMesh, PlyError> {
+    let (header, body) = split_header(data)?;
+    let (format, elements) = parse_header(header)?;
+    let mut body = match format {
+        Format::Ascii => {
+            let text = std::str::from_utf8(body).map_err(|_| PlyError::Invalid("ASCII data that isn't text"))?;
+            Body::Ascii(text.split_ascii_whitespace())
+        }
+        Format::BinaryLittleEndian => Body::Binary { data: body, big_endian: false },
+        Format::BinaryBigEndian => Body::Binary { data: body, big_endian: true },
+    };
+
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut faces = Vec::new();
+    for element in &elements {
+        let find = |names: &[&str]| element.properties.iter().position(|(name, _)| names.contains(&name.as_str()));
+        match element.name.as_str() {
+            "vertex" => {
+                let position = [find(&["x"]), find(&["y"]), find(&["z"])];
+                let [x, y, z] = match position {
+                    [Some(x), Some(y), Some(z)] => [x, y, z],
+                    _ => return Err(PlyError::Invalid("vertices without positions")),
+                };
+                let normal = [find(&["nx"]), find(&["ny"]), find(&["nz"])];
+                let uv = [find(&["u", "s", "texture_u"]), find(&["v", "t", "texture_v"])];
+                for _ in 0..element.count {
+                    let row = body.row(element)?;
+                    positions.push(Point3::new(row[x], row[y], row[z]));
+                    if let [Some(nx), Some(ny), Some(nz)] = normal {
+                        normals.push(Vec3::new(row[nx], row[ny], row[nz]).try_normalize());
+                    }
+                    if let [Some(u), Some(v)] = uv {
+                        uvs.push(Vec2::new(row[u], row[v]));
+                    }
+                }
+            }
+            "face" => {
+                let list = find(&["vertex_indices", "vertex_index"])
+                    .filter(|&i| matches!(element.properties[i].1, Property::List(..)))
+                    .ok_or(PlyError::Invalid("faces without vertex lists"))?;
+                for _ in 0..element.count {
+                    for (i, (_, property)) in element.properties.iter().enumerate() {
+                        if i != list {
+                            body.skip(*property)?;
+                            continue;
+                        }
+                        let indices = body.list(*property)?;
+                        if indices.len() < 3 {
+                            return Err(PlyError::Invalid("face with fewer than three vertices"));
+                        }
+                        for j in 1..indices.len() - 1 {
+                            faces.push([indices[0], indices[j], indices[j + 1]]);
+                        }
+                    }
+                }
+            }
+            _ => {
+                for _ in 0..element.count {
+                    body.row(element)?;
+                }
+            }
+        }
+    }
+
+    if faces.iter().flatten().any(|&index| index as usize >= positions.len()) {
+        return Err(PlyError::Invalid("face refers to a vertex out of range"));
+    }
+    // Several vertex elements may have different properties, leaving some
+    // vertices without the normals or texture coordinates of others.
+    let count = positions.len();
+    if (!normals.is_empty() && normals.len() != count) || (!uvs.is_empty() && uvs.len() != count) {
+        return Err(PlyError::Invalid("vertices differ in their properties"));
+    }
+    let mut mesh = TriangleMesh::new(positions, faces, material);
+    if !uvs.is_empty() {
+        mesh = mesh.with_uvs(uvs);
+    }
+    // Zero normals leave the choice to the shading.
+    match normals.into_iter().collect::<Option<Vec<_>>>() {
+        Some(normals) if !normals.is_empty() => Ok(mesh.with_normals(normals)),
+        _ => Ok(mesh.shaded(shading)),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Scalar {
+    // Both the original names and the sized ones newer files use.
+    fn from_name(name: &str) -> Option<Scalar> {
+        match name {
+            "char" | "int8" => Some(Scalar::I8),
+            "uchar" | "uint8" => Some(Scalar::U8),
+            "short" | "int16" => Some(Scalar::I16),
+            "ushort" | "uint16" => Some(Scalar::U16),
+            "int" | "int32" => Some(Scalar::I32),
+            "uint" | "uint32" => Some(Scalar::U32),
+            "float" | "float32" => Some(Scalar::F32),
+            "double" | "float64" => Some(Scalar::F64),
+            _ => None,
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::F64 => 8,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Property {
+    Scalar(Scalar),
+    // The types of the length and of the items.
+    List(Scalar, Scalar),
+}
+
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<(String, Property)>,
+}
+
+// The header's text and the data after it.
+fn split_header(data: &[u8]) -> Result<(&str, &[u8]), PlyError> {
+    if !data.starts_with(b"ply") {
+        return Err(PlyError::Invalid("missing ply signature"));
+    }
+    let end = data.windows(10).position(|window| window == b"end_header")
+        .ok_or(PlyError::Invalid("missing end_header"))?;
+    let body = data[end..].iter().position(|&byte| byte == b'\n')
+        .map(|newline| end + newline + 1)
+        .ok_or(PlyError::Invalid("missing end_header"))?;
+    let header = std::str::from_utf8(&data[..end]).map_err(|_| PlyError::Invalid("header that isn't text"))?;
+    Ok((header, &data[body..]))
+}
+
+fn parse_header(header: &str) -> Result<(Format, Vec<Element>), PlyError> {
+    let invalid = |reason| PlyError::Invalid(reason);
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    for line in header.lines().skip(1) {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["format", name, "1.0"] => format = Some(match *name {
+                "ascii" => Format::Ascii,
+                "binary_little_endian" => Format::BinaryLittleEndian,
+                "binary_big_endian" => Format::BinaryBigEndian,
+                _ => return Err(invalid("unknown format")),
+            }),
+            ["format", ..] => return Err(PlyError::Unsupported("format versions other than 1.0")),
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse().map_err(|_| invalid("invalid element count"))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", length, item, name] => {
+                let length = Scalar::from_name(length).ok_or_else(|| invalid("unknown property type"))?;
+                let item = Scalar::from_name(item).ok_or_else(|| invalid("unknown property type"))?;
+                let element = elements.last_mut().ok_or_else(|| invalid("property before element"))?;
+                element.properties.push((name.to_string(), Property::List(length, item)));
+            }
+            ["property", scalar, name] => {
+                let scalar = Scalar::from_name(scalar).ok_or_else(|| invalid("unknown property type"))?;
+                let element = elements.last_mut().ok_or_else(|| invalid("property before element"))?;
+                element.properties.push((name.to_string(), Property::Scalar(scalar)));
+            }
+            ["comment", ..] | ["obj_info", ..] | [] => {}
+            _ => return Err(invalid("unknown header line")),
+        }
+    }
+    Ok((format.ok_or_else(|| invalid("missing format"))?, elements))
+}
+
+enum Body<'a> {
+    Ascii(std::str::SplitAsciiWhitespace<'a>),
+    Binary { data: &'a [u8], big_endian: bool },
+}
+
+impl Body<'_> {
+    fn scalar(&mut self, scalar: Scalar) -> Result<f64, PlyError> {
+        let truncated = PlyError::Invalid("data ends early");
+        match self {
+            Body::Ascii(words) => {
+                let word = words.next().ok_or(truncated)?;
+                word.parse().map_err(|_| PlyError::Invalid("invalid number"))
+            }
+            Body::Binary { data, big_endian } => {
+                let size = scalar.size();
+                if data.len() < size {
+                    return Err(truncated);
+                }
+                let (bytes, rest) = data.split_at(size);
+                *data = rest;
+                // Read as big endian.
+                let mut buffer = [0; 8];
+                buffer[..size].copy_from_slice(bytes);
+                if !*big_endian {
+                    buffer[..size].reverse();
+                }
+                let b = buffer;
+                Ok(match scalar {
+                    Scalar::I8 => b[0] as i8 as f64,
+                    Scalar::U8 => b[0] as f64,
+                    Scalar::I16 => i16::from_be_bytes([b[0], b[1]]) as f64,
+                    Scalar::U16 => u16::from_be_bytes([b[0], b[1]]) as f64,
+                    Scalar::I32 => i32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    Scalar::U32 => u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    Scalar::F32 => f32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    Scalar::F64 => f64::from_be_bytes(b),
+                })
+            }
+        }
+    }
+
+    // Vertex indices, which meshes store as u32.
+    fn list(&mut self, property: Property) -> Result<Vec<u32>, PlyError> {
+        let (length, item) = match property {
+            Property::List(length, item) => (length, item),
+            Property::Scalar(_) => unreachable!(),
+        };
+        let length = self.scalar(length)?;
+        if length < 0.0 {
+            return Err(PlyError::Invalid("negative list length"));
+        }
+        (0..length as usize)
+            .map(|_| {
+                let index = self.scalar(item)?;
+                if index < 0.0 {
+                    Err(PlyError::Invalid("negative vertex index"))
+                }
+                else if index > u32::MAX as f64 {
+                    Err(PlyError::Invalid("face refers to a vertex out of range"))
+                }
+                else {
+                    Ok(index as u32)
+                }
+            })
+            .collect()
+    }
+
+    fn skip(&mut self, property: Property) -> Result<(), PlyError> {
+        match property {
+            Property::Scalar(scalar) => self.scalar(scalar).map(|_| ()),
+            Property::List(length, item) => {
+                let length = self.scalar(length)?;
+                for _ in 0..length.max(0.0) as usize {
+                    self.scalar(item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // The element's scalar properties, with lists read past and left as 0.
+    fn row(&mut self, element: &Element) -> Result<Vec<f64>, PlyError> {
+        element.properties.iter()
+            .map(|&(_, property)| match property {
+                Property::Scalar(scalar) => self.scalar(scalar),
+                Property::List(..) => self.skip(property).map(|_| 0.0),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use super::*;
+
+    const SQUARE: &str = "\
+ply
+format ascii 1.0
+comment a unit square facing +z
+element vertex 4
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+property float s
+property float t
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 0 0 1 0 0
+1 0 0 0 0 1 1 0
+1 1 0 0 0 1 1 1
+0 1 0 0 0 2 0 1
+4 0 1 2 3
+";
+
+    // The same square without normals or texture coordinates, but with a
+    // color per vertex and an extra element, in binary.
+    fn binary(big_endian: bool) -> Vec<u8> {
+        let format = if big_endian { "binary_big_endian" } else { "binary_little_endian" };
+        let mut data = format!("ply\r\nformat {} 1.0\r\nelement vertex 4\r\nproperty double x\r\nproperty double y\r\n\
+            property double z\r\nproperty uchar red\r\nelement face 2\r\nproperty uchar flags\r\n\
+            property list uint8 uint32 vertex_index\r\nelement edge 1\r\nproperty int vertex1\r\n\
+            property int vertex2\r\nend_header\r\n", format).into_bytes();
+        let mut push = |bytes: &mut [u8]| {
+            if !big_endian {
+                bytes.reverse();
+            }
+            data.extend_from_slice(bytes);
+        };
+        for &[x, y] in &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0f64]] {
+            for value in &[x, y, 0.0] {
+                push(&mut value.to_be_bytes());
+            }
+            push(&mut [255]);
+        }
+        for face in &[[0u32, 1, 2], [0, 2, 3]] {
+            push(&mut [7]);
+            push(&mut [3]);
+            for index in face {
+                push(&mut index.to_be_bytes());
+            }
+        }
+        push(&mut 0i32.to_be_bytes());
+        push(&mut 1i32.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn ascii_with_normals_and_uvs() {
+        let mesh = parse_ply(SQUARE.as_bytes(), Shading::Flat, Arc::new(Black)).unwrap();
+        assert_eq!(&[[0, 1, 2], [0, 2, 3]], mesh.faces());
+        assert_vec3_equal!(Point3::new(1.0, 1.0, 0.0), mesh.positions()[2]);
+        assert_vec3_equal!(Vec3::Z, mesh.normals().unwrap()[3]);
+        assert_approx_eq!(1.0, mesh.uvs().unwrap()[1].x());
+    }
+
+    #[test]
+    fn binary_in_both_byte_orders() {
+        for &big_endian in &[false, true] {
+            let mesh = parse_ply(&binary(big_endian), Shading::Flat, Arc::new(Black)).unwrap();
+            assert_eq!(&[[0, 1, 2], [0, 2, 3]], mesh.faces());
+            assert_vec3_equal!(Point3::new(0.0, 1.0, 0.0), mesh.positions()[3]);
+            assert!(mesh.normals().is_none());
+            assert!(mesh.uvs().is_none());
+        }
+        let mesh = parse_ply(&binary(false), Shading::Smooth, Arc::new(Black)).unwrap();
+        assert_vec3_equal!(Vec3::Z, mesh.normals().unwrap()[0]);
+    }
+
+    #[test]
+    fn errors() {
+        let parse = |data: &[u8]| parse_ply(data, Shading::Flat, Arc::new(Black)).unwrap_err();
+        assert!(matches!(parse(b"solid cube\n"), PlyError::Invalid("missing ply signature")));
+        assert!(matches!(parse(b"ply\nformat ascii 1.0\n"), PlyError::Invalid("missing end_header")));
+        assert!(matches!(parse(b"ply\nformat ascii 2.0\nend_header\n"), PlyError::Unsupported(_)));
+        let truncated = binary(false);
+        assert!(matches!(parse(&truncated[..truncated.len() - 12]), PlyError::Invalid("data ends early")));
+        let out_of_range = SQUARE.replace("4 0 1 2 3", "3 0 1 4");
+        assert!(matches!(parse(out_of_range.as_bytes()), PlyError::Invalid("face refers to a vertex out of range")));
+        // An index that u32 can't hold mustn't wrap around to a valid one.
+        let wrapping = SQUARE.replace("4 0 1 2 3", "3 0 1 4294967298").replace("uchar int", "uchar double");
+        assert!(matches!(parse(wrapping.as_bytes()), PlyError::Invalid("face refers to a vertex out of range")));
+    }
+
+    #[test]
+    fn vertex_elements_with_different_properties() {
+        let mixed = "\
+ply
+format ascii 1.0
+element vertex 2
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+element vertex 1
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 0 0 1
+1 0 0 0 0 1
+0 1 0
+3 0 1 2
+";
+        let result = parse_ply(mixed.as_bytes(), Shading::Flat, Arc::new(Black));
+        assert!(matches!(result, Err(PlyError::Invalid("vertices differ in their properties"))));
+        let without_normals = mixed.replace("property float nx\nproperty float ny\nproperty float nz\n", "")
+            .replace(" 0 0 1\n", "\n");
+        let mesh = parse_ply(without_normals.as_bytes(), Shading::Flat, Arc::new(Black)).unwrap();
+        assert_eq!(3, mesh.positions().len());
+    }
+}
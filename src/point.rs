@@ -0,0 +1,179 @@
+use core::fmt;
+use core::ops::{Add, Sub, AddAssign, SubAssign};
+
+use crate::vector::Vec3;
+
+// A position in space. Keeping points apart from directions (Vec3) lets the
+// compiler reject affine nonsense such as adding two points together:
+// point - point = vector, point + vector = point.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Point3(Vec3);
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3(Vec3::ZERO);
+
+    pub const fn new(x: f64, y: f64, z: f64) -> Point3 {
+        Point3(Vec3::new(x, y, z))
+    }
+
+    // Position reached by moving from the origin along the given vector.
+    pub const fn from_vec3(vector: Vec3) -> Point3 {
+        Point3(vector)
+    }
+
+    // Vector from the origin to this point.
+    pub const fn to_vec3(self) -> Vec3 {
+        self.0
+    }
+
+    pub const fn x(&self) -> f64 {
+        self.0.x()
+    }
+
+    pub const fn y(&self) -> f64 {
+        self.0.y()
+    }
+
+    pub const fn z(&self) -> f64 {
+        self.0.z()
+    }
+
+    pub fn distance(&self, other: Point3) -> f64 {
+        self.0.distance(other.0)
+    }
+
+    pub fn distance_squared(&self, other: Point3) -> f64 {
+        self.0.distance_squared(other.0)
+    }
+
+    pub fn lerp(self, other: Point3, t: f64) -> Point3 {
+        Point3(self.0.lerp(other.0, t))
+    }
+
+    // Mirror image across the plane of points p with normal · p = offset.
+    pub fn reflect_across_plane(&self, plane_normal: Vec3, plane_offset: f64) -> Point3 {
+        Point3(self.0.reflect_across_plane(plane_normal, plane_offset))
+    }
+}
+
+impl Add<Vec3> for Point3 {
+    type Output = Point3;
+    fn add(self, vector: Vec3) -> Point3 {
+        Point3(self.0 + vector)
+    }
+}
+
+impl Sub<Vec3> for Point3 {
+    type Output = Point3;
+    fn sub(self, vector: Vec3) -> Point3 {
+        Point3(self.0 - vector)
+    }
+}
+
+impl Sub<Point3> for Point3 {
+    type Output = Vec3;
+    fn sub(self, other: Point3) -> Vec3 {
+        self.0 - other.0
+    }
+}
+
+impl AddAssign<Vec3> for Point3 {
+    fn add_assign(&mut self, vector: Vec3) {
+        self.0 += vector;
+    }
+}
+
+impl SubAssign<Vec3> for Point3 {
+    fn sub_assign(&mut self, vector: Vec3) {
+        self.0 -= vector;
+    }
+}
+
+impl From<[f64; 3]> for Point3 {
+    fn from(array: [f64; 3]) -> Point3 {
+        Point3(Vec3::from(array))
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use super::*;
+
+    #[test]
+    fn origin() {
+        assert_vec3_equal!(Point3::new(0.0, 0.0, 0.0), Point3::ORIGIN);
+        assert_vec3_equal!(Point3::ORIGIN, Point3::default());
+    }
+
+    #[test]
+    fn point_plus_vector() {
+        let point = Point3::new(1.0, 2.0, 3.0);
+        let result = point + Vec3::new(4.0, 5.0, 6.0);
+        let expected = Point3::new(5.0, 7.0, 9.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn point_minus_vector() {
+        let point = Point3::new(1.0, 2.0, 3.0);
+        let result = point - Vec3::new(4.0, 5.0, 6.0);
+        let expected = Point3::new(-3.0, -3.0, -3.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn point_minus_point() {
+        let point1 = Point3::new(4.0, 5.0, 6.0);
+        let point2 = Point3::new(1.0, 2.0, 3.0);
+        let result: Vec3 = point1 - point2;
+        let expected = Vec3::new(3.0, 3.0, 3.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn assignment_operators() {
+        let mut point = Point3::new(1.0, 2.0, 3.0);
+        point += Vec3::new(1.0, 1.0, 1.0);
+        assert_vec3_equal!(Point3::new(2.0, 3.0, 4.0), point);
+        point -= Vec3::new(2.0, 0.0, 0.0);
+        assert_vec3_equal!(Point3::new(0.0, 3.0, 4.0), point);
+    }
+
+    #[test]
+    fn vec3_round_trip() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        let point = Point3::from_vec3(vector);
+        assert_vec3_equal!(vector, point);
+        assert_vec3_equal!(vector, point.to_vec3());
+    }
+
+    #[test]
+    fn distance() {
+        let point1 = Point3::new(1.0, 2.0, 3.0);
+        let point2 = Point3::new(4.0, 6.0, 3.0);
+        assert_approx_eq!(5.0, point1.distance(point2), 0.001);
+        assert_approx_eq!(25.0, point1.distance_squared(point2), 0.001);
+    }
+
+    #[test]
+    fn lerp() {
+        let point1 = Point3::new(0.0, 0.0, 0.0);
+        let point2 = Point3::new(2.0, 4.0, -2.0);
+        let result = point1.lerp(point2, 0.5);
+        assert_vec3_equal!(Point3::new(1.0, 2.0, -1.0), result);
+    }
+
+    #[test]
+    fn reflect_across_plane() {
+        let point = Point3::new(3.0, 1.0, 2.0);
+        let result = point.reflect_across_plane(Vec3::X, -1.0);
+        assert_vec3_equal!(Point3::new(-5.0, 1.0, 2.0), result);
+    }
+}
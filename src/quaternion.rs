@@ -0,0 +1,229 @@
+use core::fmt;
+use core::ops::Mul;
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
+
+use crate::vector::Vec3;
+use crate::matrix::{Mat3, Mat4};
+
+// Rotation stored as a quaternion w + xi + yj + zk. Constructors always
+// return unit quaternions; only those represent pure rotations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat {
+    w: f64,
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat::new(1.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(w: f64, x: f64, y: f64, z: f64) -> Quat {
+        Quat { w, x, y, z }
+    }
+
+    // Counter-clockwise rotation in radians about the axis, matching
+    // Mat4::rotation. A zero axis gives no rotation.
+    pub fn from_axis_angle(axis: Vec3, angle: f64) -> Quat {
+        let axis = match axis.try_normalize() {
+            Some(axis) => axis,
+            None => return Quat::IDENTITY,
+        };
+        let (sin, cos) = (angle / 2.0).sin_cos();
+        let axis = axis * sin;
+        Quat::new(cos, axis.x(), axis.y(), axis.z())
+    }
+
+    // Rotates about x first, then y, then z (extrinsic axes).
+    pub fn from_euler(x: f64, y: f64, z: f64) -> Quat {
+        Quat::from_axis_angle(Vec3::Z, z)
+            * Quat::from_axis_angle(Vec3::Y, y)
+            * Quat::from_axis_angle(Vec3::X, x)
+    }
+
+    pub const fn w(&self) -> f64 {
+        self.w
+    }
+
+    pub fn vector_part(&self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    pub fn dot(&self, other: Quat) -> f64 {
+        self.w * other.w
+        + self.x * other.x
+        + self.y * other.y
+        + self.z * other.z
+    }
+
+    pub fn length(&self) -> f64 {
+        self.dot(*self).sqrt()
+    }
+
+    pub fn normalized(self) -> Quat {
+        let length = self.length();
+        Quat::new(self.w / length, self.x / length, self.y / length, self.z / length)
+    }
+
+    // For unit quaternions the conjugate is the inverse rotation.
+    pub fn conjugate(self) -> Quat {
+        Quat::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    pub fn rotate(&self, vector: Vec3) -> Vec3 {
+        // Expanded form of q * v * q^-1.
+        let u = self.vector_part();
+        let t = 2.0 * u.cross(vector);
+        vector + self.w * t + u.cross(t)
+    }
+
+    // Spherical interpolation along the shortest arc.
+    pub fn slerp(self, other: Quat, t: f64) -> Quat {
+        let mut other = other;
+        let mut cos_theta = self.dot(other);
+        if cos_theta < 0.0 {
+            other = Quat::new(-other.w, -other.x, -other.y, -other.z);
+            cos_theta = -cos_theta;
+        }
+        // Nearly parallel: fall back to a normalized lerp to avoid dividing
+        // by sin(theta) ~ 0.
+        let (a, b) = if cos_theta > 0.9995 {
+            (1.0 - t, t)
+        }
+        else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+            (((1.0 - t) * theta).sin() / sin_theta, (t * theta).sin() / sin_theta)
+        };
+        Quat::new(
+            a * self.w + b * other.w,
+            a * self.x + b * other.x,
+            a * self.y + b * other.y,
+            a * self.z + b * other.z).normalized()
+    }
+
+    pub fn to_mat3(self) -> Mat3 {
+        let Quat { w, x, y, z } = self;
+        Mat3::new([
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y)],
+            [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x)],
+            [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y)],
+        ])
+    }
+
+    pub fn to_mat4(self) -> Mat4 {
+        Mat4::from_mat3(self.to_mat3())
+    }
+
+    pub fn approx_eq(&self, other: Quat, epsilon: f64) -> bool {
+        (self.w - other.w).abs() <= epsilon
+        && self.vector_part().approx_eq(other.vector_part(), epsilon)
+    }
+}
+
+// Hamilton product: (a * b) rotates by b first, then by a.
+impl Mul<Quat> for Quat {
+    type Output = Quat;
+    fn mul(self, other: Quat) -> Quat {
+        Quat {
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w
+        }
+    }
+}
+
+impl fmt::Display for Quat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {}, {})", self.w, self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
+    use super::*;
+
+    #[test]
+    fn identity() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(vector, Quat::IDENTITY.rotate(vector));
+    }
+
+    #[test]
+    fn rotate() {
+        let quat = Quat::from_axis_angle(Vec3::Z, FRAC_PI_2);
+        assert_vec3_equal!(Vec3::Y, quat.rotate(Vec3::X));
+        assert_approx_eq!(1.0, quat.length(), 0.0001);
+    }
+
+    #[test]
+    fn zero_axis_is_no_rotation() {
+        let quat = Quat::from_axis_angle(Vec3::ZERO, FRAC_PI_2);
+        assert!(quat == Quat::IDENTITY);
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(vector, quat.rotate(vector));
+    }
+
+    #[test]
+    fn matches_mat4_rotation() {
+        let axis = Vec3::new(1.0, -2.0, 0.5);
+        let quat = Quat::from_axis_angle(axis, 1.1);
+        let matrix = Mat4::rotation(axis, 1.1);
+        let vector = Vec3::new(0.3, 0.7, -1.2);
+        assert_vec3_equal!(matrix.transform_direction(vector), quat.rotate(vector));
+        assert!(matrix.approx_eq(&quat.to_mat4(), 0.0001));
+    }
+
+    #[test]
+    fn to_mat3() {
+        let quat = Quat::from_axis_angle(Vec3::new(2.0, 1.0, 0.0), 0.4);
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(quat.rotate(vector), quat.to_mat3() * vector);
+    }
+
+    #[test]
+    fn from_euler() {
+        let quat = Quat::from_euler(FRAC_PI_2, 0.0, FRAC_PI_2);
+        // x rotation takes y to z, z rotation then leaves z in place.
+        assert_vec3_equal!(Vec3::Z, quat.rotate(Vec3::Y));
+        // x rotation leaves x in place, z rotation takes it to y.
+        assert_vec3_equal!(Vec3::Y, quat.rotate(Vec3::X));
+    }
+
+    #[test]
+    fn composition() {
+        let a = Quat::from_axis_angle(Vec3::X, 0.3);
+        let b = Quat::from_axis_angle(Vec3::Y, 0.8);
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(a.rotate(b.rotate(vector)), (a * b).rotate(vector));
+    }
+
+    #[test]
+    fn conjugate_is_inverse() {
+        let quat = Quat::from_axis_angle(Vec3::new(1.0, 1.0, 0.0), 0.9);
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(vector, quat.conjugate().rotate(quat.rotate(vector)));
+    }
+
+    #[test]
+    fn slerp() {
+        let start = Quat::IDENTITY;
+        let end = Quat::from_axis_angle(Vec3::Z, FRAC_PI_2);
+        let halfway = start.slerp(end, 0.5);
+        assert!(halfway.approx_eq(Quat::from_axis_angle(Vec3::Z, FRAC_PI_2 / 2.0), 0.0001));
+        assert!(start.slerp(end, 0.0).approx_eq(start, 0.0001));
+        assert!(start.slerp(end, 1.0).approx_eq(end, 0.0001));
+    }
+
+    #[test]
+    fn slerp_takes_shortest_arc() {
+        let start = Quat::from_axis_angle(Vec3::Z, 0.1);
+        let end = Quat::from_axis_angle(Vec3::Z, 2.0 * PI - 0.1);
+        let halfway = start.slerp(end, 0.5);
+        assert_vec3_equal!(Vec3::X, halfway.rotate(Vec3::X));
+    }
+}
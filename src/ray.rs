@@ -0,0 +1,79 @@
+use core::fmt;
+
+use crate::point::Point3;
+use crate::vector::Vec3;
+
+// A half-line starting at origin. The direction is not necessarily unit
+// length. time is the moment the ray was sent, for motion blur; rays that do
+// not care about time use 0.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Ray {
+    origin: Point3,
+    direction: Vec3,
+    time: f64,
+}
+
+impl Ray {
+    pub const fn new(origin: Point3, direction: Vec3) -> Ray {
+        Ray { origin, direction, time: 0.0 }
+    }
+
+    pub const fn with_time(origin: Point3, direction: Vec3, time: f64) -> Ray {
+        Ray { origin, direction, time }
+    }
+
+    pub const fn origin(&self) -> Point3 {
+        self.origin
+    }
+
+    pub const fn direction(&self) -> Vec3 {
+        self.direction
+    }
+
+    pub const fn time(&self) -> f64 {
+        self.time
+    }
+
+    // Point reached after travelling t direction vectors from the origin.
+    pub fn at(&self, t: f64) -> Point3 {
+        self.origin + self.direction * t
+    }
+}
+
+impl fmt::Display for Ray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} + t{}", self.origin, self.direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use super::*;
+
+    #[test]
+    fn accessors() {
+        let ray = Ray::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_vec3_equal!(Point3::new(1.0, 2.0, 3.0), ray.origin());
+        assert_vec3_equal!(Vec3::new(0.0, 0.0, -1.0), ray.direction());
+        assert_approx_eq!(0.0, ray.time(), 0.0001);
+
+        let ray = Ray::with_time(Point3::ORIGIN, Vec3::X, 0.5);
+        assert_approx_eq!(0.5, ray.time(), 0.0001);
+    }
+
+    #[test]
+    fn at() {
+        let ray = Ray::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(2.0, 0.0, -1.0));
+        assert_vec3_equal!(Point3::new(1.0, 2.0, 3.0), ray.at(0.0));
+        assert_vec3_equal!(Point3::new(3.0, 2.0, 2.0), ray.at(1.0));
+        assert_vec3_equal!(Point3::new(-1.0, 2.0, 4.0), ray.at(-1.0));
+        assert_vec3_equal!(Point3::new(2.0, 2.0, 2.5), ray.at(0.5));
+    }
+
+    #[test]
+    fn display() {
+        let ray = Ray::new(Point3::new(1.0, 2.0, 3.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!("(1, 2, 3) + t(0, 0, -1)", format!("{}", ray));
+    }
+}
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use crate::aabb::{Aabb, BOX_PADDING};
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::vector::{Axis, Vec3};
+
+// A rectangle in a plane perpendicular to one of the axes, at k along that
+// axis. Its outward normal points along the positive axis. Built with xy, xz
+// or yz, named after the axes the rectangle spans.
+#[derive(Clone, Debug)]
+pub struct Rect {
+    axis: Axis,
+    a: Interval,
+    b: Interval,
+    k: f64,
+    material: Arc<dyn Material>,
+}
+
+impl Rect {
+    // At z = k, facing +z.
+    pub fn xy(x0: f64, x1: f64, y0: f64, y1: f64, k: f64, material: Arc<dyn Material>) -> Rect {
+        Rect::new(Axis::Z, Interval::new(x0, x1), Interval::new(y0, y1), k, material)
+    }
+
+    // At y = k, facing +y.
+    pub fn xz(x0: f64, x1: f64, z0: f64, z1: f64, k: f64, material: Arc<dyn Material>) -> Rect {
+        Rect::new(Axis::Y, Interval::new(x0, x1), Interval::new(z0, z1), k, material)
+    }
+
+    // At x = k, facing +x.
+    pub fn yz(y0: f64, y1: f64, z0: f64, z1: f64, k: f64, material: Arc<dyn Material>) -> Rect {
+        Rect::new(Axis::X, Interval::new(y0, y1), Interval::new(z0, z1), k, material)
+    }
+
+    fn new(axis: Axis, a: Interval, b: Interval, k: f64, material: Arc<dyn Material>) -> Rect {
+        // Either order of the end points is fine.
+        let a = Interval::new(a.min.min(a.max), a.min.max(a.max));
+        let b = Interval::new(b.min.min(b.max), b.min.max(b.max));
+        Rect { axis, a, b, k, material }
+    }
+
+    // The axis the rectangle is perpendicular to.
+    pub fn axis(&self) -> Axis {
+        self.axis
+    }
+
+    pub fn area(&self) -> f64 {
+        self.a.size() * self.b.size()
+    }
+
+    // The axes the rectangle spans, in the order of its constructor's bounds.
+    fn plane_axes(&self) -> (Axis, Axis) {
+        match self.axis {
+            Axis::X => (Axis::Y, Axis::Z),
+            Axis::Y => (Axis::X, Axis::Z),
+            Axis::Z => (Axis::X, Axis::Y),
+        }
+    }
+
+    fn point(&self, a: f64, b: f64) -> Point3 {
+        let (a_axis, b_axis) = self.plane_axes();
+        let mut point = Vec3::ZERO;
+        point.set(self.axis, self.k);
+        point.set(a_axis, a);
+        point.set(b_axis, b);
+        Point3::from_vec3(point)
+    }
+}
+
+impl Hittable for Rect {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        // Parallel rays give an infinite or NaN t, which is never in range.
+        let t = (self.k - ray.origin().to_vec3().get(self.axis)) / ray.direction().get(self.axis);
+        if !t_range.surrounds(t) {
+            return None;
+        }
+        let point = ray.at(t).to_vec3();
+        let (a_axis, b_axis) = self.plane_axes();
+        if !self.a.contains(point.get(a_axis)) || !self.b.contains(point.get(b_axis)) {
+            return None;
+        }
+        let mut outward_normal = Vec3::ZERO;
+        outward_normal.set(self.axis, 1.0);
+        Some(HitRecord::new(ray, t, outward_normal, &*self.material))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let aabb = Aabb::new(self.point(self.a.min, self.b.min), self.point(self.a.max, self.b.max));
+        Some(aabb.padded(BOX_PADDING))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use super::*;
+
+    #[test]
+    fn xy_rect() {
+        let rect = Rect::xy(0.0, 2.0, 1.0, -1.0, -3.0, Arc::new(Black));
+        let record = rect.hit(&Ray::new(Point3::new(1.0, 0.5, 0.0), -Vec3::Z), Interval::FORWARD).unwrap();
+        assert_approx_eq!(3.0, record.t);
+        assert_vec3_equal!(Point3::new(1.0, 0.5, -3.0), record.point);
+        assert!(record.front_face);
+        assert_vec3_equal!(Vec3::Z, record.normal);
+        assert!(rect.hit(&Ray::new(Point3::new(3.0, 0.5, 0.0), -Vec3::Z), Interval::FORWARD).is_none());
+        assert!(rect.hit(&Ray::new(Point3::new(1.0, 0.5, 0.0), Vec3::Z), Interval::FORWARD).is_none());
+        assert_approx_eq!(4.0, rect.area());
+    }
+
+    #[test]
+    fn xz_and_yz_rects() {
+        let ceiling = Rect::xz(-1.0, 1.0, -1.0, 1.0, 2.0, Arc::new(Black));
+        let record = ceiling.hit(&Ray::new(Point3::new(0.5, 4.0, -0.5), Vec3::new(0.0, -2.0, 0.0)), Interval::FORWARD).unwrap();
+        assert_approx_eq!(1.0, record.t);
+        assert!(record.front_face);
+        assert_vec3_equal!(Vec3::Y, record.normal);
+        assert!(ceiling.hit(&Ray::new(Point3::new(0.5, 0.0, -1.5), Vec3::Y), Interval::FORWARD).is_none());
+
+        let wall = Rect::yz(0.0, 1.0, 0.0, 1.0, 5.0, Arc::new(Black));
+        let record = wall.hit(&Ray::new(Point3::new(0.0, 0.5, 0.5), Vec3::X), Interval::FORWARD).unwrap();
+        assert_approx_eq!(5.0, record.t);
+        assert_vec3_equal!(-Vec3::X, record.normal);
+    }
+
+    #[test]
+    fn parallel_rays_miss() {
+        let rect = Rect::xy(-1.0, 1.0, -1.0, 1.0, 0.0, Arc::new(Black));
+        assert!(rect.hit(&Ray::new(Point3::new(0.0, 0.0, 1.0), Vec3::X), Interval::FORWARD).is_none());
+        assert!(rect.hit(&Ray::new(Point3::ORIGIN, Vec3::X), Interval::UNIVERSE).is_none());
+    }
+
+    #[test]
+    fn bounding_box_is_padded() {
+        let rect = Rect::xz(0.0, 2.0, -1.0, 1.0, 3.0, Arc::new(Black));
+        let aabb = rect.bounding_box().unwrap();
+        assert_vec3_equal!(Point3::new(0.0, 3.0, -1.0), aabb.min(), 0.001);
+        assert_vec3_equal!(Point3::new(2.0, 3.0, 1.0), aabb.max(), 0.001);
+        assert!(aabb.axis(Axis::Y).size() > 0.0);
+        assert!(aabb.hit(&Ray::new(Point3::new(1.0, 5.0, 0.0), -Vec3::Y), Interval::FORWARD));
+    }
+}
@@ -0,0 +1,127 @@
+use rand::{Rng, RngCore};
+
+use crate::background::Background;
+use crate::color::Color;
+use crate::hittable::Hittable;
+use crate::interval::Interval;
+use crate::ray::Ray;
+
+// How long paths are followed. Every path ends after max_depth bounces.
+// With roulette_after n, paths that have bounced n times are also randomly
+// cut short instead of always running to max_depth.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PathLimits {
+    pub max_depth: u32,
+    pub roulette_after: Option<u32>,
+}
+
+// The light arriving along the ray. Paths that run out of bounces carry no
+// light; rays that escape see the background.
+pub fn ray_color(ray: &Ray, world: &dyn Hittable, background: &dyn Background, limits: PathLimits,
+                 rng: &mut dyn RngCore) -> Color {
+    trace(ray, world, background, limits.max_depth, limits, rng)
+}
+
+// Follows the ray through up to depth more bounces.
+fn trace(ray: &Ray, world: &dyn Hittable, background: &dyn Background, depth: u32, limits: PathLimits,
+         rng: &mut dyn RngCore) -> Color {
+    if depth == 0 {
+        return Color::BLACK;
+    }
+    if let Some(record) = world.hit(ray, Interval::FORWARD) {
+        let emitted = record.material.emitted(ray, &record);
+        let (mut attenuation, scattered) = match record.material.scatter(ray, &record, rng) {
+            Some(scatter) => scatter,
+            None => return emitted
+        };
+        // Russian roulette: continue with a probability that follows how
+        // much light the bounce keeps, and scale up the survivors so the
+        // expected result is unchanged. Black bounces never survive, so
+        // there is no division by zero.
+        let bounces = limits.max_depth - depth;
+        if limits.roulette_after.is_some_and(|after| bounces >= after) {
+            let survival = attenuation.r().max(attenuation.g()).max(attenuation.b()).min(1.0);
+            if rng.gen::<f64>() >= survival {
+                return emitted;
+            }
+            attenuation /= survival;
+        }
+        return emitted + attenuation * trace(&scattered, world, background, depth - 1, limits, rng);
+    }
+    background.color(ray)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use assert_approx_eq::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::background::{SolidColor, VerticalGradient};
+    use crate::hittable_list::HittableList;
+    use crate::material::Lambertian;
+    use crate::plane::Plane;
+    use crate::point::Point3;
+    use crate::vector::Vec3;
+    use super::*;
+
+    const LIMITS: PathLimits = PathLimits { max_depth: 10, roulette_after: None };
+
+    // A floor of the given albedo under a white sky. Light bounces off the
+    // floor once and escapes, so without roulette every path gives albedo.
+    fn floor(albedo: f64) -> HittableList {
+        let mut world = HittableList::new();
+        world.add(Plane::new(Point3::ORIGIN, Vec3::Y, Arc::new(Lambertian::new(Color::new(albedo, albedo, albedo)))));
+        world
+    }
+
+    fn down() -> Ray {
+        Ray::new(Point3::new(0.0, 1.0, 0.0), -Vec3::Y)
+    }
+
+    #[test]
+    fn out_of_bounces_is_black() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let limits = PathLimits { max_depth: 0, ..LIMITS };
+        let color = ray_color(&down(), &floor(0.5), &SolidColor(Color::WHITE), limits, &mut rng);
+        assert!(color == Color::BLACK);
+    }
+
+    #[test]
+    fn escaping_rays_see_the_background() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let sky = VerticalGradient::sky();
+        let ray = Ray::new(Point3::ORIGIN, Vec3::new(0.0, 1.0, -1.0));
+        assert!(ray_color(&ray, &HittableList::new(), &sky, LIMITS, &mut rng) == sky.color(&ray));
+    }
+
+    #[test]
+    fn roulette_keeps_the_mean() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let world = floor(0.5);
+        let white = SolidColor(Color::WHITE);
+        let plain = ray_color(&down(), &world, &white, LIMITS, &mut rng);
+        assert_approx_eq!(0.5, plain.g());
+
+        // Survivors come back as 1 and the rest as 0.
+        let roulette = PathLimits { roulette_after: Some(0), ..LIMITS };
+        let samples = 10000;
+        let mut sum = Color::BLACK;
+        for _ in 0..samples {
+            let color = ray_color(&down(), &world, &white, roulette, &mut rng);
+            assert!(color == Color::BLACK || color == Color::WHITE);
+            sum += color;
+        }
+        assert_approx_eq!(0.5, sum.g() / samples as f64, 0.02);
+    }
+
+    #[test]
+    fn roulette_stops_black_paths() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let roulette = PathLimits { roulette_after: Some(0), ..LIMITS };
+        for _ in 0..100 {
+            let color = ray_color(&down(), &floor(0.0), &SolidColor(Color::WHITE), roulette, &mut rng);
+            assert!(color == Color::BLACK);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use core::fmt;
+
+// Floating point types that Vec3 can be built from (f32 and f64). Adds
+// constants on top of num_traits::Float so that Vec3's own constants and
+// const fns can stay generic.
+pub trait Float: num_traits::Float + fmt::Display + Default {
+    const ZERO: Self;
+    const ONE: Self;
+
+    // Converts a literal; for f32 this rounds to the nearest value.
+    fn from_f64(value: f64) -> Self;
+}
+
+impl Float for f32 {
+    const ZERO: f32 = 0.0;
+    const ONE: f32 = 1.0;
+
+    fn from_f64(value: f64) -> f32 {
+        value as f32
+    }
+}
+
+impl Float for f64 {
+    const ZERO: f64 = 0.0;
+    const ONE: f64 = 1.0;
+
+    fn from_f64(value: f64) -> f64 {
+        value
+    }
+}
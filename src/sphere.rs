@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::math;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::vector::Vec3;
+
+// A negative radius gives the same surface with the normals pointing into
+// the sphere, which makes a hollow shell when nested inside a normal one.
+#[derive(Clone, Debug)]
+pub struct Sphere {
+    center: Point3,
+    radius: f64,
+    material: Arc<dyn Material>,
+}
+
+impl Sphere {
+    pub fn new(center: Point3, radius: f64, material: Arc<dyn Material>) -> Sphere {
+        Sphere { center, radius, material }
+    }
+
+    pub const fn center(&self) -> Point3 {
+        self.center
+    }
+
+    pub const fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+impl Hittable for Sphere {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        hit_sphere(self.center, self.radius, &*self.material, ray, t_range)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(sphere_box(self.center, self.radius))
+    }
+}
+
+pub(crate) fn sphere_box(center: Point3, radius: f64) -> Aabb {
+    let extent = Vec3::ONE * radius.abs();
+    Aabb::new(center - extent, center + extent)
+}
+
+// Shared with MovingSphere, which passes its center at the ray's time.
+pub(crate) fn hit_sphere<'a>(center: Point3, radius: f64, material: &'a dyn Material,
+                             ray: &Ray, t_range: Interval) -> Option<HitRecord<'a>> {
+    // |origin + t direction - center|^2 = radius^2
+    let oc = ray.origin() - center;
+    let a = ray.direction().length_squared();
+    let b = 2.0 * oc.dot(ray.direction());
+    let c = oc.length_squared() - radius * radius;
+    let (near, far) = math::solve_quadratic(a, b, c)?;
+
+    let t = if t_range.surrounds(near) {
+        near
+    }
+    else if t_range.surrounds(far) {
+        far
+    }
+    else {
+        return None;
+    };
+    let outward_normal = (ray.at(t) - center) / radius;
+    Some(HitRecord::new(ray, t, outward_normal, material))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use crate::vector::Vec3;
+    use super::*;
+
+    fn new_sphere(center: Point3, radius: f64) -> Sphere {
+        Sphere::new(center, radius, Arc::new(Black))
+    }
+
+    #[test]
+    fn hit_from_outside() {
+        let sphere = new_sphere(Point3::new(0.0, 0.0, -5.0), 1.0);
+        let ray = Ray::new(Point3::ORIGIN, Vec3::new(0.0, 0.0, -1.0));
+        let record = sphere.hit(&ray, Interval::FORWARD).unwrap();
+        assert_approx_eq!(4.0, record.t, 0.0001);
+        assert_vec3_equal!(Point3::new(0.0, 0.0, -4.0), record.point);
+        assert_vec3_equal!(Vec3::Z, record.normal);
+        assert!(record.front_face);
+    }
+
+    #[test]
+    fn miss() {
+        let sphere = new_sphere(Point3::new(0.0, 0.0, -5.0), 1.0);
+        assert!(sphere.hit(&Ray::new(Point3::ORIGIN, Vec3::new(0.0, 1.0, -1.0)), Interval::FORWARD).is_none());
+        // Behind the ray origin.
+        assert!(sphere.hit(&Ray::new(Point3::ORIGIN, Vec3::Z), Interval::FORWARD).is_none());
+        // Beyond the end of the range.
+        assert!(sphere.hit(&Ray::new(Point3::ORIGIN, -Vec3::Z), Interval::new(0.001, 3.0)).is_none());
+    }
+
+    #[test]
+    fn grazing_rays() {
+        let sphere = new_sphere(Point3::ORIGIN, 1.0);
+        let tangent = Ray::new(Point3::new(-5.0, 1.0, 0.0), Vec3::X);
+        let record = sphere.hit(&tangent, Interval::FORWARD).unwrap();
+        assert_approx_eq!(5.0, record.t, 0.0001);
+        // Exactly tangent, so either side can count as the front.
+        assert_approx_eq!(1.0, record.normal.dot(Vec3::Y).abs(), 0.0001);
+
+        let just_outside = Ray::new(Point3::new(-5.0, 1.0 + 1e-9, 0.0), Vec3::X);
+        assert!(sphere.hit(&just_outside, Interval::FORWARD).is_none());
+
+        // A shallow ray from far away must still land on the surface.
+        let sphere = new_sphere(Point3::new(0.0, 0.0, -1000.0), 10.0);
+        let ray = Ray::new(Point3::ORIGIN, Vec3::new(0.0, 9.99, -1000.0));
+        let record = sphere.hit(&ray, Interval::FORWARD).unwrap();
+        assert_approx_eq!(10.0, record.point.distance(sphere.center()), 1e-9);
+    }
+
+    #[test]
+    fn ray_starting_inside() {
+        let sphere = new_sphere(Point3::ORIGIN, 2.0);
+        let ray = Ray::new(Point3::ORIGIN, Vec3::X);
+        let record = sphere.hit(&ray, Interval::FORWARD).unwrap();
+        assert_approx_eq!(2.0, record.t, 0.0001);
+        assert!(!record.front_face);
+        assert_vec3_equal!(-Vec3::X, record.normal);
+    }
+
+    #[test]
+    fn negative_radius_inverts_normals() {
+        let sphere = new_sphere(Point3::new(0.0, 0.0, -5.0), -1.0);
+        let ray = Ray::new(Point3::ORIGIN, Vec3::new(0.0, 0.0, -1.0));
+        let record = sphere.hit(&ray, Interval::FORWARD).unwrap();
+        assert_approx_eq!(4.0, record.t, 0.0001);
+        assert!(!record.front_face);
+        assert_vec3_equal!(Vec3::Z, record.normal);
+    }
+
+    #[test]
+    fn scaled_direction() {
+        let sphere = new_sphere(Point3::new(0.0, 0.0, -5.0), 1.0);
+        let ray = Ray::new(Point3::ORIGIN, Vec3::new(0.0, 0.0, -2.0));
+        let record = sphere.hit(&ray, Interval::FORWARD).unwrap();
+        assert_approx_eq!(2.0, record.t, 0.0001);
+        assert_vec3_equal!(Point3::new(0.0, 0.0, -4.0), record.point);
+    }
+
+    #[test]
+    fn bounding_box() {
+        for &radius in &[2.0, -2.0] {
+            let aabb = new_sphere(Point3::new(1.0, 0.0, -5.0), radius).bounding_box().unwrap();
+            assert_vec3_equal!(Point3::new(-1.0, -2.0, -7.0), aabb.min());
+            assert_vec3_equal!(Point3::new(3.0, 2.0, -3.0), aabb.max());
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::material::Material;
+use crate::mesh::{Shading, TriangleMesh};
+use crate::point::Point3;
+
+// STL import, binary or ASCII. STL stores every triangle with its own three
+// corners, so corners at the same position are merged into one vertex,
+// which smooth shading needs. The per-facet normals in the file are
+// ignored: many exporters write zeros, and the winding gives the same
+// normal.
+
+#[derive(Debug)]
+pub enum StlError {
+    Io(io::Error),
+    // A binary file whose size doesn't match its triangle count.
+    Truncated,
+    // Line number in an ASCII file, counting from 1, and what is wrong on it.
+    Syntax(usize, &'static str),
+}
+
+impl fmt::Display for StlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StlError::Io(error) => write!(f, "{}", error),
+            StlError::Truncated => write!(f, "binary STL file is truncated"),
+            StlError::Syntax(line, reason) => write!(f, "line {}: {}", line, reason),
+        }
+    }
+}
+
+impl Error for StlError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StlError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for StlError {
+    fn from(error: io::Error) -> StlError {
+        StlError::Io(error)
+    }
+}
+
+pub fn load_stl<P: AsRef<Path>>(path: P, shading: Shading, material: Arc<dyn Material>) -> Result<TriangleMesh, StlError> {
+    parse_stl(&fs::read(path)?, shading, material)
+}
+
+pub fn parse_stl(data: &[u8], shading: Shading, material: Arc<dyn Material>) -> Result<TriangleMesh, StlError> {
+    // Binary files may also start with "solid", so they are told apart by
+    // their size, or failing that by not being text.
+    let text = std::str::from_utf8(data).ok().filter(|text| !text.contains('\0'));
+    let triangles = match text {
+        Some(text) if text.starts_with("solid") && !is_binary(data) => parse_ascii(text)?,
+        _ => parse_binary(data)?,
+    };
+
+    let mut indices = HashMap::new();
+    let mut positions = Vec::new();
+    let faces = triangles.iter()
+        .map(|triangle| {
+            let mut face = [0; 3];
+            for (index, &corner) in face.iter_mut().zip(triangle) {
+                // Adding zero turns -0 into 0, which has other bits.
+                let key = corner.map(|x| (x + 0.0).to_bits());
+                *index = *indices.entry(key).or_insert_with(|| {
+                    positions.push(Point3::new(corner[0], corner[1], corner[2]));
+                    positions.len() as u32 - 1
+                });
+            }
+            face
+        })
+        .collect();
+    Ok(TriangleMesh::new(positions, faces, material).shaded(shading))
+}
+
+type Corners = [[f64; 3]; 3];
+
+// An 80 byte header, the triangle count, then 50 bytes per triangle: the
+// normal, the three corners and two bytes of attributes.
+fn is_binary(data: &[u8]) -> bool {
+    data.get(80..84)
+        .map(|count| u32::from_le_bytes(count.try_into().unwrap()) as usize)
+        .is_some_and(|count| data.len() == 84 + 50 * count)
+}
+
+fn parse_binary(data: &[u8]) -> Result<Vec<Corners>, StlError> {
+    if !is_binary(data) {
+        return Err(StlError::Truncated);
+    }
+    let float = |bytes: &[u8], i: usize| f32::from_le_bytes(bytes[4 * i..4 * i + 4].try_into().unwrap()) as f64;
+    Ok(data[84..].chunks_exact(50)
+        .map(|triangle| {
+            let corner = |i: usize| [float(triangle, 3 * i + 3), float(triangle, 3 * i + 4), float(triangle, 3 * i + 5)];
+            [corner(0), corner(1), corner(2)]
+        })
+        .collect())
+}
+
+fn parse_ascii(text: &str) -> Result<Vec<Corners>, StlError> {
+    let mut triangles = Vec::new();
+    let mut corners = Vec::with_capacity(3);
+    for (number, line) in text.lines().enumerate() {
+        let syntax = |reason| StlError::Syntax(number + 1, reason);
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("facet") => corners.clear(),
+            Some("vertex") => {
+                let coordinates = words.map(|word| word.parse::<f64>().ok()).collect::<Option<Vec<_>>>();
+                match coordinates.as_deref() {
+                    Some(&[x, y, z]) if corners.len() < 3 => corners.push([x, y, z]),
+                    Some(&[_, _, _]) => return Err(syntax("facet with more than three vertices")),
+                    _ => return Err(syntax("invalid vertex")),
+                }
+            }
+            Some("endfacet") => {
+                if corners.len() != 3 {
+                    return Err(syntax("facet without three vertices"));
+                }
+                triangles.push([corners[0], corners[1], corners[2]]);
+                corners.clear();
+            }
+            // solid, outer loop, endloop and endsolid.
+            _ => {}
+        }
+    }
+    Ok(triangles)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::material::Black;
+    use crate::vector::Vec3;
+    use super::*;
+
+    const TETRAHEDRON: &str = "\
+solid tetrahedron
+  facet normal 0 0 -1
+    outer loop
+      vertex 0 0 0
+      vertex 0 1 0
+      vertex 1 0 0
+    endloop
+  endfacet
+  facet normal 0 0 0
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0 0 1
+    endloop
+  endfacet
+  facet normal 0 0 0
+    outer loop
+      vertex -0 0 0
+      vertex 0 0 1
+      vertex 0 1 0
+    endloop
+  endfacet
+  facet normal 0 0 0
+    outer loop
+      vertex 1 0 0
+      vertex 0 1 0
+      vertex 0 0 1
+    endloop
+  endfacet
+endsolid tetrahedron
+";
+
+    fn binary(triangles: &[Corners]) -> Vec<u8> {
+        // Binary files starting with "solid" are common.
+        let mut data = b"solid but binary".to_vec();
+        data.resize(80, 0);
+        data.extend_from_slice(&(triangles.len() as u32).to_le_bytes());
+        for triangle in triangles {
+            data.extend_from_slice(&[0; 12]);
+            for value in triangle.iter().flatten() {
+                data.extend_from_slice(&(*value as f32).to_le_bytes());
+            }
+            data.extend_from_slice(&[0; 2]);
+        }
+        data
+    }
+
+    #[test]
+    fn ascii_corners_are_merged() {
+        let mesh = parse_stl(TETRAHEDRON.as_bytes(), Shading::Flat, Arc::new(Black)).unwrap();
+        assert_eq!(4, mesh.len());
+        assert_eq!(4, mesh.positions().len());
+        assert_eq!([0, 1, 2], mesh.faces()[0]);
+        assert_eq!([0, 2, 3], mesh.faces()[1]);
+        assert!(mesh.normals().is_none());
+    }
+
+    #[test]
+    fn binary_with_smooth_normals() {
+        let triangles = parse_ascii(TETRAHEDRON).unwrap();
+        let mesh = parse_stl(&binary(&triangles), Shading::Smooth, Arc::new(Black)).unwrap();
+        assert_eq!(4, mesh.len());
+        assert_eq!(4, mesh.positions().len());
+        assert_vec3_equal!(Point3::new(0.0, 0.0, 1.0), mesh.positions()[3]);
+        // The corner at the origin is surrounded by three faces facing -x,
+        // -y and -z, with the same areas.
+        assert_vec3_equal!(-Vec3::ONE.unit_vector(), mesh.normals().unwrap()[0]);
+    }
+
+    #[test]
+    fn errors() {
+        let parse = |data: &[u8]| parse_stl(data, Shading::Flat, Arc::new(Black)).unwrap_err();
+        assert!(matches!(parse(&binary(&[[[0.0; 3]; 3]])[..120]), StlError::Truncated));
+        assert!(matches!(parse(b"solid x\nfacet\nouter loop\nvertex 0 0\n"), StlError::Syntax(4, _)));
+        assert!(matches!(parse(b"solid x\nfacet\nouter loop\nvertex 0 0 0\nendloop\nendfacet\n"), StlError::Syntax(6, _)));
+    }
+}
@@ -0,0 +1,36 @@
+// Assertions shared by the unit tests of every module. Anything with
+// x(), y() and z() accessors can be compared, e.g. Vec3 and Point3.
+//
+// Components of both sides are put in one array, which forces them to the
+// same scalar type, and the difference goes through num_traits::Float::abs
+// instead of a method call. Together these let vectors built from bare
+// literals or constants (`Vec3::new(1.0, 2.0, 3.0)`, `Vec3::X`) infer their
+// scalar type from the other side or fall back to f64.
+macro_rules! assert_vec3_equal {
+    ($expected:expr, $actual:expr) => {
+        assert_vec3_equal!($expected, $actual, 0.0001);
+    };
+    ($expected:expr, $actual:expr, $tolerance:expr) => {
+        let (expected, actual, tolerance) = ($expected, $actual, $tolerance);
+        let components = [
+            [expected.x(), actual.x()],
+            [expected.y(), actual.y()],
+            [expected.z(), actual.z()],
+        ];
+        for (axis, [e, a]) in ["x", "y", "z"].iter().zip(components.iter()) {
+            let difference = num_traits::Float::abs(*e - *a);
+            assert!(difference <= tolerance,
+                "assertion failed: `(left !== right)` on {} (left: `{}`, right: `{}`, expect diff: `{}`, real diff: `{}`)",
+                axis, expected, actual, tolerance, difference);
+        }
+    }
+}
+
+// Materials are trait objects without PartialEq, so they are compared by
+// their Debug output. Either side may be a material or an Arc holding one.
+macro_rules! assert_material_equal {
+    ($expected:expr, $actual:expr) => {
+        let (expected, actual) = (format!("{:?}", $expected), format!("{:?}", $actual));
+        assert!(expected == actual, "assertion failed: `(left == right)` (left: `{}`, right: `{}`)", expected, actual);
+    };
+}
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use crate::aabb::{Aabb, BOX_PADDING};
+use crate::hittable::{HitRecord, Hittable};
+use crate::interval::Interval;
+use crate::material::Material;
+use crate::math;
+use crate::point::Point3;
+use crate::ray::Ray;
+use crate::vector::{Vec2, Vec3};
+
+// A triangle with corners a, b and c. Counterclockwise seen from the front,
+// as usual for meshes. Without vertex normals it is shaded flat; with them
+// the normal is blended across the face, which smooths out meshes.
+#[derive(Clone, Debug)]
+pub struct Triangle {
+    vertices: [Point3; 3],
+    normals: Option<[Vec3; 3]>,
+    uvs: Option<[Vec2; 3]>,
+    material: Arc<dyn Material>,
+}
+
+impl Triangle {
+    pub fn new(a: Point3, b: Point3, c: Point3, material: Arc<dyn Material>) -> Triangle {
+        Triangle { vertices: [a, b, c], normals: None, uvs: None, material }
+    }
+
+    // Unit normals at a, b and c.
+    pub fn with_normals(self, normals: [Vec3; 3]) -> Triangle {
+        Triangle { normals: Some(normals), ..self }
+    }
+
+    // Texture coordinates at a, b and c.
+    pub fn with_uvs(self, uvs: [Vec2; 3]) -> Triangle {
+        Triangle { uvs: Some(uvs), ..self }
+    }
+
+    pub const fn vertices(&self) -> [Point3; 3] {
+        self.vertices
+    }
+
+    pub fn area(&self) -> f64 {
+        let [a, b, c] = self.vertices;
+        0.5 * (b - a).cross(c - a).length()
+    }
+}
+
+impl Hittable for Triangle {
+    fn hit(&self, ray: &Ray, t_range: Interval) -> Option<HitRecord<'_>> {
+        let [a, b, c] = self.vertices;
+        let (t, weights) = intersect(a, b, c, ray, t_range)?;
+        Some(shade(ray, t, weights, [a, b, c], self.normals, self.uvs, &*self.material))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let [a, b, c] = self.vertices;
+        Some(triangle_box(a, b, c))
+    }
+}
+
+// Möller-Trumbore: solves origin + t direction = a + u (b - a) + v (c - a)
+// directly, without first intersecting the triangle's plane. Returns t and
+// the barycentric weights of a, b and c.
+pub(crate) fn intersect(a: Point3, b: Point3, c: Point3, ray: &Ray, t_range: Interval) -> Option<(f64, Vec3)> {
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let p = ray.direction().cross(edge2);
+    let determinant = edge1.dot(p);
+    // Rays parallel to the plane, and degenerate triangles.
+    if determinant.abs() < 1e-12 {
+        return None;
+    }
+    let inverse = 1.0 / determinant;
+    let s = ray.origin() - a;
+    let u = s.dot(p) * inverse;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge1);
+    let v = ray.direction().dot(q) * inverse;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = edge2.dot(q) * inverse;
+    if !t_range.surrounds(t) {
+        return None;
+    }
+    Some((t, Vec3::new(1.0 - u - v, u, v)))
+}
+
+// The hit record for a hit at t with the given barycentric weights. Which
+// side was hit is decided by the geometric normal, so that blended vertex
+// normals can't make a ray seem to hit the back of a face it hit from the
+// front.
+pub(crate) fn shade<'a>(ray: &Ray, t: f64, weights: Vec3, [a, b, c]: [Point3; 3], normals: Option<[Vec3; 3]>,
+                        uvs: Option<[Vec2; 3]>, material: &'a dyn Material) -> HitRecord<'a> {
+    let geometric_normal = (b - a).cross(c - a).unit_vector();
+    let mut record = HitRecord::new(ray, t, geometric_normal, material);
+    if let Some([na, nb, nc]) = normals {
+        let normal = math::interpolate_barycentric(weights, na, nb, nc).unit_vector();
+        record.normal = if record.front_face { normal } else { -normal };
+    }
+    if let Some([ua, ub, uc]) = uvs {
+        record = record.with_uv(math::interpolate_barycentric(weights, ua, ub, uc));
+    }
+    record
+}
+
+// Padded, since triangles in an axis plane have flat boxes.
+pub(crate) fn triangle_box(a: Point3, b: Point3, c: Point3) -> Aabb {
+    Aabb::surrounding_box(Aabb::new(a, b), Aabb::new(c, c)).padded(BOX_PADDING)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use crate::material::Black;
+    use super::*;
+
+    // In the plane z = -2, facing +z.
+    fn triangle() -> Triangle {
+        Triangle::new(Point3::new(0.0, 0.0, -2.0), Point3::new(1.0, 0.0, -2.0), Point3::new(0.0, 1.0, -2.0),
+                      Arc::new(Black))
+    }
+
+    fn ray_to(x: f64, y: f64) -> Ray {
+        Ray::new(Point3::new(x, y, 0.0), -Vec3::Z)
+    }
+
+    #[test]
+    fn hit_inside() {
+        let triangle = triangle();
+        let record = triangle.hit(&ray_to(0.25, 0.25), Interval::FORWARD).unwrap();
+        assert_approx_eq!(2.0, record.t);
+        assert_vec3_equal!(Point3::new(0.25, 0.25, -2.0), record.point);
+        assert!(record.front_face);
+        assert_vec3_equal!(Vec3::Z, record.normal);
+        assert_approx_eq!(0.5, triangle.area());
+    }
+
+    #[test]
+    fn misses() {
+        let triangle = triangle();
+        assert!(triangle.hit(&ray_to(0.6, 0.6), Interval::FORWARD).is_none());
+        assert!(triangle.hit(&ray_to(-0.1, 0.5), Interval::FORWARD).is_none());
+        assert!(triangle.hit(&ray_to(0.5, -0.1), Interval::FORWARD).is_none());
+        assert!(triangle.hit(&ray_to(0.25, 0.25), Interval::new(0.001, 1.0)).is_none());
+        assert!(triangle.hit(&Ray::new(Point3::new(0.25, 0.25, 0.0), Vec3::Z), Interval::FORWARD).is_none());
+        assert!(triangle.hit(&Ray::new(Point3::new(-1.0, 0.25, -2.0), Vec3::X), Interval::FORWARD).is_none());
+    }
+
+    #[test]
+    fn back_face() {
+        let ray = Ray::new(Point3::new(0.25, 0.25, -4.0), Vec3::Z);
+        let triangle = triangle();
+        let record = triangle.hit(&ray, Interval::FORWARD).unwrap();
+        assert!(!record.front_face);
+        assert_vec3_equal!(-Vec3::Z, record.normal);
+    }
+
+    #[test]
+    fn interpolated_normals_and_uvs() {
+        let tilted = Vec3::new(1.0, 0.0, 1.0).unit_vector();
+        let triangle = triangle()
+            .with_normals([Vec3::Z, tilted, Vec3::Z])
+            .with_uvs([Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0)]);
+        let record = triangle.hit(&ray_to(0.5, 0.25), Interval::FORWARD).unwrap();
+        let expected = (Vec3::Z * 0.25 + tilted * 0.5 + Vec3::Z * 0.25).unit_vector();
+        assert_vec3_equal!(expected, record.normal);
+        assert_approx_eq!(0.5, record.uv.x());
+        assert_approx_eq!(0.25, record.uv.y());
+
+        let record = triangle.hit(&Ray::new(Point3::new(0.5, 0.25, -4.0), Vec3::Z), Interval::FORWARD).unwrap();
+        assert!(!record.front_face);
+        assert_vec3_equal!(-expected, record.normal);
+    }
+
+    #[test]
+    fn flat_shading_has_no_uvs() {
+        let triangle = triangle();
+        assert!(triangle.hit(&ray_to(0.5, 0.25), Interval::FORWARD).unwrap().uv == Vec2::ZERO);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let aabb = triangle().bounding_box().unwrap();
+        assert_vec3_equal!(Point3::new(0.0, 0.0, -2.0), aabb.min(), 0.001);
+        assert_vec3_equal!(Point3::new(1.0, 1.0, -2.0), aabb.max(), 0.001);
+        assert!(aabb.hit(&ray_to(0.25, 0.25), Interval::FORWARD));
+    }
+}
@@ -1,25 +1,113 @@
 use core::fmt;
-use std::ops::{Add, Sub, Neg, Mul, Div};
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Sub, Neg, Mul, Div, Rem, Index, IndexMut};
+use core::ops::{AddAssign, SubAssign, MulAssign, DivAssign, RemAssign};
+use core::iter::{Sum, Product};
+use core::str::FromStr;
+use rand::Rng;
+use rand::distributions::{Distribution, Standard};
+use rand::distributions::uniform::SampleUniform;
+#[cfg(not(feature = "std"))]
+use num_traits::Float as _;
 
-#[derive(Clone, Copy)]
-pub struct Vec3 {
-    x: f64,
-    y: f64,
-    z: f64,
+use crate::scalar::Float;
+use crate::math;
+
+#[cfg(feature = "serde")]
+mod serde_support;
+#[cfg(feature = "approx")]
+mod approx_support;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_support;
+#[cfg(feature = "glam")]
+mod glam_support;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_support;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
 }
 
-impl Vec3 {
-    pub fn new(x: f64, y: f64, z: f64) -> Vec3 {
+impl Axis {
+    pub const ALL: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+}
+
+// Generic over the component type so the renderer can run in f32 or f64.
+// Plain `Vec3` means `Vec3<f64>`. Laid out like [T; 3].
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[repr(C)]
+pub struct Vec3<T = f64> {
+    x: T,
+    y: T,
+    z: T,
+}
+
+impl<T: Float> Vec3<T> {
+    pub const ZERO: Vec3<T> = Vec3::new(T::ZERO, T::ZERO, T::ZERO);
+    pub const ONE: Vec3<T> = Vec3::new(T::ONE, T::ONE, T::ONE);
+    pub const X: Vec3<T> = Vec3::new(T::ONE, T::ZERO, T::ZERO);
+    pub const Y: Vec3<T> = Vec3::new(T::ZERO, T::ONE, T::ZERO);
+    pub const Z: Vec3<T> = Vec3::new(T::ZERO, T::ZERO, T::ONE);
+
+    pub const fn new(x: T, y: T, z: T) -> Vec3<T> {
         Vec3 { x, y, z }
     }
 
-    pub fn dot(&self, other: Vec3) -> f64 {
+    pub const fn x(&self) -> T {
+        self.x
+    }
+
+    pub const fn y(&self) -> T {
+        self.y
+    }
+
+    pub const fn z(&self) -> T {
+        self.z
+    }
+
+    // Swizzles reorder the components.
+    pub fn xzy(&self) -> Vec3<T> {
+        Vec3::new(self.x, self.z, self.y)
+    }
+
+    pub fn yxz(&self) -> Vec3<T> {
+        Vec3::new(self.y, self.x, self.z)
+    }
+
+    pub fn yzx(&self) -> Vec3<T> {
+        Vec3::new(self.y, self.z, self.x)
+    }
+
+    pub fn zxy(&self) -> Vec3<T> {
+        Vec3::new(self.z, self.x, self.y)
+    }
+
+    pub fn zyx(&self) -> Vec3<T> {
+        Vec3::new(self.z, self.y, self.x)
+    }
+
+    pub fn with_x(self, x: T) -> Vec3<T> {
+        Vec3 { x, ..self }
+    }
+
+    pub fn with_y(self, y: T) -> Vec3<T> {
+        Vec3 { y, ..self }
+    }
+
+    pub fn with_z(self, z: T) -> Vec3<T> {
+        Vec3 { z, ..self }
+    }
+
+    pub fn dot(&self, other: Vec3<T>) -> T {
         self.x * other.x 
         + self.y * other.y 
         + self.z * other.z
     }
 
-    pub fn cross(&self, other: Vec3) -> Vec3 {
+    pub fn cross(&self, other: Vec3<T>) -> Vec3<T> {
         Vec3{
             x: self.y * other.z - self.z * other.y,
             y: self.z * other.x - self.x * other.z,
@@ -27,35 +115,479 @@ impl Vec3 {
         }
     }
 
-    pub fn length_squared(&self) -> f64 {
+    // a · (b × c): the signed volume of the parallelepiped spanned by a, b
+    // and c. Positive when they form a right-handed system.
+    pub fn scalar_triple(a: Vec3<T>, b: Vec3<T>, c: Vec3<T>) -> T {
+        a.dot(b.cross(c))
+    }
+
+    // a × (b × c), which equals b (a · c) - c (a · b).
+    pub fn vector_triple(a: Vec3<T>, b: Vec3<T>, c: Vec3<T>) -> Vec3<T> {
+        a.cross(b.cross(c))
+    }
+
+    pub fn approx_eq(&self, other: Vec3<T>, epsilon: T) -> bool {
+        (self.x - other.x).abs() <= epsilon
+        && (self.y - other.y).abs() <= epsilon
+        && (self.z - other.z).abs() <= epsilon
+    }
+
+    // Angle in radians. Rounding can push the cosine slightly outside
+    // [-1, 1], so it is clamped before acos. Zero-length vectors give NaN.
+    pub fn angle_between(&self, other: Vec3<T>) -> T {
+        let cos_theta = self.dot(other) / (self.length() * other.length());
+        cos_theta.max(-T::ONE).min(T::ONE).acos()
+    }
+
+    // Spherical coordinates follow the ISO physics convention: theta is the
+    // polar angle from +z in [0, pi], phi the azimuth from +x towards +y in
+    // (-pi, pi].
+    pub fn from_spherical(r: T, theta: T, phi: T) -> Vec3<T> {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        Vec3 {
+            x: r * sin_theta * cos_phi,
+            y: r * sin_theta * sin_phi,
+            z: r * cos_theta
+        }
+    }
+
+    // Returns (r, theta, phi). The zero vector maps to all zeros.
+    pub fn to_spherical(self) -> (T, T, T) {
+        let r = self.length();
+        if r == T::ZERO {
+            return (T::ZERO, T::ZERO, T::ZERO);
+        }
+        let theta = (self.z / r).max(-T::ONE).min(T::ONE).acos();
+        let phi = self.y.atan2(self.x);
+        (r, theta, phi)
+    }
+
+    // Component of self parallel to other (other does not need to be unit length).
+    pub fn project_onto(&self, other: Vec3<T>) -> Vec3<T> {
+        other * (self.dot(other) / other.length_squared())
+    }
+
+    // Component of self perpendicular to other.
+    pub fn reject_from(&self, other: Vec3<T>) -> Vec3<T> {
+        *self - self.project_onto(other)
+    }
+
+    pub fn length_squared(&self) -> T {
         self.x * self.x 
         + self.y * self.y 
         + self.z * self.z
     }
 
-    pub fn length(&self) -> f64 {
+    pub fn length(&self) -> T {
         self.length_squared().sqrt()
     }
 
-    pub fn to_rgb(&self) -> [u8; 3] {
-        fn f(num: f64) -> u8 {
-            if num < 0.0 { 
-                0
+    pub fn distance_squared(&self, other: Vec3<T>) -> T {
+        (*self - other).length_squared()
+    }
+
+    pub fn distance(&self, other: Vec3<T>) -> T {
+        (*self - other).length()
+    }
+
+    pub fn near_zero(&self) -> bool {
+        let epsilon = T::from_f64(1e-8);
+        self.x.abs() < epsilon
+        && self.y.abs() < epsilon
+        && self.z.abs() < epsilon
+    }
+
+    // A zero-length vector has no direction, so it is returned unchanged
+    // instead of being divided into NaNs.
+    pub fn unit_vector(&self) -> Vec3<T> {
+        let length = self.length();
+        if length == T::ZERO {
+            *self
+        }
+        else {
+            *self / length
+        }
+    }
+
+    pub fn normalize(&mut self) {
+        *self = self.unit_vector();
+    }
+
+    // None when the vector has no usable direction: zero length, or a NaN or
+    // infinite component.
+    pub fn try_normalize(self) -> Option<Vec3<T>> {
+        let length = self.length();
+        if length > T::ZERO && length.is_finite() {
+            Some(self / length)
+        }
+        else {
+            None
+        }
+    }
+
+    // Like try_normalize, falling back to the given vector instead of None.
+    pub fn normalize_or(self, fallback: Vec3<T>) -> Vec3<T> {
+        self.try_normalize().unwrap_or(fallback)
+    }
+
+    pub fn min_element(&self) -> T {
+        self.x.min(self.y).min(self.z)
+    }
+
+    pub fn max_element(&self) -> T {
+        self.x.max(self.y).max(self.z)
+    }
+
+    pub fn component_sum(&self) -> T {
+        self.x + self.y + self.z
+    }
+
+    // Relative luminance of a linear RGB color with Rec. 709 primaries.
+    pub fn luminance(&self) -> T {
+        self.dot(Vec3::new(T::from_f64(0.2126), T::from_f64(0.7152), T::from_f64(0.0722)))
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
+    pub fn has_nan(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
+
+    // Passes the vector through, panicking in debug builds if any component
+    // is NaN or infinite. Costs nothing in release builds.
+    pub fn debug_assert_finite(self) -> Vec3<T> {
+        debug_assert!(self.is_finite(), "non-finite Vec3: {}", self);
+        self
+    }
+
+    // Applies f to each component.
+    pub fn map(self, f: impl Fn(T) -> T) -> Vec3<T> {
+        Vec3 {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z)
+        }
+    }
+
+    // Applies f to each pair of matching components.
+    pub fn zip_map(self, other: Vec3<T>, f: impl Fn(T, T) -> T) -> Vec3<T> {
+        Vec3 {
+            x: f(self.x, other.x),
+            y: f(self.y, other.y),
+            z: f(self.z, other.z)
+        }
+    }
+
+    // Component-wise square root, e.g. gamma 2 correction of a color.
+    pub fn sqrt(self) -> Vec3<T> {
+        self.map(T::sqrt)
+    }
+
+    // 1 or -1 by the sign of each component and 0 for zeros, where
+    // f64::signum would give 1. NaN components stay NaN.
+    pub fn signum(self) -> Vec3<T> {
+        self.map(|c| {
+            if c > T::ZERO {
+                T::ONE
             }
-            else if num >= 1.0 {
-                255
+            else if c < T::ZERO {
+                -T::ONE
             }
             else {
-                (num * 255.99) as u8
+                c
             }
+        })
+    }
+
+    pub fn floor(self) -> Vec3<T> {
+        self.map(T::floor)
+    }
+
+    pub fn ceil(self) -> Vec3<T> {
+        self.map(T::ceil)
+    }
+
+    // Rounds half-way cases away from zero.
+    pub fn round(self) -> Vec3<T> {
+        self.map(T::round)
+    }
+
+    // x - floor(x) as in GLSL, so the result is always in [0, 1), also for
+    // negative components. f64::fract would give values in (-1, 0) there.
+    pub fn fract(self) -> Vec3<T> {
+        self - self.floor()
+    }
+
+    pub fn powf(self, n: T) -> Vec3<T> {
+        self.map(|c| c.powf(n))
+    }
+
+    pub fn exp(self) -> Vec3<T> {
+        self.map(T::exp)
+    }
+
+    pub fn ln(self) -> Vec3<T> {
+        self.map(T::ln)
+    }
+
+    // Zero components follow IEEE division and become +/-inf (depending on
+    // the sign of the zero), which is what the AABB slab test expects.
+    pub fn recip(&self) -> Vec3<T> {
+        self.map(T::recip)
+    }
+
+    // Written as (1 - t) * a + t * b rather than a + t * (b - a) so that
+    // t = 1 returns exactly b.
+    pub fn lerp(self, other: Vec3<T>, t: T) -> Vec3<T> {
+        self * (T::ONE - t) + other * t
+    }
+
+    pub fn abs(self) -> Vec3<T> {
+        self.map(T::abs)
+    }
+
+    pub fn min(self, other: Vec3<T>) -> Vec3<T> {
+        self.zip_map(other, T::min)
+    }
+
+    pub fn max(self, other: Vec3<T>) -> Vec3<T> {
+        self.zip_map(other, T::max)
+    }
+
+    pub fn clamp(self, min: T, max: T) -> Vec3<T> {
+        self.clamp_vec(Vec3::new(min, min, min), Vec3::new(max, max, max))
+    }
+
+    // Clamps each component between the matching components of min and max.
+    pub fn clamp_vec(self, min: Vec3<T>, max: Vec3<T>) -> Vec3<T> {
+        self.max(min).min(max)
+    }
+
+    // Component-wise math::step: 0 where v is below edge, 1 elsewhere.
+    pub fn step(edge: Vec3<T>, v: Vec3<T>) -> Vec3<T> {
+        edge.zip_map(v, math::step)
+    }
+
+    // Component-wise math::smoothstep between edge0 and edge1.
+    pub fn smoothstep(edge0: Vec3<T>, edge1: Vec3<T>, v: Vec3<T>) -> Vec3<T> {
+        Vec3 {
+            x: math::smoothstep(edge0.x, edge1.x, v.x),
+            y: math::smoothstep(edge0.y, edge1.y, v.y),
+            z: math::smoothstep(edge0.z, edge1.z, v.z)
+        }
+    }
+
+    // Rotations are counter-clockwise in radians when looking down the
+    // axis towards the origin.
+    pub fn rotate_x(&self, angle: T) -> Vec3<T> {
+        let (sin, cos) = angle.sin_cos();
+        Vec3 {
+            x: self.x,
+            y: self.y * cos - self.z * sin,
+            z: self.y * sin + self.z * cos
+        }
+    }
+
+    pub fn rotate_y(&self, angle: T) -> Vec3<T> {
+        let (sin, cos) = angle.sin_cos();
+        Vec3 {
+            x: self.x * cos + self.z * sin,
+            y: self.y,
+            z: -self.x * sin + self.z * cos
+        }
+    }
+
+    pub fn rotate_z(&self, angle: T) -> Vec3<T> {
+        let (sin, cos) = angle.sin_cos();
+        Vec3 {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+            z: self.z
+        }
+    }
+
+    // Rodrigues' rotation formula. The axis does not need to be unit length.
+    pub fn rotate_about_axis(&self, axis: Vec3<T>, angle: T) -> Vec3<T> {
+        let k = axis.unit_vector();
+        let (sin, cos) = angle.sin_cos();
+        *self * cos + k.cross(*self) * sin + k * k.dot(*self) * (T::ONE - cos)
+    }
+
+    // Mirror reflection around a unit-length normal.
+    pub fn reflect(&self, normal: Vec3<T>) -> Vec3<T> {
+        *self - normal * (T::from_f64(2.0) * self.dot(normal))
+    }
+
+    // Mirrors this position across the plane of points p with
+    // plane_normal · p = plane_offset. The normal need not be unit length;
+    // the offset is measured in the same units as the normal.
+    pub fn reflect_across_plane(&self, plane_normal: Vec3<T>, plane_offset: T) -> Vec3<T> {
+        let signed_distance = (self.dot(plane_normal) - plane_offset) / plane_normal.length_squared();
+        *self - plane_normal * (T::from_f64(2.0) * signed_distance)
+    }
+
+    // Snell's law for a unit-length incoming direction and normal. The
+    // result is split into parts perpendicular and parallel to the normal.
+    pub fn refract(&self, normal: Vec3<T>, etai_over_etat: T) -> Vec3<T> {
+        let cos_theta = (-*self).dot(normal).min(T::ONE);
+        let r_out_perpendicular = (*self + normal * cos_theta) * etai_over_etat;
+        let r_out_parallel = normal * -(T::ONE - r_out_perpendicular.length_squared()).abs().sqrt();
+        r_out_perpendicular + r_out_parallel
+    }
+
+    // Like refract, but returns None on total internal reflection.
+    pub fn try_refract(&self, normal: Vec3<T>, etai_over_etat: T) -> Option<Vec3<T>> {
+        let cos_theta = (-*self).dot(normal).min(T::ONE);
+        let sin_theta = (T::ONE - cos_theta * cos_theta).sqrt();
+        if etai_over_etat * sin_theta > T::ONE {
+            None
+        }
+        else {
+            Some(self.refract(normal, etai_over_etat))
+        }
+    }
+
+    pub const fn as_array(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn get(&self, axis: Axis) -> T {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+        }
+    }
+
+    pub fn set(&mut self, axis: Axis, value: T) {
+        match axis {
+            Axis::X => self.x = value,
+            Axis::Y => self.y = value,
+            Axis::Z => self.z = value,
+        }
+    }
+
+    // Builds a vector whose x, y and z are taken from the given axes of this
+    // one, e.g. permute(Axis::Z, Axis::X, Axis::Y) gives (z, x, y).
+    pub fn permute(&self, x_axis: Axis, y_axis: Axis, z_axis: Axis) -> Vec3<T> {
+        Vec3::new(self.get(x_axis), self.get(y_axis), self.get(z_axis))
+    }
+
+    // Reads the components as linear 0.0-1.0 color channels. They are
+    // clamped to that range and scaled so that 1.0 maps to 255; NaN maps to 0.
+    pub fn to_rgb8(self) -> [u8; 3] {
+        fn f<T: Float>(channel: T) -> u8 {
+            (channel.max(T::ZERO).min(T::ONE) * T::from_f64(255.99)).to_u8().unwrap_or(255)
         }
         [f(self.x), f(self.y), f(self.z)]
+    }
+
+    // As to_rgb8, but gamma encodes the channels first: gamma 2.0 takes the
+    // square root of each channel.
+    pub fn to_rgb8_gamma(self, gamma: T) -> [u8; 3] {
+        self.max(Vec3::ZERO).powf(T::ONE / gamma).to_rgb8()
     }    
 }
 
-impl Add<Vec3> for Vec3 {
-    type Output = Vec3;
-    fn add(self, other: Vec3) -> Vec3 {
+impl<T: Float + SampleUniform> Vec3<T> where Standard: Distribution<T> {
+    // Components are sampled uniformly from [0, 1).
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Vec3<T> {
+        Vec3 {
+            x: rng.gen(),
+            y: rng.gen(),
+            z: rng.gen()
+        }
+    }
+
+    // Components are sampled uniformly from [min, max).
+    pub fn random_range<R: Rng + ?Sized>(rng: &mut R, min: T, max: T) -> Vec3<T> {
+        Vec3 {
+            x: rng.gen_range(min..max),
+            y: rng.gen_range(min..max),
+            z: rng.gen_range(min..max)
+        }
+    }
+
+    // Rejection sampling: draw from the enclosing cube until the point
+    // lands inside the sphere.
+    pub fn random_in_unit_sphere<R: Rng + ?Sized>(rng: &mut R) -> Vec3<T> {
+        loop {
+            let point = Vec3::random_range(rng, -T::ONE, T::ONE);
+            if point.length_squared() < T::ONE {
+                return point;
+            }
+        }
+    }
+
+    pub fn random_unit_vector<R: Rng + ?Sized>(rng: &mut R) -> Vec3<T> {
+        loop {
+            let point: Vec3<T> = Vec3::random_in_unit_sphere(rng);
+            // Points too close to the center would lose precision when scaled up.
+            if point.length_squared() > T::from_f64(1e-160) {
+                return point.unit_vector();
+            }
+        }
+    }
+
+    pub fn random_in_hemisphere<R: Rng + ?Sized>(rng: &mut R, normal: Vec3<T>) -> Vec3<T> {
+        let in_unit_sphere = Vec3::random_in_unit_sphere(rng);
+        if in_unit_sphere.dot(normal) > T::ZERO {
+            in_unit_sphere
+        }
+        else {
+            -in_unit_sphere
+        }
+    }
+
+    pub fn random_in_unit_disk<R: Rng + ?Sized>(rng: &mut R) -> Vec3<T> {
+        loop {
+            let point = Vec3::new(rng.gen_range(-T::ONE..T::ONE), rng.gen_range(-T::ONE..T::ONE), T::ZERO);
+            if point.length_squared() < T::ONE {
+                return point;
+            }
+        }
+    }
+}
+
+// rng.gen::<Vec3>() samples like Vec3::random.
+impl<T> Distribution<Vec3<T>> for Standard where Standard: Distribution<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3<T> {
+        Vec3 {
+            x: rng.gen(),
+            y: rng.gen(),
+            z: rng.gen()
+        }
+    }
+}
+
+// Unit vectors uniformly distributed on the surface of the sphere, as from
+// Vec3::random_unit_vector.
+#[derive(Clone, Copy, Debug)]
+pub struct UnitSphere;
+
+impl<T: Float + SampleUniform> Distribution<Vec3<T>> for UnitSphere where Standard: Distribution<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3<T> {
+        Vec3::random_unit_vector(rng)
+    }
+}
+
+// Points inside the unit disk in the xy plane, as from
+// Vec3::random_in_unit_disk.
+#[derive(Clone, Copy, Debug)]
+pub struct UnitDisk;
+
+impl<T: Float + SampleUniform> Distribution<Vec3<T>> for UnitDisk where Standard: Distribution<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec3<T> {
+        Vec3::random_in_unit_disk(rng)
+    }
+}
+
+impl<T: Float> Add<Vec3<T>> for Vec3<T> {
+    type Output = Vec3<T>;
+    fn add(self, other: Vec3<T>) -> Vec3<T> {
         Vec3 { 
             x: self.x + other.x, 
             y: self.y + other.y,
@@ -64,9 +596,9 @@ impl Add<Vec3> for Vec3 {
     }
 }
 
-impl Sub<Vec3> for Vec3 {
-    type Output = Vec3;
-    fn sub(self, other: Vec3) -> Vec3 {
+impl<T: Float> Sub<Vec3<T>> for Vec3<T> {
+    type Output = Vec3<T>;
+    fn sub(self, other: Vec3<T>) -> Vec3<T> {
         Vec3 { 
             x: self.x - other.x, 
             y: self.y - other.y,
@@ -75,9 +607,9 @@ impl Sub<Vec3> for Vec3 {
     }
 }
 
-impl Neg for Vec3 {
-    type Output = Vec3;
-    fn neg(self) -> Vec3 {
+impl<T: Float> Neg for Vec3<T> {
+    type Output = Vec3<T>;
+    fn neg(self) -> Vec3<T> {
         Vec3{
             x: -self.x,
             y: -self.y, 
@@ -86,20 +618,9 @@ impl Neg for Vec3 {
     }
 }
 
-impl Mul<Vec3> for f64 {
-    type Output = Vec3;
-    fn mul(self, vector: Vec3) -> Vec3 {
-        Vec3{
-            x: self * vector.x,
-            y: self * vector.y, 
-            z: self * vector.z
-        }
-    }
-}
-
-impl Mul<f64> for Vec3 {
-    type Output = Vec3;
-    fn mul(self, scalar: f64) -> Vec3 {
+impl<T: Float> Mul<T> for Vec3<T> {
+    type Output = Vec3<T>;
+    fn mul(self, scalar: T) -> Vec3<T> {
         Vec3{
             x: self.x * scalar,
             y: self.y * scalar, 
@@ -109,9 +630,9 @@ impl Mul<f64> for Vec3 {
 }
 
 // Hadamard product
-impl Mul<Vec3> for Vec3 {
-    type Output = Vec3;
-    fn mul(self, other: Vec3) -> Vec3 {
+impl<T: Float> Mul<Vec3<T>> for Vec3<T> {
+    type Output = Vec3<T>;
+    fn mul(self, other: Vec3<T>) -> Vec3<T> {
         Vec3{
             x: self.x * other.x,
             y: self.y * other.y, 
@@ -120,9 +641,9 @@ impl Mul<Vec3> for Vec3 {
     }
 }
 
-impl Div<f64> for Vec3 {
-    type Output = Vec3;
-    fn div(self, scalar: f64) -> Vec3 {
+impl<T: Float> Div<T> for Vec3<T> {
+    type Output = Vec3<T>;
+    fn div(self, scalar: T) -> Vec3<T> {
         Vec3{
             x: self.x / scalar,
             y: self.y / scalar, 
@@ -131,119 +652,2364 @@ impl Div<f64> for Vec3 {
     }
 }
 
-impl fmt::Display for Vec3 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+// Hadamard division
+impl<T: Float> Div<Vec3<T>> for Vec3<T> {
+    type Output = Vec3<T>;
+    fn div(self, other: Vec3<T>) -> Vec3<T> {
+        Vec3{
+            x: self.x / other.x,
+            y: self.y / other.y,
+            z: self.z / other.z
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use assert_approx_eq::*;
-    use super::*;
-    
-    macro_rules! assert_vec3_equal {
-        ($expected:expr, $actual:expr) => {
-            let tolerance = 0.0001;
-            assert_approx_eq!($expected.x, $actual.x, tolerance);
-            assert_approx_eq!($expected.y, $actual.y, tolerance);
-            assert_approx_eq!($expected.z, $actual.z, tolerance);
+// Same semantics as % on floats: the result takes the sign of the dividend.
+impl<T: Float> Rem<T> for Vec3<T> {
+    type Output = Vec3<T>;
+    fn rem(self, scalar: T) -> Vec3<T> {
+        Vec3{
+            x: self.x % scalar,
+            y: self.y % scalar,
+            z: self.z % scalar
         }
     }
-    
-    #[test]
-    fn addition() {
-        let vector1 = Vec3::new(1.0, 2.0, 3.0);
-        let vector2 = Vec3::new(4.0, 5.0, 6.0);
-        let result = vector1 + vector2;
-        let expected = Vec3::new(5.0, 7.0, 9.0);
-        assert_vec3_equal!(expected, result);
-    }
+}
 
-    #[test]
-    fn substraction() {
-        let vector1 = Vec3::new(1.0, 2.0, 3.0);
-        let vector2 = Vec3::new(4.0, 5.0, 6.0);
-        let result = vector1 - vector2;
-        let expected = Vec3::new(-3.0, -3.0, -3.0);
-        assert_vec3_equal!(expected, result);
+impl<T: Float> Rem<Vec3<T>> for Vec3<T> {
+    type Output = Vec3<T>;
+    fn rem(self, other: Vec3<T>) -> Vec3<T> {
+        Vec3{
+            x: self.x % other.x,
+            y: self.y % other.y,
+            z: self.z % other.z
+        }
     }
+}
 
-    #[test]
-    fn negation() {
-        let vector1 = Vec3::new(1.0, 2.0, 3.0);
-        let result = -vector1; 
-        let expected = Vec3::new(-1.0, -2.0, -3.0);
-        assert_vec3_equal!(expected, result);
+impl<T: Float> AddAssign<Vec3<T>> for Vec3<T> {
+    fn add_assign(&mut self, other: Vec3<T>) {
+        *self = *self + other;
     }
+}
 
-    #[test]
-    fn multiplication() {
-        let vector1 = Vec3::new(1.0, 2.0, 3.0);
-        let result1 = 5.0 * vector1;
-        let expected = Vec3::new(5.0, 10.0, 15.0);
-        assert_vec3_equal!(expected, result1);
+impl<T: Float> SubAssign<Vec3<T>> for Vec3<T> {
+    fn sub_assign(&mut self, other: Vec3<T>) {
+        *self = *self - other;
+    }
+}
 
-        let result2 = vector1 * 5.0;
-        assert_vec3_equal!(expected, result2);
+impl<T: Float> MulAssign<T> for Vec3<T> {
+    fn mul_assign(&mut self, scalar: T) {
+        *self = *self * scalar;
     }
+}
 
-    #[test]
+// Hadamard product
+impl<T: Float> MulAssign<Vec3<T>> for Vec3<T> {
+    fn mul_assign(&mut self, other: Vec3<T>) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Float> DivAssign<T> for Vec3<T> {
+    fn div_assign(&mut self, scalar: T) {
+        *self = *self / scalar;
+    }
+}
+
+// Hadamard division
+impl<T: Float> DivAssign<Vec3<T>> for Vec3<T> {
+    fn div_assign(&mut self, other: Vec3<T>) {
+        *self = *self / other;
+    }
+}
+
+impl<T: Float> RemAssign<T> for Vec3<T> {
+    fn rem_assign(&mut self, scalar: T) {
+        *self = *self % scalar;
+    }
+}
+
+impl<T: Float> RemAssign<Vec3<T>> for Vec3<T> {
+    fn rem_assign(&mut self, other: Vec3<T>) {
+        *self = *self % other;
+    }
+}
+
+impl<T: Float> Sum for Vec3<T> {
+    fn sum<I: Iterator<Item = Vec3<T>>>(iter: I) -> Vec3<T> {
+        iter.fold(Vec3::ZERO, |sum, vector| sum + vector)
+    }
+}
+
+impl<'a, T: Float> Sum<&'a Vec3<T>> for Vec3<T> {
+    fn sum<I: Iterator<Item = &'a Vec3<T>>>(iter: I) -> Vec3<T> {
+        iter.copied().sum()
+    }
+}
+
+// Hadamard product, e.g. the combined attenuation along a path
+impl<T: Float> Product for Vec3<T> {
+    fn product<I: Iterator<Item = Vec3<T>>>(iter: I) -> Vec3<T> {
+        iter.fold(Vec3::ONE, |product, vector| product * vector)
+    }
+}
+
+impl<'a, T: Float> Product<&'a Vec3<T>> for Vec3<T> {
+    fn product<I: Iterator<Item = &'a Vec3<T>>>(iter: I) -> Vec3<T> {
+        iter.copied().product()
+    }
+}
+
+impl<T> Index<usize> for Vec3<T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vec3 index out of range: {} (expected 0, 1 or 2)", index)
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Vec3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Vec3 index out of range: {} (expected 0, 1 or 2)", index)
+        }
+    }
+}
+
+impl<T> From<[T; 3]> for Vec3<T> {
+    fn from([x, y, z]: [T; 3]) -> Vec3<T> {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Vec3<T> {
+        Vec3 { x, y, z }
+    }
+}
+
+// Impls with the scalar on the left (or as the target type) can't be generic
+// over T because of the orphan rules, so they are stamped out per type.
+macro_rules! impl_scalar_ops {
+    ($t:ty) => {
+        impl Mul<Vec3<$t>> for $t {
+            type Output = Vec3<$t>;
+            fn mul(self, vector: Vec3<$t>) -> Vec3<$t> {
+                vector * self
+            }
+        }
+
+        impl From<Vec3<$t>> for [$t; 3] {
+            fn from(vector: Vec3<$t>) -> [$t; 3] {
+                vector.as_array()
+            }
+        }
+
+        impl From<Vec3<$t>> for ($t, $t, $t) {
+            fn from(vector: Vec3<$t>) -> ($t, $t, $t) {
+                (vector.x, vector.y, vector.z)
+            }
+        }
+    }
+}
+
+impl_scalar_ops!(f32);
+impl_scalar_ops!(f64);
+
+// Width and precision flags apply to each component, so `{:.3}` prints
+// every component with three decimals.
+impl<T: fmt::Display> fmt::Display for Vec3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        fmt::Display::fmt(&self.x, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.y, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.z, f)?;
+        write!(f, ")")
+    }
+}
+
+impl<T: fmt::LowerExp> fmt::LowerExp for Vec3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        fmt::LowerExp::fmt(&self.x, f)?;
+        write!(f, ", ")?;
+        fmt::LowerExp::fmt(&self.y, f)?;
+        write!(f, ", ")?;
+        fmt::LowerExp::fmt(&self.z, f)?;
+        write!(f, ")")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseVec3Error {
+    UnbalancedParentheses,
+    WrongComponentCount(usize),
+    // The component that is not a valid number.
+    InvalidComponent(Axis),
+}
+
+impl fmt::Display for ParseVec3Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseVec3Error::UnbalancedParentheses =>
+                write!(f, "unbalanced parentheses in vector"),
+            ParseVec3Error::WrongComponentCount(count) =>
+                write!(f, "expected 3 vector components, found {}", count),
+            ParseVec3Error::InvalidComponent(axis) => {
+                let name = match axis {
+                    Axis::X => "x",
+                    Axis::Y => "y",
+                    Axis::Z => "z",
+                };
+                write!(f, "invalid {} component in vector", name)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseVec3Error {}
+
+// Accepts three numbers separated by commas or whitespace, optionally in
+// parentheses: "1.0, 2.0, 3.0" or "(1 2 3)".
+impl<T: Float + FromStr> FromStr for Vec3<T> {
+    type Err = ParseVec3Error;
+
+    fn from_str(s: &str) -> Result<Vec3<T>, ParseVec3Error> {
+        let s = s.trim();
+        let inner = match (s.strip_prefix('('), s.ends_with(')')) {
+            (Some(rest), true) => &rest[..rest.len() - 1],
+            (None, false) => s,
+            _ => return Err(ParseVec3Error::UnbalancedParentheses),
+        };
+
+        let comma_separated = inner.contains(',');
+        let parts = inner
+            .split(|c: char| if comma_separated { c == ',' } else { c.is_whitespace() })
+            .map(str::trim)
+            .filter(|part| comma_separated || !part.is_empty());
+
+        let mut components = [T::ZERO; 3];
+        let mut count = 0;
+        for part in parts {
+            if let Some(&axis) = Axis::ALL.get(count) {
+                components[count] = part.parse()
+                    .map_err(|_| ParseVec3Error::InvalidComponent(axis))?;
+            }
+            count += 1;
+        }
+        if count != 3 {
+            return Err(ParseVec3Error::WrongComponentCount(count));
+        }
+        Ok(Vec3::from(components))
+    }
+}
+
+impl Vec3 {
+    // Projections onto a coordinate plane, e.g. for axis-aligned rectangles.
+    pub fn xy(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    pub fn xz(&self) -> Vec2 {
+        Vec2::new(self.x, self.z)
+    }
+
+    pub fn yx(&self) -> Vec2 {
+        Vec2::new(self.y, self.x)
+    }
+
+    pub fn yz(&self) -> Vec2 {
+        Vec2::new(self.y, self.z)
+    }
+
+    pub fn zx(&self) -> Vec2 {
+        Vec2::new(self.z, self.x)
+    }
+
+    pub fn zy(&self) -> Vec2 {
+        Vec2::new(self.z, self.y)
+    }
+
+    // Homogeneous coordinates for Mat4: see Vec4::from_point and
+    // Vec4::from_direction.
+    pub const fn to_point4(self) -> Vec4 {
+        Vec4::from_point(self)
+    }
+
+    pub const fn to_dir4(self) -> Vec4 {
+        Vec4::from_direction(self)
+    }
+
+    // Raw bit patterns of the components. -0.0 and 0.0 differ here, as do
+    // NaNs with different payloads.
+    pub fn to_bits(self) -> [u64; 3] {
+        [self.x.to_bits(), self.y.to_bits(), self.z.to_bits()]
+    }
+}
+
+// Vec3 wrapper with Eq and Hash, for use as a HashMap key (e.g. vertex
+// deduplication). Equality is exact: -0.0 is treated as 0.0 and all NaNs
+// are equal to each other, otherwise vectors must match bit for bit.
+#[derive(Clone, Copy, Debug)]
+pub struct OrderedVec3(pub Vec3);
+
+impl OrderedVec3 {
+    fn canonical_bits(&self) -> [u64; 3] {
+        fn canonical(value: f64) -> u64 {
+            if value.is_nan() {
+                f64::NAN.to_bits()
+            }
+            else if value == 0.0 {
+                0.0f64.to_bits()
+            }
+            else {
+                value.to_bits()
+            }
+        }
+        [canonical(self.0.x), canonical(self.0.y), canonical(self.0.z)]
+    }
+}
+
+impl PartialEq for OrderedVec3 {
+    fn eq(&self, other: &OrderedVec3) -> bool {
+        self.canonical_bits() == other.canonical_bits()
+    }
+}
+
+impl Eq for OrderedVec3 {}
+
+impl Hash for OrderedVec3 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_bits().hash(state);
+    }
+}
+
+impl From<Vec3> for OrderedVec3 {
+    fn from(vector: Vec3) -> OrderedVec3 {
+        OrderedVec3(vector)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct Vec2 {
+    x: f64,
+    y: f64,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2::new(0.0, 0.0);
+    pub const ONE: Vec2 = Vec2::new(1.0, 1.0);
+    pub const X: Vec2 = Vec2::new(1.0, 0.0);
+    pub const Y: Vec2 = Vec2::new(0.0, 1.0);
+
+    pub const fn new(x: f64, y: f64) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub const fn x(&self) -> f64 {
+        self.x
+    }
+
+    pub const fn y(&self) -> f64 {
+        self.y
+    }
+
+    pub fn dot(&self, other: Vec2) -> f64 {
+        self.x * other.x
+        + self.y * other.y
+    }
+
+    pub fn approx_eq(&self, other: Vec2, epsilon: f64) -> bool {
+        (self.x - other.x).abs() <= epsilon
+        && (self.y - other.y).abs() <= epsilon
+    }
+
+    pub fn length_squared(&self) -> f64 {
+        self.x * self.x
+        + self.y * self.y
+    }
+
+    pub fn length(&self) -> f64 {
+        self.length_squared().sqrt()
+    }
+
+    // Same zero-length handling as Vec3::unit_vector.
+    pub fn unit_vector(&self) -> Vec2 {
+        let length = self.length();
+        if length == 0.0 {
+            *self
+        }
+        else {
+            *self / length
+        }
+    }
+
+    pub fn normalize(&mut self) {
+        *self = self.unit_vector();
+    }
+
+    pub fn extend(&self, z: f64) -> Vec3 {
+        Vec3::new(self.x, self.y, z)
+    }
+
+    pub const fn as_array(&self) -> [f64; 2] {
+        [self.x, self.y]
+    }
+}
+
+impl Add<Vec2> for Vec2 {
+    type Output = Vec2;
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2 {
+            x: self.x + other.x,
+            y: self.y + other.y
+        }
+    }
+}
+
+impl Sub<Vec2> for Vec2 {
+    type Output = Vec2;
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2 {
+            x: self.x - other.x,
+            y: self.y - other.y
+        }
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+    fn neg(self) -> Vec2 {
+        Vec2 {
+            x: -self.x,
+            y: -self.y
+        }
+    }
+}
+
+impl Mul<Vec2> for f64 {
+    type Output = Vec2;
+    fn mul(self, vector: Vec2) -> Vec2 {
+        Vec2 {
+            x: self * vector.x,
+            y: self * vector.y
+        }
+    }
+}
+
+impl Mul<f64> for Vec2 {
+    type Output = Vec2;
+    fn mul(self, scalar: f64) -> Vec2 {
+        Vec2 {
+            x: self.x * scalar,
+            y: self.y * scalar
+        }
+    }
+}
+
+// Hadamard product
+impl Mul<Vec2> for Vec2 {
+    type Output = Vec2;
+    fn mul(self, other: Vec2) -> Vec2 {
+        Vec2 {
+            x: self.x * other.x,
+            y: self.y * other.y
+        }
+    }
+}
+
+impl Div<f64> for Vec2 {
+    type Output = Vec2;
+    fn div(self, scalar: f64) -> Vec2 {
+        Vec2 {
+            x: self.x / scalar,
+            y: self.y / scalar
+        }
+    }
+}
+
+impl AddAssign<Vec2> for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl SubAssign<Vec2> for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl MulAssign<f64> for Vec2 {
+    fn mul_assign(&mut self, scalar: f64) {
+        self.x *= scalar;
+        self.y *= scalar;
+    }
+}
+
+// Hadamard product
+impl MulAssign<Vec2> for Vec2 {
+    fn mul_assign(&mut self, other: Vec2) {
+        self.x *= other.x;
+        self.y *= other.y;
+    }
+}
+
+impl DivAssign<f64> for Vec2 {
+    fn div_assign(&mut self, scalar: f64) {
+        self.x /= scalar;
+        self.y /= scalar;
+    }
+}
+
+impl Index<usize> for Vec2 {
+    type Output = f64;
+    fn index(&self, index: usize) -> &f64 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Vec2 index out of range: {} (expected 0 or 1)", index)
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec2 {
+    fn index_mut(&mut self, index: usize) -> &mut f64 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Vec2 index out of range: {} (expected 0 or 1)", index)
+        }
+    }
+}
+
+impl From<[f64; 2]> for Vec2 {
+    fn from(array: [f64; 2]) -> Vec2 {
+        Vec2 { x: array[0], y: array[1] }
+    }
+}
+
+impl From<(f64, f64)> for Vec2 {
+    fn from((x, y): (f64, f64)) -> Vec2 {
+        Vec2 { x, y }
+    }
+}
+
+impl From<Vec2> for [f64; 2] {
+    fn from(vector: Vec2) -> [f64; 2] {
+        vector.as_array()
+    }
+}
+
+impl From<Vec2> for (f64, f64) {
+    fn from(vector: Vec2) -> (f64, f64) {
+        (vector.x, vector.y)
+    }
+}
+
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[repr(C)]
+pub struct Vec4 {
+    x: f64,
+    y: f64,
+    z: f64,
+    w: f64,
+}
+
+impl Vec4 {
+    pub const ZERO: Vec4 = Vec4::new(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(x: f64, y: f64, z: f64, w: f64) -> Vec4 {
+        Vec4 { x, y, z, w }
+    }
+
+    // Points are affected by translation, so they get w = 1.
+    pub const fn from_point(point: Vec3) -> Vec4 {
+        Vec4 { x: point.x, y: point.y, z: point.z, w: 1.0 }
+    }
+
+    // Directions ignore translation, so they get w = 0.
+    pub const fn from_direction(direction: Vec3) -> Vec4 {
+        Vec4 { x: direction.x, y: direction.y, z: direction.z, w: 0.0 }
+    }
+
+    pub const fn x(&self) -> f64 {
+        self.x
+    }
+
+    pub const fn y(&self) -> f64 {
+        self.y
+    }
+
+    pub const fn z(&self) -> f64 {
+        self.z
+    }
+
+    pub const fn w(&self) -> f64 {
+        self.w
+    }
+
+    // Drops w without a perspective divide.
+    pub const fn xyz(&self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    // Back from homogeneous coordinates, dividing by w. Directions (w = 0)
+    // have no position to divide into, so they just drop w.
+    pub fn to_vec3(&self) -> Vec3 {
+        if self.w == 0.0 || self.w == 1.0 {
+            self.xyz()
+        }
+        else {
+            self.xyz() / self.w
+        }
+    }
+
+    pub fn dot(&self, other: Vec4) -> f64 {
+        self.x * other.x
+        + self.y * other.y
+        + self.z * other.z
+        + self.w * other.w
+    }
+
+    pub fn approx_eq(&self, other: Vec4, epsilon: f64) -> bool {
+        (self.x - other.x).abs() <= epsilon
+        && (self.y - other.y).abs() <= epsilon
+        && (self.z - other.z).abs() <= epsilon
+        && (self.w - other.w).abs() <= epsilon
+    }
+
+    pub fn length_squared(&self) -> f64 {
+        self.dot(*self)
+    }
+
+    pub fn length(&self) -> f64 {
+        self.length_squared().sqrt()
+    }
+
+    pub const fn as_array(&self) -> [f64; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+}
+
+impl Add<Vec4> for Vec4 {
+    type Output = Vec4;
+    fn add(self, other: Vec4) -> Vec4 {
+        Vec4 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+            w: self.w + other.w
+        }
+    }
+}
+
+impl Sub<Vec4> for Vec4 {
+    type Output = Vec4;
+    fn sub(self, other: Vec4) -> Vec4 {
+        Vec4 {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+            w: self.w - other.w
+        }
+    }
+}
+
+impl Neg for Vec4 {
+    type Output = Vec4;
+    fn neg(self) -> Vec4 {
+        Vec4 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: -self.w
+        }
+    }
+}
+
+impl Mul<Vec4> for f64 {
+    type Output = Vec4;
+    fn mul(self, vector: Vec4) -> Vec4 {
+        Vec4 {
+            x: self * vector.x,
+            y: self * vector.y,
+            z: self * vector.z,
+            w: self * vector.w
+        }
+    }
+}
+
+impl Mul<f64> for Vec4 {
+    type Output = Vec4;
+    fn mul(self, scalar: f64) -> Vec4 {
+        Vec4 {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+            w: self.w * scalar
+        }
+    }
+}
+
+// Hadamard product
+impl Mul<Vec4> for Vec4 {
+    type Output = Vec4;
+    fn mul(self, other: Vec4) -> Vec4 {
+        Vec4 {
+            x: self.x * other.x,
+            y: self.y * other.y,
+            z: self.z * other.z,
+            w: self.w * other.w
+        }
+    }
+}
+
+impl Div<f64> for Vec4 {
+    type Output = Vec4;
+    fn div(self, scalar: f64) -> Vec4 {
+        Vec4 {
+            x: self.x / scalar,
+            y: self.y / scalar,
+            z: self.z / scalar,
+            w: self.w / scalar
+        }
+    }
+}
+
+impl AddAssign<Vec4> for Vec4 {
+    fn add_assign(&mut self, other: Vec4) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+        self.w += other.w;
+    }
+}
+
+impl SubAssign<Vec4> for Vec4 {
+    fn sub_assign(&mut self, other: Vec4) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+        self.w -= other.w;
+    }
+}
+
+impl MulAssign<f64> for Vec4 {
+    fn mul_assign(&mut self, scalar: f64) {
+        self.x *= scalar;
+        self.y *= scalar;
+        self.z *= scalar;
+        self.w *= scalar;
+    }
+}
+
+// Hadamard product
+impl MulAssign<Vec4> for Vec4 {
+    fn mul_assign(&mut self, other: Vec4) {
+        self.x *= other.x;
+        self.y *= other.y;
+        self.z *= other.z;
+        self.w *= other.w;
+    }
+}
+
+impl DivAssign<f64> for Vec4 {
+    fn div_assign(&mut self, scalar: f64) {
+        self.x /= scalar;
+        self.y /= scalar;
+        self.z /= scalar;
+        self.w /= scalar;
+    }
+}
+
+impl Index<usize> for Vec4 {
+    type Output = f64;
+    fn index(&self, index: usize) -> &f64 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Vec4 index out of range: {} (expected 0, 1, 2 or 3)", index)
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec4 {
+    fn index_mut(&mut self, index: usize) -> &mut f64 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("Vec4 index out of range: {} (expected 0, 1, 2 or 3)", index)
+        }
+    }
+}
+
+impl From<[f64; 4]> for Vec4 {
+    fn from(array: [f64; 4]) -> Vec4 {
+        Vec4 { x: array[0], y: array[1], z: array[2], w: array[3] }
+    }
+}
+
+impl From<Vec4> for [f64; 4] {
+    fn from(vector: Vec4) -> [f64; 4] {
+        vector.as_array()
+    }
+}
+
+impl fmt::Display for Vec4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
+
+// Integer vector for grid cells, voxel indices and pixel coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Vec3i {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl Vec3i {
+    pub const ZERO: Vec3i = Vec3i::new(0, 0, 0);
+    pub const ONE: Vec3i = Vec3i::new(1, 1, 1);
+    pub const X: Vec3i = Vec3i::new(1, 0, 0);
+    pub const Y: Vec3i = Vec3i::new(0, 1, 0);
+    pub const Z: Vec3i = Vec3i::new(0, 0, 1);
+
+    pub const fn new(x: i32, y: i32, z: i32) -> Vec3i {
+        Vec3i { x, y, z }
+    }
+
+    // Cell containing the point, i.e. rounding towards negative infinity.
+    // Components outside the i32 range saturate.
+    pub fn from_vec3_floor(vector: Vec3) -> Vec3i {
+        Vec3i {
+            x: vector.x.floor() as i32,
+            y: vector.y.floor() as i32,
+            z: vector.z.floor() as i32
+        }
+    }
+
+    pub fn from_vec3_round(vector: Vec3) -> Vec3i {
+        Vec3i {
+            x: vector.x.round() as i32,
+            y: vector.y.round() as i32,
+            z: vector.z.round() as i32
+        }
+    }
+
+    pub const fn x(&self) -> i32 {
+        self.x
+    }
+
+    pub const fn y(&self) -> i32 {
+        self.y
+    }
+
+    pub const fn z(&self) -> i32 {
+        self.z
+    }
+
+    pub fn min(self, other: Vec3i) -> Vec3i {
+        Vec3i {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z)
+        }
+    }
+
+    pub fn max(self, other: Vec3i) -> Vec3i {
+        Vec3i {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z)
+        }
+    }
+
+    pub fn to_vec3(self) -> Vec3 {
+        Vec3::new(self.x as f64, self.y as f64, self.z as f64)
+    }
+
+    pub const fn as_array(&self) -> [i32; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl Add<Vec3i> for Vec3i {
+    type Output = Vec3i;
+    fn add(self, other: Vec3i) -> Vec3i {
+        Vec3i {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z
+        }
+    }
+}
+
+impl Sub<Vec3i> for Vec3i {
+    type Output = Vec3i;
+    fn sub(self, other: Vec3i) -> Vec3i {
+        Vec3i {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z
+        }
+    }
+}
+
+impl Neg for Vec3i {
+    type Output = Vec3i;
+    fn neg(self) -> Vec3i {
+        Vec3i {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z
+        }
+    }
+}
+
+impl Mul<Vec3i> for i32 {
+    type Output = Vec3i;
+    fn mul(self, vector: Vec3i) -> Vec3i {
+        vector * self
+    }
+}
+
+impl Mul<i32> for Vec3i {
+    type Output = Vec3i;
+    fn mul(self, scalar: i32) -> Vec3i {
+        Vec3i {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar
+        }
+    }
+}
+
+// Hadamard product
+impl Mul<Vec3i> for Vec3i {
+    type Output = Vec3i;
+    fn mul(self, other: Vec3i) -> Vec3i {
+        Vec3i {
+            x: self.x * other.x,
+            y: self.y * other.y,
+            z: self.z * other.z
+        }
+    }
+}
+
+impl AddAssign<Vec3i> for Vec3i {
+    fn add_assign(&mut self, other: Vec3i) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign<Vec3i> for Vec3i {
+    fn sub_assign(&mut self, other: Vec3i) {
+        *self = *self - other;
+    }
+}
+
+impl Index<usize> for Vec3i {
+    type Output = i32;
+    fn index(&self, index: usize) -> &i32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vec3i index out of range: {} (expected 0, 1 or 2)", index)
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec3i {
+    fn index_mut(&mut self, index: usize) -> &mut i32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Vec3i index out of range: {} (expected 0, 1 or 2)", index)
+        }
+    }
+}
+
+impl From<[i32; 3]> for Vec3i {
+    fn from([x, y, z]: [i32; 3]) -> Vec3i {
+        Vec3i { x, y, z }
+    }
+}
+
+impl From<Vec3i> for Vec3 {
+    fn from(vector: Vec3i) -> Vec3 {
+        vector.to_vec3()
+    }
+}
+
+impl fmt::Display for Vec3i {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::*;
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use super::*;
+
+    // Most tests exercise the default f64 instantiation; pinning the alias
+    // keeps literal-only expressions from needing annotations.
+    type Vec3 = super::Vec3<f64>;
+    type Vec3f = super::Vec3<f32>;
+    
+    macro_rules! assert_vec2_equal {
+        ($expected:expr, $actual:expr) => {
+            let tolerance = 0.0001;
+            assert_approx_eq!($expected.x, $actual.x, tolerance);
+            assert_approx_eq!($expected.y, $actual.y, tolerance);
+        }
+    }
+
+    macro_rules! assert_vec4_equal {
+        ($expected:expr, $actual:expr) => {
+            let tolerance = 0.0001;
+            assert_approx_eq!($expected.x, $actual.x, tolerance);
+            assert_approx_eq!($expected.y, $actual.y, tolerance);
+            assert_approx_eq!($expected.z, $actual.z, tolerance);
+            assert_approx_eq!($expected.w, $actual.w, tolerance);
+        }
+    }
+    
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let vector = Vec3::random(&mut rng);
+            for axis in 0..3 {
+                assert!(vector[axis] >= 0.0 && vector[axis] < 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn random_range() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let vector = Vec3::random_range(&mut rng, -2.0, 3.0);
+            for axis in 0..3 {
+                assert!(vector[axis] >= -2.0 && vector[axis] < 3.0);
+            }
+        }
+    }
+
+    #[test]
+    fn random_is_deterministic_when_seeded() {
+        let mut rng1 = StdRng::seed_from_u64(7);
+        let mut rng2 = StdRng::seed_from_u64(7);
+        assert!(Vec3::random(&mut rng1) == Vec3::random(&mut rng2));
+    }
+
+    #[test]
+    fn random_in_unit_sphere() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut sum = Vec3::ZERO;
+        for _ in 0..10000 {
+            let vector = Vec3::random_in_unit_sphere(&mut rng);
+            assert!(vector.length() < 1.0);
+            sum += vector;
+        }
+        assert_vec3_equal!(Vec3::ZERO, sum / 10000.0, 0.05);
+    }
+
+    #[test]
+    fn random_unit_vector() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut sum = Vec3::ZERO;
+        for _ in 0..10000 {
+            let vector = Vec3::random_unit_vector(&mut rng);
+            assert_approx_eq!(1.0, vector.length(), 0.0001);
+            sum += vector;
+        }
+        assert_vec3_equal!(Vec3::ZERO, sum / 10000.0, 0.05);
+    }
+
+    #[test]
+    fn random_in_hemisphere() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let normal = Vec3::new(1.0, -1.0, 0.5).unit_vector();
+        for _ in 0..1000 {
+            let vector = Vec3::random_in_hemisphere(&mut rng, normal);
+            assert!(vector.length() < 1.0);
+            assert!(vector.dot(normal) >= 0.0);
+        }
+    }
+
+    #[test]
+    fn random_in_unit_disk() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..1000 {
+            let vector = Vec3::random_in_unit_disk(&mut rng);
+            assert!(vector.length() < 1.0);
+            assert_eq!(0.0, vector.z);
+        }
+    }
+
+    #[test]
+    fn standard_distribution() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector: Vec3 = rng.gen();
+        let expected = Vec3::random(&mut StdRng::seed_from_u64(42));
+        assert!(vector == expected);
+        let vector: Vec3f = rng.gen();
+        assert!(vector.min_element() >= 0.0 && vector.max_element() < 1.0);
+    }
+
+    #[test]
+    fn unit_sphere_distribution() {
+        let rng = StdRng::seed_from_u64(42);
+        let samples: Vec<Vec3> = rng.sample_iter(UnitSphere).take(1000).collect();
+        for vector in &samples {
+            assert_approx_eq!(1.0, vector.length(), 0.0001);
+        }
+        let mean = samples.iter().sum::<Vec3>() / 1000.0;
+        assert_vec3_equal!(Vec3::ZERO, mean, 0.1);
+    }
+
+    #[test]
+    fn unit_disk_distribution() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..1000 {
+            let vector: Vec3 = rng.sample(UnitDisk);
+            assert!(vector.length() < 1.0);
+            assert_eq!(0.0, vector.z);
+        }
+    }
+
+    #[test]
+    fn accessors() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_approx_eq!(1.0, vector.x(), 0.001);
+        assert_approx_eq!(2.0, vector.y(), 0.001);
+        assert_approx_eq!(3.0, vector.z(), 0.001);
+    }
+
+    #[test]
+    fn constants() {
+        assert_vec3_equal!(Vec3::new(0.0, 0.0, 0.0), Vec3::ZERO);
+        assert_vec3_equal!(Vec3::new(1.0, 1.0, 1.0), Vec3::ONE);
+        assert_vec3_equal!(Vec3::new(-1.0, -4.0, 3.0), -Vec3::X - 4.0 * Vec3::Y + 3.0 * Vec3::Z);
+    }
+
+    #[test]
+    fn const_construction() {
+        static ALBEDO: Vec3 = Vec3::new(0.8, 0.6, 0.2);
+        const RED: f64 = ALBEDO.x();
+        assert_approx_eq!(0.8, RED, 0.001);
+        assert_eq!([0.8, 0.6, 0.2], ALBEDO.as_array());
+    }
+
+    #[test]
+    fn default() {
+        let result = Vec3::default();
+        assert_vec3_equal!(Vec3::ZERO, result);
+    }
+
+    #[test]
+    fn addition() {
+        let vector1 = Vec3::new(1.0, 2.0, 3.0);
+        let vector2 = Vec3::new(4.0, 5.0, 6.0);
+        let result = vector1 + vector2;
+        let expected = Vec3::new(5.0, 7.0, 9.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn substraction() {
+        let vector1 = Vec3::new(1.0, 2.0, 3.0);
+        let vector2 = Vec3::new(4.0, 5.0, 6.0);
+        let result = vector1 - vector2;
+        let expected = Vec3::new(-3.0, -3.0, -3.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn negation() {
+        let vector1 = Vec3::new(1.0, 2.0, 3.0);
+        let result = -vector1; 
+        let expected = Vec3::new(-1.0, -2.0, -3.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn multiplication() {
+        let vector1 = Vec3::new(1.0, 2.0, 3.0);
+        let result1 = 5.0 * vector1;
+        let expected = Vec3::new(5.0, 10.0, 15.0);
+        assert_vec3_equal!(expected, result1);
+
+        let result2 = vector1 * 5.0;
+        assert_vec3_equal!(expected, result2);
+    }
+
+    #[test]
     fn hadamard_product() {
         let vector1 = Vec3::new(1.0, 2.0, 3.0);
-        let vector2 = Vec3::new(4.0, 5.0, 6.0);
+        let vector2 = Vec3::new(4.0, 5.0, 6.0);
+
+        let result = vector1 * vector2;
+        let expected = Vec3::new(4.0, 10.0, 18.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn division() {
+        let vector = Vec3::new(12.0, 13.5, 15.0);
+        let divisor = 3.0;
+        let result = vector / divisor;
+        let expected = Vec3::new(4.0, 4.5, 5.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn hadamard_division() {
+        let vector1 = Vec3::new(4.0, 10.0, 18.0);
+        let vector2 = Vec3::new(4.0, 5.0, 6.0);
+
+        let result = vector1 / vector2;
+        let expected = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn remainder() {
+        let vector = Vec3::new(5.5, -5.5, 2.25);
+        assert_vec3_equal!(Vec3::new(0.5, -0.5, 0.25), vector % 1.0);
+        assert_vec3_equal!(Vec3::new(1.5, -1.5, 0.25), vector % Vec3::new(2.0, 4.0, 1.0));
+    }
+
+    #[test]
+    fn addition_assignment() {
+        let mut vector = Vec3::new(1.0, 2.0, 3.0);
+        vector += Vec3::new(4.0, 5.0, 6.0);
+        let expected = Vec3::new(5.0, 7.0, 9.0);
+        assert_vec3_equal!(expected, vector);
+    }
+
+    #[test]
+    fn substraction_assignment() {
+        let mut vector = Vec3::new(1.0, 2.0, 3.0);
+        vector -= Vec3::new(4.0, 5.0, 6.0);
+        let expected = Vec3::new(-3.0, -3.0, -3.0);
+        assert_vec3_equal!(expected, vector);
+    }
+
+    #[test]
+    fn multiplication_assignment() {
+        let mut vector = Vec3::new(1.0, 2.0, 3.0);
+        vector *= 5.0;
+        let expected = Vec3::new(5.0, 10.0, 15.0);
+        assert_vec3_equal!(expected, vector);
+    }
+
+    #[test]
+    fn hadamard_product_assignment() {
+        let mut vector = Vec3::new(1.0, 2.0, 3.0);
+        vector *= Vec3::new(4.0, 5.0, 6.0);
+        let expected = Vec3::new(4.0, 10.0, 18.0);
+        assert_vec3_equal!(expected, vector);
+    }
+
+    #[test]
+    fn division_assignment() {
+        let mut vector = Vec3::new(12.0, 13.5, 15.0);
+        vector /= 3.0;
+        let expected = Vec3::new(4.0, 4.5, 5.0);
+        assert_vec3_equal!(expected, vector);
+    }
+
+    #[test]
+    fn hadamard_division_assignment() {
+        let mut vector = Vec3::new(4.0, 10.0, 18.0);
+        vector /= Vec3::new(4.0, 5.0, 6.0);
+        let expected = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(expected, vector);
+    }
+
+    #[test]
+    fn remainder_assignment() {
+        let mut vector = Vec3::new(5.5, 7.0, 9.25);
+        vector %= 2.0;
+        assert_vec3_equal!(Vec3::new(1.5, 1.0, 1.25), vector);
+        vector %= Vec3::new(1.0, 2.0, 0.5);
+        assert_vec3_equal!(Vec3::new(0.5, 1.0, 0.25), vector);
+    }
+
+    #[test]
+    fn equality() {
+        let vector1 = Vec3::new(1.0, 2.0, 3.0);
+        let vector2 = Vec3::new(1.0, 2.0, 3.0);
+        let vector3 = Vec3::new(1.0, 2.0, 3.5);
+        assert!(vector1 == vector2);
+        assert!(vector1 != vector3);
+    }
+
+    #[test]
+    fn approx_eq() {
+        let vector1 = Vec3::new(1.0, 2.0, 3.0);
+        let vector2 = Vec3::new(1.00001, 1.99999, 3.0);
+        assert!(vector1.approx_eq(vector2, 0.0001));
+        assert!(!vector1.approx_eq(vector2, 0.000001));
+    }
+
+    #[test]
+    fn sum() {
+        let vectors = [Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0), Vec3::new(-1.0, 0.0, 1.0)];
+        let result: Vec3 = vectors.iter().sum();
+        assert_vec3_equal!(Vec3::new(4.0, 7.0, 10.0), result);
+        let mapped: Vec3 = vectors.iter().map(|v| *v * 2.0).sum();
+        assert_vec3_equal!(Vec3::new(8.0, 14.0, 20.0), mapped);
+        let empty: Vec3 = std::iter::empty::<Vec3>().sum();
+        assert_vec3_equal!(Vec3::ZERO, empty);
+    }
+
+    #[test]
+    fn product() {
+        let vectors = [Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0), Vec3::new(0.5, 0.5, 0.5)];
+        let result: Vec3 = vectors.iter().product();
+        assert_vec3_equal!(Vec3::new(2.0, 5.0, 9.0), result);
+        let empty: Vec3 = std::iter::empty::<Vec3>().product();
+        assert_vec3_equal!(Vec3::ONE, empty);
+    }
+
+    #[test]
+    fn get_and_set_by_axis() {
+        let mut vector = Vec3::new(1.0, 2.0, 3.0);
+        let values: Vec<f64> = Axis::ALL.iter().map(|&axis| vector.get(axis)).collect();
+        assert_eq!(vec![1.0, 2.0, 3.0], values);
+
+        vector.set(Axis::Y, 5.0);
+        assert_vec3_equal!(Vec3::new(1.0, 5.0, 3.0), vector);
+        for &axis in Axis::ALL.iter() {
+            vector.set(axis, 0.0);
+        }
+        assert_vec3_equal!(Vec3::ZERO, vector);
+    }
+
+    #[test]
+    fn permute() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(vector, vector.permute(Axis::X, Axis::Y, Axis::Z));
+        assert_vec3_equal!(Vec3::new(3.0, 1.0, 2.0), vector.permute(Axis::Z, Axis::X, Axis::Y));
+        assert_vec3_equal!(Vec3::new(2.0, 2.0, 1.0), vector.permute(Axis::Y, Axis::Y, Axis::X));
+    }
+
+    #[test]
+    fn display() {
+        let vector = Vec3::new(1.0, -2.5, 1.0 / 3.0);
+        assert_eq!("(1, -2.5, 0.3333333333333333)", format!("{}", vector));
+        assert_eq!("(1.000, -2.500, 0.333)", format!("{:.3}", vector));
+        assert_eq!("(  1.0,  -2.5,   0.3)", format!("{:5.1}", vector));
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!("Vec3 { x: 1.0, y: -2.5, z: 0.0 }", format!("{:?}", Vec3::new(1.0, -2.5, 0.0)));
+        assert_eq!(Vec3::new(1.0, 2.0, 3.0), Vec3::from([1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn lower_exp() {
+        let vector = Vec3::new(1500.0, -0.025, 0.0);
+        assert_eq!("(1.5e3, -2.5e-2, 0e0)", format!("{:e}", vector));
+        assert_eq!("(1.50e3, -2.50e-2, 0.00e0)", format!("{:.2e}", vector));
+    }
+
+    #[test]
+    fn index() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_approx_eq!(1.0, vector[0], 0.001);
+        assert_approx_eq!(2.0, vector[1], 0.001);
+        assert_approx_eq!(3.0, vector[2], 0.001);
+    }
+
+    #[test]
+    fn index_mut() {
+        let mut vector = Vec3::new(1.0, 2.0, 3.0);
+        for axis in 0..3 {
+            vector[axis] *= 2.0;
+        }
+        let expected = Vec3::new(2.0, 4.0, 6.0);
+        assert_vec3_equal!(expected, vector);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vec3 index out of range: 3")]
+    fn index_out_of_range() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        let _ = vector[3];
+    }
+
+    #[test]
+    fn length() {
+        let vector = Vec3::new(3.0, 4.0, 5.0);
+        let result = vector.length();
+        assert_approx_eq!(7.07168, result, 0.001);
+    }
+
+    #[test]
+    fn length_squared() {
+        let vector = Vec3::new(3.0, 4.0, 5.0);
+        let result = vector.length_squared();
+        assert_approx_eq!(50.0, result, 0.001);
+    }
+
+    #[test]
+    fn distance() {
+        let point1 = Vec3::new(1.0, 2.0, 3.0);
+        let point2 = Vec3::new(4.0, 6.0, 3.0);
+        assert_approx_eq!(5.0, point1.distance(point2), 0.001);
+        assert_approx_eq!(5.0, point2.distance(point1), 0.001);
+    }
+
+    #[test]
+    fn distance_squared() {
+        let point1 = Vec3::new(1.0, 2.0, 3.0);
+        let point2 = Vec3::new(4.0, 6.0, 3.0);
+        assert_approx_eq!(25.0, point1.distance_squared(point2), 0.001);
+    }
+
+    #[test]
+    fn near_zero() {
+        assert!(Vec3::ZERO.near_zero());
+        assert!(Vec3::new(1e-9, -1e-9, 0.0).near_zero());
+        assert!(!Vec3::new(1e-9, 0.0, 1e-7).near_zero());
+    }
+
+    #[test]
+    fn unit_vector() {
+        let vector = Vec3::new(3.0, 4.0, 0.0);
+        let result = vector.unit_vector();
+        let expected = Vec3::new(0.6, 0.8, 0.0);
+        assert_vec3_equal!(expected, result);
+        assert_approx_eq!(1.0, result.length(), 0.001);
+    }
+
+    #[test]
+    fn unit_vector_of_zero_vector() {
+        let result = Vec3::ZERO.unit_vector();
+        assert_vec3_equal!(Vec3::ZERO, result);
+    }
+
+    #[test]
+    fn normalize() {
+        let mut vector = Vec3::new(0.0, 0.0, -2.0);
+        vector.normalize();
+        let expected = Vec3::new(0.0, 0.0, -1.0);
+        assert_vec3_equal!(expected, vector);
+    }
+
+    #[test]
+    fn recip() {
+        let vector = Vec3::new(2.0, -4.0, 0.5);
+        let result = vector.recip();
+        let expected = Vec3::new(0.5, -0.25, 2.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn recip_of_zero_components() {
+        let vector = Vec3::new(0.0, -0.0, 1.0);
+        let result = vector.recip();
+        assert_eq!(f64::INFINITY, result.x);
+        assert_eq!(f64::NEG_INFINITY, result.y);
+        assert_approx_eq!(1.0, result.z, 0.001);
+    }
+
+    #[test]
+    fn swizzle_vec2() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec2_equal!(Vec2::new(1.0, 2.0), vector.xy());
+        assert_vec2_equal!(Vec2::new(1.0, 3.0), vector.xz());
+        assert_vec2_equal!(Vec2::new(2.0, 1.0), vector.yx());
+        assert_vec2_equal!(Vec2::new(2.0, 3.0), vector.yz());
+        assert_vec2_equal!(Vec2::new(3.0, 1.0), vector.zx());
+        assert_vec2_equal!(Vec2::new(3.0, 2.0), vector.zy());
+        assert_vec3_equal!(vector, vector.xy().extend(3.0));
+    }
+
+    #[test]
+    fn swizzle_vec3() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(Vec3::new(1.0, 3.0, 2.0), vector.xzy());
+        assert_vec3_equal!(Vec3::new(2.0, 1.0, 3.0), vector.yxz());
+        assert_vec3_equal!(Vec3::new(2.0, 3.0, 1.0), vector.yzx());
+        assert_vec3_equal!(Vec3::new(3.0, 1.0, 2.0), vector.zxy());
+        assert_vec3_equal!(Vec3::new(3.0, 2.0, 1.0), vector.zyx());
+    }
+
+    #[test]
+    fn with_component() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(Vec3::new(9.0, 2.0, 3.0), vector.with_x(9.0));
+        assert_vec3_equal!(Vec3::new(1.0, 9.0, 3.0), vector.with_y(9.0));
+        assert_vec3_equal!(Vec3::new(1.0, 2.0, 9.0), vector.with_z(9.0));
+    }
+
+    #[test]
+    fn map() {
+        let vector = Vec3::new(1.0, -2.0, 3.0);
+        assert_vec3_equal!(Vec3::new(2.0, -4.0, 6.0), vector.map(|c| c * 2.0));
+        assert_vec3_equal!(Vec3::new(1.0, 0.0, 1.0), vector.map(|c| c.clamp(0.0, 1.0)));
+    }
+
+    #[test]
+    fn zip_map() {
+        let a = Vec3::new(1.0, 5.0, 3.0);
+        let b = Vec3::new(4.0, 2.0, 3.0);
+        assert_vec3_equal!(Vec3::new(4.0, 10.0, 9.0), a.zip_map(b, |p, q| p * q));
+        assert_vec3_equal!(Vec3::new(1.0, 2.0, 3.0), a.zip_map(b, f64::min));
+    }
+
+    #[test]
+    fn sqrt() {
+        assert_vec3_equal!(Vec3::new(0.0, 0.5, 3.0), Vec3::new(0.0, 0.25, 9.0).sqrt());
+        assert!(Vec3::new(-1.0, 1.0, 1.0).sqrt().x().is_nan());
+    }
+
+    #[test]
+    fn signum() {
+        assert_vec3_equal!(Vec3::new(1.0, -1.0, 0.0), Vec3::new(2.5, -0.1, 0.0).signum());
+        assert_vec3_equal!(Vec3::new(0.0, 1.0, -1.0), Vec3::new(-0.0, f64::INFINITY, -1e-300).signum());
+        assert!(Vec3::new(f64::NAN, 1.0, 1.0).signum().has_nan());
+    }
+
+    #[test]
+    fn floor_ceil_round() {
+        let vector = Vec3::new(1.5, -1.5, 2.25);
+        assert_vec3_equal!(Vec3::new(1.0, -2.0, 2.0), vector.floor());
+        assert_vec3_equal!(Vec3::new(2.0, -1.0, 3.0), vector.ceil());
+        assert_vec3_equal!(Vec3::new(2.0, -2.0, 2.0), vector.round());
+    }
+
+    #[test]
+    fn fract() {
+        assert_vec3_equal!(Vec3::new(0.5, 0.5, 0.0), Vec3::new(1.5, -1.5, 3.0).fract());
+        assert_vec3_equal!(Vec3::new(0.75, 0.25, 0.0), Vec3::new(-0.25, 10.25, -2.0).fract());
+    }
+
+    #[test]
+    fn powf_exp_ln() {
+        let vector = Vec3::new(1.0, 2.0, 4.0);
+        assert_vec3_equal!(Vec3::new(1.0, 8.0, 64.0), vector.powf(3.0));
+        assert_vec3_equal!(Vec3::new(1.0, 2.0, 4.0), vector.powf(2.2).powf(1.0 / 2.2));
+        assert_vec3_equal!(Vec3::new(1.0, std::f64::consts::E, std::f64::consts::E.powi(2)), Vec3::new(0.0, 1.0, 2.0).exp());
+        assert_vec3_equal!(vector, vector.ln().exp());
+    }
+
+    #[test]
+    fn finiteness() {
+        assert!(Vec3::new(1.0, -2.0, 3.0).is_finite());
+        assert!(!Vec3::new(1.0, f64::INFINITY, 3.0).is_finite());
+        assert!(!Vec3::new(f64::NAN, 2.0, 3.0).is_finite());
+        assert!(Vec3::new(1.0, 2.0, f64::NAN).has_nan());
+        assert!(!Vec3::new(f64::NEG_INFINITY, 2.0, 3.0).has_nan());
+        assert_vec3_equal!(Vec3::ONE, Vec3::ONE.debug_assert_finite());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "non-finite Vec3")]
+    fn debug_assert_finite_panics_on_nan() {
+        Vec3::new(0.0, f64::NAN, 0.0).debug_assert_finite();
+    }
+
+    #[test]
+    fn reductions() {
+        let vector = Vec3::new(2.0, -1.0, 5.0);
+        assert_approx_eq!(-1.0, vector.min_element(), 0.0001);
+        assert_approx_eq!(5.0, vector.max_element(), 0.0001);
+        assert_approx_eq!(6.0, vector.component_sum(), 0.0001);
+    }
+
+    #[test]
+    fn luminance() {
+        assert_approx_eq!(1.0, Vec3::ONE.luminance(), 0.0001);
+        assert_approx_eq!(0.0, Vec3::ZERO.luminance(), 0.0001);
+        assert_approx_eq!(0.7152, Vec3::Y.luminance(), 0.0001);
+    }
+
+    #[test]
+    fn try_normalize() {
+        let result = Vec3::new(3.0, 0.0, 4.0).try_normalize().unwrap();
+        assert_vec3_equal!(Vec3::new(0.6, 0.0, 0.8), result);
+        assert!(Vec3::ZERO.try_normalize().is_none());
+        assert!(Vec3::new(1.0, f64::NAN, 0.0).try_normalize().is_none());
+        assert!(Vec3::new(f64::INFINITY, 0.0, 0.0).try_normalize().is_none());
+    }
+
+    #[test]
+    fn normalize_or() {
+        assert_vec3_equal!(Vec3::Y, Vec3::new(0.0, 2.0, 0.0).normalize_or(Vec3::X));
+        assert_vec3_equal!(Vec3::X, Vec3::ZERO.normalize_or(Vec3::X));
+        assert_vec3_equal!(Vec3::Z, Vec3::new(f64::NAN, 0.0, 0.0).normalize_or(Vec3::Z));
+    }
+
+    #[test]
+    fn dot(){
+        let vector1 = Vec3::new(1.0, 2.0, 3.0);
+        let vector2 = Vec3::new(1.0, 5.0, 7.0);
+        let result = vector1.dot(vector2);
+        assert_approx_eq!(32.0, result, 0.001);
+    }
+
+    #[test]
+    fn angle_between() {
+        let vector1 = Vec3::new(2.0, 0.0, 0.0);
+        let vector2 = Vec3::new(1.0, 1.0, 0.0);
+        assert_approx_eq!(std::f64::consts::FRAC_PI_4, vector1.angle_between(vector2), 0.001);
+        assert_approx_eq!(std::f64::consts::PI, vector1.angle_between(-vector1), 0.001);
+    }
+
+    #[test]
+    fn angle_between_parallel_vectors() {
+        let vector = Vec3::new(0.1, 0.2, 0.3);
+        let result = vector.angle_between(vector * 3.0);
+        assert!(!result.is_nan());
+        assert_approx_eq!(0.0, result, 0.0001);
+    }
+
+    #[test]
+    fn from_spherical() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+        assert_vec3_equal!(Vec3::Z * 2.0, Vec3::from_spherical(2.0, 0.0, 0.0));
+        assert_vec3_equal!(Vec3::X, Vec3::from_spherical(1.0, FRAC_PI_2, 0.0));
+        assert_vec3_equal!(Vec3::Y, Vec3::from_spherical(1.0, FRAC_PI_2, FRAC_PI_2));
+        assert_vec3_equal!(-Vec3::X, Vec3::from_spherical(1.0, FRAC_PI_2, PI));
+    }
+
+    #[test]
+    fn spherical_round_trip() {
+        let vector = Vec3::new(1.0, -2.0, 0.5);
+        let (r, theta, phi) = vector.to_spherical();
+        assert_approx_eq!(vector.length(), r, 0.0001);
+        assert!((0.0..=std::f64::consts::PI).contains(&theta));
+        assert!(phi < 0.0);
+        assert_vec3_equal!(vector, Vec3::from_spherical(r, theta, phi));
+    }
+
+    #[test]
+    fn to_spherical_of_zero_vector() {
+        assert_eq!((0.0, 0.0, 0.0), Vec3::ZERO.to_spherical());
+    }
+
+    #[test]
+    fn project_onto() {
+        let vector1 = Vec3::new(2.0, 3.0, 4.0);
+        let vector2 = Vec3::new(0.0, 2.0, 0.0);
+        let result = vector1.project_onto(vector2);
+        let expected = Vec3::new(0.0, 3.0, 0.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn reject_from() {
+        let vector1 = Vec3::new(2.0, 3.0, 4.0);
+        let vector2 = Vec3::new(1.0, 1.0, 0.0);
+        let result = vector1.reject_from(vector2);
+        let expected = Vec3::new(-0.5, 0.5, 4.0);
+        assert_vec3_equal!(expected, result);
+        assert_approx_eq!(0.0, result.dot(vector2), 0.001);
+    }
+
+    #[test]
+    fn cross(){
+        let vector1 = Vec3::new(1.0, 2.0, 3.0);
+        let vector2 = Vec3::new(1.0, 5.0, 7.0);
+        let result = vector1.cross(vector2);
+        let expected = Vec3::new(-1.0, -4.0, 3.0);
+        assert_vec3_equal!(expected, result);
 
-        let result = vector1 * vector2;
-        let expected = Vec3::new(4.0, 10.0, 18.0);
+    }
+
+    #[test]
+    fn scalar_triple() {
+        assert_approx_eq!(1.0, Vec3::scalar_triple(Vec3::X, Vec3::Y, Vec3::Z), 0.0001);
+        assert_approx_eq!(-1.0, Vec3::scalar_triple(Vec3::Y, Vec3::X, Vec3::Z), 0.0001);
+
+        // Six times the volume of the tetrahedron with these edges.
+        let a = Vec3::new(2.0, 0.0, 0.0);
+        let b = Vec3::new(1.0, 3.0, 0.0);
+        let c = Vec3::new(1.0, 1.0, 4.0);
+        assert_approx_eq!(24.0, Vec3::scalar_triple(a, b, c), 0.0001);
+        assert_approx_eq!(0.0, Vec3::scalar_triple(a, b, a + b), 0.0001);
+    }
+
+    #[test]
+    fn vector_triple() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(-2.0, 0.5, 1.0);
+        let c = Vec3::new(4.0, -1.0, 2.0);
+        let expected = b * a.dot(c) - c * a.dot(b);
+        assert_vec3_equal!(expected, Vec3::vector_triple(a, b, c));
+        assert_vec3_equal!(Vec3::ZERO, Vec3::vector_triple(Vec3::X, Vec3::X, Vec3::Y) + Vec3::Y);
+    }
+
+    #[test]
+    fn lerp() {
+        let vector1 = Vec3::new(1.0, 1.0, 1.0);
+        let vector2 = Vec3::new(0.5, 0.7, 1.0);
+        let result = vector1.lerp(vector2, 0.25);
+        let expected = Vec3::new(0.875, 0.925, 1.0);
         assert_vec3_equal!(expected, result);
     }
 
     #[test]
-    fn division() {
-        let vector = Vec3::new(12.0, 13.5, 15.0);
-        let divisor = 3.0;
-        let result = vector / divisor;
-        let expected = Vec3::new(4.0, 4.5, 5.0);
+    fn lerp_endpoints_are_exact() {
+        let vector1 = Vec3::new(0.1, 1e10, -3.3);
+        let vector2 = Vec3::new(0.7, 1e-10, 2.2);
+        assert!(vector1 == vector1.lerp(vector2, 0.0));
+        assert!(vector2 == vector1.lerp(vector2, 1.0));
+    }
+
+    #[test]
+    fn abs() {
+        let vector = Vec3::new(-1.0, 2.0, -0.5);
+        let result = vector.abs();
+        let expected = Vec3::new(1.0, 2.0, 0.5);
         assert_vec3_equal!(expected, result);
     }
 
     #[test]
-    fn length() {
-        let vector = Vec3::new(3.0, 4.0, 5.0);
-        let result = vector.length();
-        assert_approx_eq!(7.07168, result, 0.001);
+    fn min() {
+        let vector1 = Vec3::new(1.0, 5.0, -3.0);
+        let vector2 = Vec3::new(2.0, 4.0, -6.0);
+        let result = vector1.min(vector2);
+        let expected = Vec3::new(1.0, 4.0, -6.0);
+        assert_vec3_equal!(expected, result);
     }
 
     #[test]
-    fn length_squared() {
-        let vector = Vec3::new(3.0, 4.0, 5.0);
-        let result = vector.length_squared();
-        assert_approx_eq!(50.0, result, 0.001);
+    fn max() {
+        let vector1 = Vec3::new(1.0, 5.0, -3.0);
+        let vector2 = Vec3::new(2.0, 4.0, -6.0);
+        let result = vector1.max(vector2);
+        let expected = Vec3::new(2.0, 5.0, -3.0);
+        assert_vec3_equal!(expected, result);
     }
 
     #[test]
-    fn dot(){
-        let vector1 = Vec3::new(1.0, 2.0, 3.0);
-        let vector2 = Vec3::new(1.0, 5.0, 7.0);
-        let result = vector1.dot(vector2);
-        assert_approx_eq!(32.0, result, 0.001);
+    fn step() {
+        let edge = Vec3::new(0.5, 0.5, -1.0);
+        assert_vec3_equal!(Vec3::new(0.0, 1.0, 1.0), Vec3::step(edge, Vec3::new(0.25, 0.5, 0.0)));
     }
 
     #[test]
-    fn cross(){
-        let vector1 = Vec3::new(1.0, 2.0, 3.0);
-        let vector2 = Vec3::new(1.0, 5.0, 7.0);
-        let result = vector1.cross(vector2);
-        let expected = Vec3::new(-1.0, -4.0, 3.0);
+    fn smoothstep() {
+        let edge0 = Vec3::new(0.0, 1.0, 0.0);
+        let edge1 = Vec3::new(1.0, 3.0, 2.0);
+        let result = Vec3::smoothstep(edge0, edge1, Vec3::new(0.5, 2.0, 3.0));
+        assert_vec3_equal!(Vec3::new(0.5, 0.5, 1.0), result);
+        assert_vec3_equal!(Vec3::new(0.15625, 0.0, 0.0), Vec3::smoothstep(edge0, edge1, Vec3::new(0.25, -1.0, 0.0)));
+    }
+
+    #[test]
+    fn clamp() {
+        let vector = Vec3::new(-0.5, 0.5, 1.5);
+        let result = vector.clamp(0.0, 1.0);
+        let expected = Vec3::new(0.0, 0.5, 1.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn clamp_vec() {
+        let vector = Vec3::new(-0.5, 0.5, 1.5);
+        let min = Vec3::new(0.0, 0.6, -1.0);
+        let max = Vec3::new(1.0, 1.0, 1.2);
+        let result = vector.clamp_vec(min, max);
+        let expected = Vec3::new(0.0, 0.6, 1.2);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn rotate_axes() {
+        let quarter = std::f64::consts::FRAC_PI_2;
+        assert_vec3_equal!(Vec3::Z, Vec3::Y.rotate_x(quarter));
+        assert_vec3_equal!(Vec3::X, Vec3::Z.rotate_y(quarter));
+        assert_vec3_equal!(Vec3::Y, Vec3::X.rotate_z(quarter));
+    }
+
+    #[test]
+    fn rotate_about_axis() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        let angle = 0.6;
+        assert_vec3_equal!(vector.rotate_x(angle), vector.rotate_about_axis(Vec3::X * 2.0, angle));
+        assert_vec3_equal!(vector.rotate_z(angle), vector.rotate_about_axis(Vec3::Z, angle));
+
+        let third = 2.0 * std::f64::consts::PI / 3.0;
+        assert_vec3_equal!(Vec3::Y, Vec3::X.rotate_about_axis(Vec3::ONE, third));
+    }
+
+    #[test]
+    fn reflect() {
+        let vector = Vec3::new(1.0, -1.0, 0.0);
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let result = vector.reflect(normal);
+        let expected = Vec3::new(1.0, 1.0, 0.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn reflect_across_plane() {
+        let point = Vec3::new(1.0, 5.0, -2.0);
+        assert_vec3_equal!(Vec3::new(1.0, -1.0, -2.0), point.reflect_across_plane(Vec3::Y, 2.0));
+        assert_vec3_equal!(Vec3::new(1.0, -1.0, -2.0), point.reflect_across_plane(Vec3::Y * 2.0, 4.0));
+
+        // Points on the plane stay put, and reflecting twice is the identity.
+        let normal = Vec3::new(1.0, 1.0, 1.0);
+        assert_vec3_equal!(Vec3::ONE, Vec3::ONE.reflect_across_plane(normal, 3.0));
+        assert_vec3_equal!(point, point.reflect_across_plane(normal, 1.5).reflect_across_plane(normal, 1.5));
+    }
+
+    #[test]
+    fn reflect_keeps_length() {
+        let vector = Vec3::new(2.0, -3.0, 1.0);
+        let normal = Vec3::new(1.0, 1.0, 0.0).unit_vector();
+        let result = vector.reflect(normal);
+        assert_approx_eq!(vector.length(), result.length(), 0.001);
+        assert_approx_eq!(-vector.dot(normal), result.dot(normal), 0.001);
+    }
+
+    #[test]
+    fn refract_straight_through() {
+        let vector = Vec3::new(0.0, -1.0, 0.0);
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let result = vector.refract(normal, 1.0 / 1.5);
+        assert_vec3_equal!(vector, result);
+    }
+
+    #[test]
+    fn refract_snells_law() {
+        let incident_angle: f64 = 0.5;
+        let vector = Vec3::new(incident_angle.sin(), -incident_angle.cos(), 0.0);
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let etai_over_etat = 1.0 / 1.5;
+        let result = vector.refract(normal, etai_over_etat);
+        let sin_refracted = result.x / result.length();
+        assert_approx_eq!(etai_over_etat * incident_angle.sin(), sin_refracted, 0.001);
+        assert_approx_eq!(1.0, result.length(), 0.001);
+        assert!(result.y < 0.0);
+    }
+
+    #[test]
+    fn try_refract_total_internal_reflection() {
+        let incident_angle: f64 = 1.2;
+        let vector = Vec3::new(incident_angle.sin(), -incident_angle.cos(), 0.0);
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        assert!(vector.try_refract(normal, 1.5).is_none());
+        assert!(vector.try_refract(normal, 1.0 / 1.5).is_some());
+    }
+
+    #[test]
+    fn from_array() {
+        let result = Vec3::from([1.0, 2.0, 3.0]);
+        let expected = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn from_tuple() {
+        let result: Vec3 = (1.0, 2.0, 3.0).into();
+        let expected = Vec3::new(1.0, 2.0, 3.0);
         assert_vec3_equal!(expected, result);
+    }
+
+    #[test]
+    fn from_str() {
+        let expected = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec3_equal!(expected, "1.0, 2.0, 3.0".parse::<Vec3>().unwrap());
+        assert_vec3_equal!(expected, "(1 2 3)".parse::<Vec3>().unwrap());
+        assert_vec3_equal!(expected, "  (1,2,3) ".parse::<Vec3>().unwrap());
+        assert_vec3_equal!(Vec3::new(-0.5, 1e3, 0.0), "-0.5\t1e3  0".parse::<Vec3>().unwrap());
+        assert_vec3_equal!(Vec3f::new(1.0, 2.0, 3.0), "1, 2, 3".parse::<Vec3f>().unwrap());
+    }
+
+    #[test]
+    fn from_str_errors() {
+        assert_eq!(Err(ParseVec3Error::UnbalancedParentheses), "(1, 2, 3".parse::<Vec3>().map(|_| ()));
+        assert_eq!(Err(ParseVec3Error::WrongComponentCount(2)), "1, 2".parse::<Vec3>().map(|_| ()));
+        assert_eq!(Err(ParseVec3Error::WrongComponentCount(4)), "(1 2 3 4)".parse::<Vec3>().map(|_| ()));
+        assert_eq!(Err(ParseVec3Error::WrongComponentCount(0)), "".parse::<Vec3>().map(|_| ()));
+        assert_eq!(Err(ParseVec3Error::InvalidComponent(Axis::Y)), "1, y, 3".parse::<Vec3>().map(|_| ()));
+        assert_eq!(Err(ParseVec3Error::InvalidComponent(Axis::Z)), "(1 2 three)".parse::<Vec3>().map(|_| ()));
+        assert_eq!(Err(ParseVec3Error::InvalidComponent(Axis::Y)), "1,,3".parse::<Vec3>().map(|_| ()));
+        assert_eq!("invalid y component in vector", ParseVec3Error::InvalidComponent(Axis::Y).to_string());
+        assert_eq!("invalid z component in vector", ParseVec3Error::InvalidComponent(Axis::Z).to_string());
+    }
 
+    #[test]
+    fn into_array_and_tuple() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        let array: [f64; 3] = vector.into();
+        let tuple: (f64, f64, f64) = vector.into();
+        assert_eq!([1.0, 2.0, 3.0], array);
+        assert_eq!((1.0, 2.0, 3.0), tuple);
+        assert_eq!(array, vector.as_array());
     }
 
     #[test]
-    fn to_rgb(){
+    fn to_rgb8(){
         let vector = Vec3::new(-1.0, 0.5, 1.4);
-        let result = vector.to_rgb();
+        let result = vector.to_rgb8();
         let expected = [0u8, 127u8, 255u8];
         assert_eq!(expected, result);
+        assert_eq!([0u8, 0u8, 255u8], Vec3::new(f64::NAN, 0.0, 1.0).to_rgb8());
     }
-}
+
+    #[test]
+    fn to_rgb8_gamma() {
+        let vector = Vec3::new(0.25, 0.0, 1.0);
+        assert_eq!([127u8, 0u8, 255u8], vector.to_rgb8_gamma(2.0));
+        assert_eq!(vector.to_rgb8(), vector.to_rgb8_gamma(1.0));
+        assert_eq!([0u8, 186u8, 255u8], Vec3::new(-0.5, 0.5, 2.0).to_rgb8_gamma(2.2));
+    }
+
+    #[test]
+    fn to_bits() {
+        let vector = Vec3::new(1.0, -0.0, 0.0);
+        let bits = vector.to_bits();
+        assert_eq!(1.0f64.to_bits(), bits[0]);
+        assert_ne!(bits[1], bits[2]);
+    }
+
+    #[test]
+    fn ordered_vec3_as_hash_key() {
+        let mut indices = std::collections::HashMap::new();
+        let vertices = [
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(-0.0, 1.0, 0.0),
+        ];
+        for vertex in vertices.iter() {
+            let next = indices.len();
+            indices.entry(OrderedVec3(*vertex)).or_insert(next);
+        }
+        assert_eq!(2, indices.len());
+        assert_eq!(Some(&1), indices.get(&OrderedVec3::from(Vec3::new(0.0, 1.0, 0.0))));
+    }
+
+    #[test]
+    fn ordered_vec3_nan_equals_itself() {
+        let vector = OrderedVec3(Vec3::new(f64::NAN, 0.0, 0.0));
+        assert!(vector == vector);
+        assert!(OrderedVec3(Vec3::new(1.0, 2.0, 3.0)) != OrderedVec3(Vec3::new(1.0, 2.0, 3.000001)));
+    }
+
+    #[test]
+    fn f32_operators() {
+        let vector1 = Vec3f::new(1.0, 2.0, 3.0);
+        let vector2 = Vec3f::new(4.0, 5.0, 6.0);
+        assert_vec3_equal!(Vec3f::new(5.0, 7.0, 9.0), vector1 + vector2);
+        assert_vec3_equal!(Vec3f::new(-3.0, -3.0, -3.0), vector1 - vector2);
+        assert_vec3_equal!(Vec3f::new(-1.0, -2.0, -3.0), -vector1);
+        assert_vec3_equal!(Vec3f::new(5.0, 10.0, 15.0), 5.0f32 * vector1);
+        assert_vec3_equal!(Vec3f::new(5.0, 10.0, 15.0), vector1 * 5.0);
+        assert_vec3_equal!(Vec3f::new(4.0, 10.0, 18.0), vector1 * vector2);
+        assert_vec3_equal!(Vec3f::new(0.5, 1.0, 1.5), vector1 / 2.0);
+    }
+
+    #[test]
+    fn f32_assignment_operators() {
+        let mut vector = Vec3f::new(1.0, 2.0, 3.0);
+        vector += Vec3f::ONE;
+        vector -= Vec3f::X;
+        vector *= 2.0;
+        vector *= Vec3f::new(1.0, 0.5, 2.0);
+        vector /= 4.0;
+        assert_vec3_equal!(Vec3f::new(0.5, 0.75, 4.0), vector);
+    }
+
+    #[test]
+    fn f32_products_and_length() {
+        let vector1 = Vec3f::new(1.0, 2.0, 3.0);
+        let vector2 = Vec3f::new(1.0, 5.0, 7.0);
+        assert_approx_eq!(32.0f32, vector1.dot(vector2), 0.001);
+        assert_vec3_equal!(Vec3f::new(-1.0, -4.0, 3.0), vector1.cross(vector2));
+        assert_approx_eq!(50.0f32, Vec3f::new(3.0, 4.0, 5.0).length_squared(), 0.001);
+        assert_vec3_equal!(Vec3f::new(0.6, 0.8, 0.0), Vec3f::new(3.0, 4.0, 0.0).unit_vector());
+    }
+
+    #[test]
+    fn f32_reflect_and_refract() {
+        let vector = Vec3f::new(1.0, -1.0, 0.0);
+        assert_vec3_equal!(Vec3f::new(1.0, 1.0, 0.0), vector.reflect(Vec3f::Y));
+        let straight = Vec3f::new(0.0, -1.0, 0.0);
+        assert_vec3_equal!(straight, straight.refract(Vec3f::Y, 1.0 / 1.5));
+    }
+
+    #[test]
+    fn f32_conversions() {
+        let vector = Vec3f::from([1.0, 2.0, 3.0]);
+        let array: [f32; 3] = vector.into();
+        let tuple: (f32, f32, f32) = vector.into();
+        assert_eq!([1.0, 2.0, 3.0], array);
+        assert_eq!((1.0, 2.0, 3.0), tuple);
+        assert_eq!([0u8, 127u8, 255u8], Vec3f::new(-1.0, 0.5, 1.4).to_rgb8());
+    }
+
+    #[test]
+    fn f32_random() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let vector = Vec3f::random_unit_vector(&mut rng);
+            assert_approx_eq!(1.0f32, vector.length(), 0.0001);
+        }
+    }
+
+    #[test]
+    fn vec2_constants() {
+        assert_vec2_equal!(Vec2::new(0.0, 0.0), Vec2::ZERO);
+        assert_vec2_equal!(Vec2::new(1.0, 1.0), Vec2::ONE);
+        assert_vec2_equal!(Vec2::new(2.0, -3.0), 2.0 * Vec2::X - 3.0 * Vec2::Y);
+        assert_vec2_equal!(Vec2::ZERO, Vec2::default());
+    }
+
+    #[test]
+    fn vec2_accessors() {
+        let vector = Vec2::new(1.0, 2.0);
+        assert_approx_eq!(1.0, vector.x(), 0.001);
+        assert_approx_eq!(2.0, vector.y(), 0.001);
+    }
+
+    #[test]
+    fn vec2_addition() {
+        let result = Vec2::new(1.0, 2.0) + Vec2::new(4.0, 5.0);
+        assert_vec2_equal!(Vec2::new(5.0, 7.0), result);
+    }
+
+    #[test]
+    fn vec2_substraction() {
+        let result = Vec2::new(1.0, 2.0) - Vec2::new(4.0, 5.0);
+        assert_vec2_equal!(Vec2::new(-3.0, -3.0), result);
+    }
+
+    #[test]
+    fn vec2_negation() {
+        let result = -Vec2::new(1.0, -2.0);
+        assert_vec2_equal!(Vec2::new(-1.0, 2.0), result);
+    }
+
+    #[test]
+    fn vec2_multiplication() {
+        let vector = Vec2::new(1.0, 2.0);
+        let expected = Vec2::new(5.0, 10.0);
+        assert_vec2_equal!(expected, 5.0 * vector);
+        assert_vec2_equal!(expected, vector * 5.0);
+    }
+
+    #[test]
+    fn vec2_hadamard_product() {
+        let result = Vec2::new(1.0, 2.0) * Vec2::new(4.0, 5.0);
+        assert_vec2_equal!(Vec2::new(4.0, 10.0), result);
+    }
+
+    #[test]
+    fn vec2_division() {
+        let result = Vec2::new(12.0, 13.5) / 3.0;
+        assert_vec2_equal!(Vec2::new(4.0, 4.5), result);
+    }
+
+    #[test]
+    fn vec2_assignment_operators() {
+        let mut vector = Vec2::new(1.0, 2.0);
+        vector += Vec2::new(1.0, 1.0);
+        assert_vec2_equal!(Vec2::new(2.0, 3.0), vector);
+        vector -= Vec2::new(0.5, 0.5);
+        assert_vec2_equal!(Vec2::new(1.5, 2.5), vector);
+        vector *= 2.0;
+        assert_vec2_equal!(Vec2::new(3.0, 5.0), vector);
+        vector *= Vec2::new(2.0, 0.5);
+        assert_vec2_equal!(Vec2::new(6.0, 2.5), vector);
+        vector /= 2.0;
+        assert_vec2_equal!(Vec2::new(3.0, 1.25), vector);
+    }
+
+    #[test]
+    fn vec2_index() {
+        let mut vector = Vec2::new(1.0, 2.0);
+        vector[1] = 4.0;
+        assert_approx_eq!(1.0, vector[0], 0.001);
+        assert_approx_eq!(4.0, vector[1], 0.001);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vec2 index out of range: 2")]
+    fn vec2_index_out_of_range() {
+        let vector = Vec2::new(1.0, 2.0);
+        let _ = vector[2];
+    }
+
+    #[test]
+    fn vec2_dot() {
+        let result = Vec2::new(1.0, 2.0).dot(Vec2::new(3.0, 4.0));
+        assert_approx_eq!(11.0, result, 0.001);
+    }
+
+    #[test]
+    fn vec2_length() {
+        let vector = Vec2::new(3.0, 4.0);
+        assert_approx_eq!(25.0, vector.length_squared(), 0.001);
+        assert_approx_eq!(5.0, vector.length(), 0.001);
+    }
+
+    #[test]
+    fn vec2_unit_vector() {
+        let mut vector = Vec2::new(3.0, 4.0);
+        assert_vec2_equal!(Vec2::new(0.6, 0.8), vector.unit_vector());
+        vector.normalize();
+        assert_vec2_equal!(Vec2::new(0.6, 0.8), vector);
+        assert_vec2_equal!(Vec2::ZERO, Vec2::ZERO.unit_vector());
+    }
+
+    #[test]
+    fn vec2_conversions() {
+        let vector = Vec2::new(1.0, 2.0);
+        assert!(vector == Vec2::from([1.0, 2.0]));
+        assert!(vector == Vec2::from((1.0, 2.0)));
+        let array: [f64; 2] = vector.into();
+        let tuple: (f64, f64) = vector.into();
+        assert_eq!([1.0, 2.0], array);
+        assert_eq!((1.0, 2.0), tuple);
+    }
+
+    #[test]
+    fn vec2_approx_eq() {
+        let vector = Vec2::new(1.0, 2.0);
+        assert!(vector.approx_eq(Vec2::new(1.00001, 2.0), 0.0001));
+        assert!(!vector.approx_eq(Vec2::new(1.001, 2.0), 0.0001));
+    }
+
+    #[test]
+    fn vec2_display() {
+        assert_eq!("(1.5, -2)", format!("{}", Vec2::new(1.5, -2.0)));
+    }
+
+    #[test]
+    fn vec4_from_point_and_direction() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        let point = Vec4::from_point(vector);
+        let direction = Vec4::from_direction(vector);
+        assert_vec4_equal!(Vec4::new(1.0, 2.0, 3.0, 1.0), point);
+        assert_vec4_equal!(Vec4::new(1.0, 2.0, 3.0, 0.0), direction);
+        assert_vec3_equal!(vector, point.xyz());
+    }
+
+    #[test]
+    fn vec3_to_homogeneous() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_vec4_equal!(Vec4::new(1.0, 2.0, 3.0, 1.0), vector.to_point4());
+        assert_vec4_equal!(Vec4::new(1.0, 2.0, 3.0, 0.0), vector.to_dir4());
+        assert_vec3_equal!(vector, vector.to_point4().to_vec3());
+        assert_vec3_equal!(vector, vector.to_dir4().to_vec3());
+    }
+
+    #[test]
+    fn vec4_to_vec3_divides_by_w() {
+        assert_vec3_equal!(Vec3::new(0.5, 1.0, 1.5), Vec4::new(1.0, 2.0, 3.0, 2.0).to_vec3());
+        assert_vec3_equal!(Vec3::new(-1.0, -2.0, -3.0), Vec4::new(1.0, 2.0, 3.0, -1.0).to_vec3());
+    }
+
+    #[test]
+    fn vec4_accessors() {
+        let vector = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        assert_approx_eq!(1.0, vector.x(), 0.001);
+        assert_approx_eq!(2.0, vector.y(), 0.001);
+        assert_approx_eq!(3.0, vector.z(), 0.001);
+        assert_approx_eq!(4.0, vector.w(), 0.001);
+    }
+
+    #[test]
+    fn vec4_point_minus_point_is_direction() {
+        let point1 = Vec4::from_point(Vec3::new(1.0, 2.0, 3.0));
+        let point2 = Vec4::from_point(Vec3::new(4.0, 5.0, 6.0));
+        let result = point2 - point1;
+        assert_vec4_equal!(Vec4::new(3.0, 3.0, 3.0, 0.0), result);
+    }
+
+    #[test]
+    fn vec4_addition() {
+        let result = Vec4::new(1.0, 2.0, 3.0, 4.0) + Vec4::new(4.0, 5.0, 6.0, 7.0);
+        assert_vec4_equal!(Vec4::new(5.0, 7.0, 9.0, 11.0), result);
+    }
+
+    #[test]
+    fn vec4_substraction() {
+        let result = Vec4::new(1.0, 2.0, 3.0, 4.0) - Vec4::new(4.0, 5.0, 6.0, 7.0);
+        assert_vec4_equal!(Vec4::new(-3.0, -3.0, -3.0, -3.0), result);
+    }
+
+    #[test]
+    fn vec4_negation() {
+        let result = -Vec4::new(1.0, -2.0, 3.0, -4.0);
+        assert_vec4_equal!(Vec4::new(-1.0, 2.0, -3.0, 4.0), result);
+    }
+
+    #[test]
+    fn vec4_multiplication() {
+        let vector = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let expected = Vec4::new(5.0, 10.0, 15.0, 20.0);
+        assert_vec4_equal!(expected, 5.0 * vector);
+        assert_vec4_equal!(expected, vector * 5.0);
+    }
+
+    #[test]
+    fn vec4_hadamard_product() {
+        let result = Vec4::new(1.0, 2.0, 3.0, 4.0) * Vec4::new(4.0, 5.0, 6.0, 7.0);
+        assert_vec4_equal!(Vec4::new(4.0, 10.0, 18.0, 28.0), result);
+    }
+
+    #[test]
+    fn vec4_division() {
+        let result = Vec4::new(12.0, 13.5, 15.0, 3.0) / 3.0;
+        assert_vec4_equal!(Vec4::new(4.0, 4.5, 5.0, 1.0), result);
+    }
+
+    #[test]
+    fn vec4_assignment_operators() {
+        let mut vector = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        vector += Vec4::new(1.0, 1.0, 1.0, 1.0);
+        assert_vec4_equal!(Vec4::new(2.0, 3.0, 4.0, 5.0), vector);
+        vector -= Vec4::new(0.5, 0.5, 0.5, 0.5);
+        assert_vec4_equal!(Vec4::new(1.5, 2.5, 3.5, 4.5), vector);
+        vector *= 2.0;
+        assert_vec4_equal!(Vec4::new(3.0, 5.0, 7.0, 9.0), vector);
+        vector *= Vec4::new(2.0, 0.5, 1.0, 0.0);
+        assert_vec4_equal!(Vec4::new(6.0, 2.5, 7.0, 0.0), vector);
+        vector /= 2.0;
+        assert_vec4_equal!(Vec4::new(3.0, 1.25, 3.5, 0.0), vector);
+    }
+
+    #[test]
+    fn vec4_index() {
+        let mut vector = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        vector[3] = 8.0;
+        assert_approx_eq!(3.0, vector[2], 0.001);
+        assert_approx_eq!(8.0, vector[3], 0.001);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vec4 index out of range: 4")]
+    fn vec4_index_out_of_range() {
+        let vector = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        let _ = vector[4];
+    }
+
+    #[test]
+    fn vec4_dot() {
+        let result = Vec4::new(1.0, 2.0, 3.0, 4.0).dot(Vec4::new(1.0, 5.0, 7.0, 2.0));
+        assert_approx_eq!(40.0, result, 0.001);
+    }
+
+    #[test]
+    fn vec4_length() {
+        let vector = Vec4::new(1.0, 2.0, 2.0, 4.0);
+        assert_approx_eq!(25.0, vector.length_squared(), 0.001);
+        assert_approx_eq!(5.0, vector.length(), 0.001);
+    }
+
+    #[test]
+    fn vec4_conversions() {
+        let vector = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        assert!(vector == Vec4::from([1.0, 2.0, 3.0, 4.0]));
+        let array: [f64; 4] = vector.into();
+        assert_eq!([1.0, 2.0, 3.0, 4.0], array);
+        assert!(vector.approx_eq(Vec4::new(1.0, 2.0, 3.0, 4.00001), 0.0001));
+    }
+
+    #[test]
+    fn vec4_display() {
+        assert_eq!("(1, 2, 3, 1)", format!("{}", Vec4::new(1.0, 2.0, 3.0, 1.0)));
+    }
+
+    #[test]
+    fn vec3i_arithmetic() {
+        let vector1 = Vec3i::new(1, 2, 3);
+        let vector2 = Vec3i::new(4, -5, 6);
+        assert!(Vec3i::new(5, -3, 9) == vector1 + vector2);
+        assert!(Vec3i::new(-3, 7, -3) == vector1 - vector2);
+        assert!(Vec3i::new(-1, -2, -3) == -vector1);
+        assert!(Vec3i::new(2, 4, 6) == 2 * vector1);
+        assert!(Vec3i::new(2, 4, 6) == vector1 * 2);
+        assert!(Vec3i::new(4, -10, 18) == vector1 * vector2);
+
+        let mut vector = Vec3i::ZERO;
+        vector += Vec3i::X;
+        vector -= Vec3i::Z;
+        assert!(Vec3i::new(1, 0, -1) == vector);
+    }
+
+    #[test]
+    fn vec3i_min_max() {
+        let vector1 = Vec3i::new(1, 5, -3);
+        let vector2 = Vec3i::new(2, 4, -6);
+        assert!(Vec3i::new(1, 4, -6) == vector1.min(vector2));
+        assert!(Vec3i::new(2, 5, -3) == vector1.max(vector2));
+    }
+
+    #[test]
+    fn vec3i_index() {
+        let mut vector = Vec3i::new(1, 2, 3);
+        vector[2] = 7;
+        assert_eq!([1, 2, 7], vector.as_array());
+        assert_eq!(2, vector[1]);
+    }
+
+    #[test]
+    fn vec3i_from_vec3() {
+        let vector = Vec3::new(1.7, -0.2, -3.5);
+        assert!(Vec3i::new(1, -1, -4) == Vec3i::from_vec3_floor(vector));
+        assert!(Vec3i::new(2, 0, -4) == Vec3i::from_vec3_round(vector));
+    }
+
+    #[test]
+    fn vec3i_to_vec3() {
+        let vector = Vec3i::new(1, -2, 3);
+        assert_vec3_equal!(Vec3::new(1.0, -2.0, 3.0), vector.to_vec3());
+        assert_vec3_equal!(Vec3::new(1.0, -2.0, 3.0), Vec3::from(vector));
+    }
+
+    #[test]
+    fn vec3i_hash() {
+        let mut cells = std::collections::HashSet::new();
+        cells.insert(Vec3i::new(1, 2, 3));
+        cells.insert(Vec3i::from([1, 2, 3]));
+        cells.insert(Vec3i::new(3, 2, 1));
+        assert_eq!(2, cells.len());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+// Component-wise comparisons from the approx crate: two vectors are equal
+// when every pair of components is.
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use super::Vec3;
+
+impl<T: AbsDiffEq> AbsDiffEq for Vec3<T> where T::Epsilon: Copy {
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> T::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Vec3<T>, epsilon: T::Epsilon) -> bool {
+        self.x.abs_diff_eq(&other.x, epsilon)
+        && self.y.abs_diff_eq(&other.y, epsilon)
+        && self.z.abs_diff_eq(&other.z, epsilon)
+    }
+}
+
+impl<T: RelativeEq> RelativeEq for Vec3<T> where T::Epsilon: Copy {
+    fn default_max_relative() -> T::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(&self, other: &Vec3<T>, epsilon: T::Epsilon, max_relative: T::Epsilon) -> bool {
+        self.x.relative_eq(&other.x, epsilon, max_relative)
+        && self.y.relative_eq(&other.y, epsilon, max_relative)
+        && self.z.relative_eq(&other.z, epsilon, max_relative)
+    }
+}
+
+impl<T: UlpsEq> UlpsEq for Vec3<T> where T::Epsilon: Copy {
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Vec3<T>, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+        self.x.ulps_eq(&other.x, epsilon, max_ulps)
+        && self.y.ulps_eq(&other.y, epsilon, max_ulps)
+        && self.z.ulps_eq(&other.z, epsilon, max_ulps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq};
+
+    type Vec3 = super::Vec3<f64>;
+    type Vec3f = super::Vec3<f32>;
+
+    #[test]
+    fn abs_diff_eq() {
+        let vector = Vec3::new(1.0, 2.0, 3.0);
+        assert_abs_diff_eq!(vector, Vec3::new(0.5, 1.0, 1.5) * 2.0);
+        assert_abs_diff_eq!(vector, Vec3::new(1.05, 2.0, 2.95), epsilon = 0.1);
+        assert!(!approx::abs_diff_eq!(vector, Vec3::new(1.0, 2.2, 3.0), epsilon = 0.1));
+    }
+
+    #[test]
+    fn relative_eq() {
+        let vector = Vec3::new(1e6, 2e6, -3e6);
+        assert_relative_eq!(vector, vector * (1.0 + 1e-9), max_relative = 1e-6);
+        assert_relative_ne!(vector, vector + Vec3::new(0.0, 0.0, 10.0), max_relative = 1e-9);
+        assert_relative_eq!((Vec3::X + Vec3::Y).unit_vector().length(), 1.0);
+    }
+
+    #[test]
+    fn ulps_eq() {
+        let a = Vec3::new(0.1, 0.2, 0.3);
+        assert_ulps_eq!(a + a + a, a * 3.0);
+        assert_ulps_eq!(Vec3f::new(0.1, 0.2, 0.3) * 10.0, Vec3f::new(1.0, 2.0, 3.0), max_ulps = 4);
+    }
+}
@@ -0,0 +1,45 @@
+// Vec3 and Vec4 are repr(C) structs of a single float type, so they have no
+// padding and any bit pattern is valid: buffers of them can be viewed as
+// plain bytes or floats, e.g. for GPU uploads.
+use bytemuck::{Pod, Zeroable};
+
+use super::{Vec3, Vec4};
+
+unsafe impl<T: Zeroable> Zeroable for Vec3<T> {}
+unsafe impl<T: Pod> Pod for Vec3<T> {}
+
+unsafe impl Zeroable for Vec4 {}
+unsafe impl Pod for Vec4 {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Vec4;
+
+    type Vec3 = super::Vec3<f64>;
+    type Vec3f = super::Vec3<f32>;
+
+    #[test]
+    fn cast_slice_to_floats() {
+        let vectors = [Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0)];
+        let floats: &[f64] = bytemuck::cast_slice(&vectors);
+        assert_eq!(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], floats);
+
+        let vectors = [Vec3f::new(1.0, 2.0, 3.0)];
+        assert_eq!(12, bytemuck::cast_slice::<Vec3f, u8>(&vectors).len());
+    }
+
+    #[test]
+    fn cast_floats_to_vectors() {
+        let floats = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let vectors: &[Vec4] = bytemuck::cast_slice(&floats);
+        assert_eq!(2, vectors.len());
+        assert!(vectors[1] == Vec4::new(5.0, 6.0, 7.0, 8.0));
+    }
+
+    #[test]
+    fn zeroed() {
+        let vector: Vec3 = bytemuck::Zeroable::zeroed();
+        assert!(vector == Vec3::ZERO);
+        assert_eq!(32, core::mem::size_of::<Vec4>());
+    }
+}
@@ -0,0 +1,57 @@
+// Conversions with glam: Vec3<f64> pairs with DVec3 and Vec3<f32> with
+// glam's own Vec3.
+use super::Vec3;
+
+impl From<glam::DVec3> for Vec3<f64> {
+    fn from(vector: glam::DVec3) -> Vec3<f64> {
+        Vec3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Vec3<f64>> for glam::DVec3 {
+    fn from(vector: Vec3<f64>) -> glam::DVec3 {
+        glam::DVec3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<glam::Vec3> for Vec3<f32> {
+    fn from(vector: glam::Vec3) -> Vec3<f32> {
+        Vec3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<Vec3<f32>> for glam::Vec3 {
+    fn from(vector: Vec3<f32>) -> glam::Vec3 {
+        glam::Vec3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    type Vec3 = super::Vec3<f64>;
+    type Vec3f = super::Vec3<f32>;
+
+    #[test]
+    fn dvec3_round_trip() {
+        let vector = Vec3::new(1.0, -2.0, 3.5);
+        let converted: glam::DVec3 = vector.into();
+        assert_eq!(glam::DVec3::new(1.0, -2.0, 3.5), converted);
+        assert!(vector == Vec3::from(converted));
+    }
+
+    #[test]
+    fn vec3_round_trip() {
+        let vector = Vec3f::new(1.0, -2.0, 3.5);
+        let converted: glam::Vec3 = vector.into();
+        assert_eq!(glam::Vec3::new(1.0, -2.0, 3.5), converted);
+        assert!(vector == Vec3f::from(converted));
+    }
+
+    #[test]
+    fn results_agree() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(-4.0, 0.5, 2.0);
+        let cross = glam::DVec3::from(a).cross(glam::DVec3::from(b));
+        assert!(a.cross(b) == Vec3::from(cross));
+    }
+}
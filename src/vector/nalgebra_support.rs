@@ -0,0 +1,45 @@
+// Conversions with nalgebra's column vectors.
+use nalgebra::Vector3;
+
+use super::Vec3;
+use crate::scalar::Float;
+
+impl<T: Float + nalgebra::Scalar> From<Vector3<T>> for Vec3<T> {
+    fn from(vector: Vector3<T>) -> Vec3<T> {
+        Vec3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl<T: Float + nalgebra::Scalar> From<Vec3<T>> for Vector3<T> {
+    fn from(vector: Vec3<T>) -> Vector3<T> {
+        Vector3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Vector3;
+
+    type Vec3 = super::Vec3<f64>;
+    type Vec3f = super::Vec3<f32>;
+
+    #[test]
+    fn round_trip() {
+        let vector = Vec3::new(1.0, -2.0, 3.5);
+        let converted: Vector3<f64> = vector.into();
+        assert_eq!(Vector3::new(1.0, -2.0, 3.5), converted);
+        assert!(vector == Vec3::from(converted));
+
+        let converted: Vector3<f32> = Vec3f::new(1.0, 2.0, 3.0).into();
+        assert_eq!(Vector3::new(1.0f32, 2.0, 3.0), converted);
+    }
+
+    #[test]
+    fn results_agree() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(-4.0, 0.5, 2.0);
+        let cross = Vector3::from(a).cross(&Vector3::from(b));
+        assert!(a.cross(b) == Vec3::from(cross));
+        assert_eq!(a.dot(b), Vector3::from(a).dot(&Vector3::from(b)));
+    }
+}
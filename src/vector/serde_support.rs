@@ -0,0 +1,183 @@
+// Vec3 is written as a `[x, y, z]` sequence, which keeps scene files short.
+// Human-readable formats also accept an `{ "x": .., "y": .., "z": .. }` map
+// when reading. Compact binary formats aren't self-describing, so there
+// the tuple is all that is read.
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+use super::Vec3;
+
+impl<T: Serialize> Serialize for Vec3<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(3)?;
+        tuple.serialize_element(&self.x)?;
+        tuple.serialize_element(&self.y)?;
+        tuple.serialize_element(&self.z)?;
+        tuple.end()
+    }
+}
+
+const FIELDS: &[&str] = &["x", "y", "z"];
+
+// Map keys are matched as identifiers rather than read into a String so
+// that deserializing works without an allocator.
+enum Field {
+    X,
+    Y,
+    Z,
+}
+
+struct FieldVisitor;
+
+impl<'de> Visitor<'de> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`x`, `y` or `z`")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+        match value {
+            "x" => Ok(Field::X),
+            "y" => Ok(Field::Y),
+            "z" => Ok(Field::Z),
+            other => Err(de::Error::unknown_field(other, FIELDS))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Field, D::Error> {
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct Vec3Visitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for Vec3Visitor<T> {
+    type Value = Vec3<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an [x, y, z] array or a map with x, y and z")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec3<T>, A::Error> {
+        let x = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let y = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let z = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(4, &self));
+        }
+        Ok(Vec3 { x, y, z })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Vec3<T>, A::Error> {
+        let (mut x, mut y, mut z) = (None, None, None);
+        while let Some(field) = map.next_key::<Field>()? {
+            let (slot, name) = match field {
+                Field::X => (&mut x, "x"),
+                Field::Y => (&mut y, "y"),
+                Field::Z => (&mut z, "z")
+            };
+            if slot.is_some() {
+                return Err(de::Error::duplicate_field(name));
+            }
+            *slot = Some(map.next_value()?);
+        }
+        Ok(Vec3 {
+            x: x.ok_or_else(|| de::Error::missing_field("x"))?,
+            y: y.ok_or_else(|| de::Error::missing_field("y"))?,
+            z: z.ok_or_else(|| de::Error::missing_field("z"))?
+        })
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Vec3<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Vec3<T>, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(Vec3Visitor(PhantomData))
+        }
+        else {
+            deserializer.deserialize_tuple(3, Vec3Visitor(PhantomData))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::de::value::{Error, SeqDeserializer};
+    use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+    type Vec3 = super::Vec3<f64>;
+
+    // Stands in for a binary format like bincode, which can only read what
+    // it is asked for.
+    struct Compact<'a>(&'a [f64]);
+
+    impl<'de> Deserializer<'de> for Compact<'_> {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+            Err(de::Error::custom("format is not self-describing"))
+        }
+
+        fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_seq(SeqDeserializer::new(self.0.iter().copied()))
+        }
+
+        fn is_human_readable(&self) -> bool {
+            false
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    #[test]
+    fn serialize_as_array() {
+        let json = serde_json::to_string(&Vec3::new(1.0, 2.5, -3.0)).unwrap();
+        assert_eq!("[1.0,2.5,-3.0]", json);
+    }
+
+    #[test]
+    fn deserialize_array() {
+        let result: Vec3 = serde_json::from_str("[1.0, 2.5, -3]").unwrap();
+        assert_vec3_equal!(Vec3::new(1.0, 2.5, -3.0), result);
+    }
+
+    #[test]
+    fn deserialize_map() {
+        let result: Vec3 = serde_json::from_str(r#"{"z": -3, "x": 1.0, "y": 2.5}"#).unwrap();
+        assert_vec3_equal!(Vec3::new(1.0, 2.5, -3.0), result);
+    }
+
+    #[test]
+    fn round_trip() {
+        let vector = Vec3::new(0.1, 0.2, 0.3);
+        let json = serde_json::to_string(&vector).unwrap();
+        let result: Vec3 = serde_json::from_str(&json).unwrap();
+        assert!(vector == result);
+    }
+
+    #[test]
+    fn deserialize_compact_tuple() {
+        let result = Vec3::deserialize(Compact(&[1.0, 2.5, -3.0])).unwrap();
+        assert_vec3_equal!(Vec3::new(1.0, 2.5, -3.0), result);
+        assert!(Vec3::deserialize(Compact(&[1.0, 2.0])).is_err());
+    }
+
+    #[test]
+    fn reject_malformed_input() {
+        assert!(serde_json::from_str::<Vec3>("[1.0, 2.0]").is_err());
+        assert!(serde_json::from_str::<Vec3>("[1.0, 2.0, 3.0, 4.0]").is_err());
+        assert!(serde_json::from_str::<Vec3>(r#"{"x": 1, "y": 2}"#).is_err());
+        assert!(serde_json::from_str::<Vec3>(r#"{"x": 1, "y": 2, "z": 3, "w": 4}"#).is_err());
+        assert!(serde_json::from_str::<Vec3>(r#"{"x": 1, "x": 2, "z": 3}"#).is_err());
+    }
+}